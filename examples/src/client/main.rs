@@ -1,10 +1,13 @@
 mod auth;
+mod registry;
 #[macro_use]
 mod request;
 
 use futures::prelude::*;
 use hyper::client::{Client, ResponseFuture};
-use oauth_credentials::{Credentials, Token};
+use oauth_credentials::{Credentials, Secret, Token};
+
+use crate::registry::SignerRegistry;
 
 request! {
     GET "http://127.0.0.1:8080/echo";
@@ -23,11 +26,11 @@ request! {
 
 const CLIENT: Credentials<&str> = Credentials {
     identifier: "client",
-    secret: "client_secret",
+    secret: Secret("client_secret"),
 };
 
 #[tokio::main]
-async fn main() {
+async fn main() -> anyhow::Result<()> {
     let http = Client::new();
 
     let temporary_credentials = auth::temporary_credentials(&CLIENT, "oob", &http).await;
@@ -37,16 +40,24 @@ async fn main() {
     let token = auth::token_credentials(&CLIENT, &temporary_credentials, verifier, &http).await;
     let token = Token::new(CLIENT, token);
 
-    let res1 = GetEcho { foo: "GET" }.send(&token, &http);
+    let mut signers = SignerRegistry::new();
+    signers.insert(
+        "127.0.0.1",
+        oauth::Builder::with_token(token, oauth::HMAC_SHA1),
+    );
+
+    let res1 = GetEcho { foo: "GET" }.send(&signers, &http)?;
     let res2 = PostEcho {
         bar: "POST",
         baz: "ＰＯＳＴ",
     }
-    .send(&token, &http);
+    .send(&signers, &http)?;
 
     let (res1, res2) = future::join(to_string(res1), to_string(res2)).await;
     println!("{}", res1);
     println!("{}", res2);
+
+    Ok(())
 }
 
 async fn to_string(res: ResponseFuture) -> String {
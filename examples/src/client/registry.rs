@@ -0,0 +1,92 @@
+//! A registry of signers keyed by hostname.
+//!
+//! This is a sketch of how to sign requests to several providers with one HTTP client, rather
+//! than a complete implementation. For example, it doesn't tell apart the ports or the schemes.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use oauth::signature_method::SignatureMethod;
+
+/// Maps hostnames to the `oauth::Builder`s (i.e. credentials, options and signature method) to
+/// sign requests to them with, so that a single HTTP client can talk to multiple OAuth 1.0
+/// providers with different settings.
+///
+/// Use `oauth::signature_method::AnyMethod` as `SM` if the providers use different signature
+/// methods.
+pub struct SignerRegistry<'a, SM, C = String, T = C> {
+    signers: HashMap<String, oauth::Builder<'a, SM, C, T>>,
+}
+
+impl<'a, SM, C, T> SignerRegistry<'a, SM, C, T>
+where
+    SM: SignatureMethod + Clone,
+    C: AsRef<str>,
+    T: AsRef<str>,
+{
+    pub fn new() -> Self {
+        SignerRegistry {
+            signers: HashMap::new(),
+        }
+    }
+
+    /// Registers `signer` for requests to `host`, returning the signer previously registered for
+    /// the host, if any.
+    pub fn insert(
+        &mut self,
+        host: &str,
+        signer: oauth::Builder<'a, SM, C, T>,
+    ) -> Option<oauth::Builder<'a, SM, C, T>> {
+        self.signers.insert(host.to_ascii_lowercase(), signer)
+    }
+
+    /// Returns the signer registered for `host`.
+    pub fn get(&self, host: &str) -> Option<&oauth::Builder<'a, SM, C, T>> {
+        self.signers.get(&*host.to_ascii_lowercase())
+    }
+
+    /// Authorizes a request to `uri` with the signer registered for its host, returning an
+    /// `Authorization` header value.
+    ///
+    /// `uri` must not contain a query part, which would result in a wrong signature.
+    pub fn authorize<R>(
+        &self,
+        method: &http::Method,
+        uri: &http::Uri,
+        request: &R,
+    ) -> Result<String, UnknownHost>
+    where
+        R: oauth::Request + ?Sized,
+    {
+        let signer = uri
+            .host()
+            .and_then(|host| self.get(host))
+            .ok_or_else(|| UnknownHost(uri.clone()))?;
+        Ok(signer.authorize(method.as_str(), uri, request))
+    }
+}
+
+impl<'a, SM, C, T> Default for SignerRegistry<'a, SM, C, T>
+where
+    SM: SignatureMethod + Clone,
+    C: AsRef<str>,
+    T: AsRef<str>,
+{
+    fn default() -> Self {
+        SignerRegistry::new()
+    }
+}
+
+/// An error returned by `SignerRegistry::authorize` when no signer is registered for the host of
+/// the URI.
+#[derive(Debug)]
+pub struct UnknownHost(pub http::Uri);
+
+impl Display for UnknownHost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "no signer is registered for the host of `{}`", self.0)
+    }
+}
+
+impl Error for UnknownHost {}
@@ -1,7 +1,8 @@
 use http::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
-use oauth_credentials::Token;
 use tower_service::Service;
 
+use crate::registry::{SignerRegistry, UnknownHost};
+
 /// Defines a struct and associete it with a request method and URI of an API endpoint.
 macro_rules! request {
     ($(
@@ -17,18 +18,19 @@ macro_rules! request {
         }
 
         impl $(<$($param)*>)? $Name $(<$($param)*>)? {
-            pub fn send<C, T, S, B>(
+            pub fn send<SM, C, T, S, B>(
                 &self,
-                token: &oauth_credentials::Token<C, T>,
+                signers: &$crate::registry::SignerRegistry<'_, SM, C, T>,
                 http: S,
-            ) -> S::Future
+            ) -> Result<S::Future, $crate::registry::UnknownHost>
             where
+                SM: oauth::signature_method::SignatureMethod + Clone,
                 C: AsRef<str>,
                 T: AsRef<str>,
                 S: tower_service::Service<http::Request<B>>,
                 B: Default + From<Vec<u8>>,
             {
-                $crate::request::SendRequest::send(self, token, http)
+                $crate::request::SendRequest::send(self, signers, http)
             }
         }
 
@@ -45,30 +47,39 @@ pub trait SendRequest: oauth::Request {
     const METHOD: http::Method;
     const URI: &'static str;
 
-    fn send<C, T, S, B>(&self, token: &Token<C, T>, http: S) -> S::Future
+    fn send<SM, C, T, S, B>(
+        &self,
+        signers: &SignerRegistry<'_, SM, C, T>,
+        http: S,
+    ) -> Result<S::Future, UnknownHost>
     where
+        SM: oauth::signature_method::SignatureMethod + Clone,
         C: AsRef<str>,
         T: AsRef<str>,
         S: Service<http::Request<B>>,
         B: Default + From<Vec<u8>>,
     {
-        send::<Self, _, _, _>(self, token.as_ref(), http)
+        send::<Self, _, _, _, _, _, _>(self, signers, http)
     }
 }
 
-fn send<SR, R, S, B>(request: R, token: Token<&str, &str>, mut http: S) -> S::Future
+fn send<SR, R, SM, C, T, S, B>(
+    request: R,
+    signers: &SignerRegistry<'_, SM, C, T>,
+    mut http: S,
+) -> Result<S::Future, UnknownHost>
 where
     SR: SendRequest + ?Sized,
     R: oauth::Request,
+    SM: oauth::signature_method::SignatureMethod + Clone,
+    C: AsRef<str>,
+    T: AsRef<str>,
     S: Service<http::Request<B>>,
     B: Default + From<Vec<u8>>,
 {
     let uri = http::Uri::from_static(SR::URI);
 
-    let mut builder = oauth::Builder::new(token.client, oauth::HMAC_SHA1);
-    builder.token(token.token);
-
-    let authorization = builder.authorize(SR::METHOD.as_str(), SR::URI, &request);
+    let authorization = signers.authorize(&SR::METHOD, &uri, &request)?;
 
     let is_post = SR::METHOD == http::Method::POST;
     let req = http::Request::builder()
@@ -88,5 +99,5 @@ where
         req.uri(uri).body(Default::default()).unwrap()
     };
 
-    http.call(req)
+    Ok(http.call(req))
 }
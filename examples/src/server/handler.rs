@@ -6,7 +6,7 @@ use hmac::{Hmac, Mac, NewMac};
 use http::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use http::uri::Authority;
 use hyper::{Body, Request, Response, StatusCode};
-use oauth_credentials::Credentials;
+use oauth_credentials::{Credentials, Secret};
 use percent_encoding::{percent_decode, percent_encode, AsciiSet};
 use serde::Serialize;
 use sha1::Sha1;
@@ -15,16 +15,16 @@ use crate::authorization;
 
 const CLIENT: Credentials<&str> = Credentials {
     identifier: "client",
-    secret: "client_secret",
+    secret: Secret("client_secret"),
 };
 const REQUEST: Credentials<&str> = Credentials {
     identifier: "request",
-    secret: "request_secret",
+    secret: Secret("request_secret"),
 };
 const VERIFIER: &str = "verifier";
 const TOKEN: Credentials<&str> = Credentials {
     identifier: "token",
-    secret: "token_secret",
+    secret: Secret("token_secret"),
 };
 
 const SCHEME: &str = "http";
@@ -261,12 +261,13 @@ where
                 let signature = &signature[..SIGN_LEN];
 
                 let mut mac = {
-                    let capacity = client.secret.len() + 1 + token.map_or(0, |t| t.secret.len());
+                    let capacity =
+                        client.secret().len() + 1 + token.map_or(0, |t| t.secret().len());
                     let mut key = Vec::with_capacity(capacity);
-                    key.extend(client.secret.as_bytes());
+                    key.extend(client.secret().as_bytes());
                     key.push(b'&');
                     if let Some(t) = token {
-                        key.extend(t.secret.as_bytes());
+                        key.extend(t.secret().as_bytes());
                     }
                     Hmac::<Sha1>::new_from_slice(&key).unwrap()
                 };
@@ -290,10 +291,10 @@ where
                 use nom::combinator::eof;
 
                 let parser = move |input| {
-                    let (input, _) = tag::<_, _, ()>(client.secret)(input)?;
+                    let (input, _) = tag::<_, _, ()>(client.secret.into_inner())(input)?;
                     let (mut input, _) = tag("&")(input)?;
                     if let Some(token) = token {
-                        input = tag(token.secret)(input)?.0;
+                        input = tag(token.secret.into_inner())(input)?.0;
                     }
                     eof(input)
                 };
@@ -20,6 +20,8 @@ use std::fmt::{self, Debug, Formatter};
 
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 use alloc::string::String;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
 
 /// An OAuth "credentials" pair defined in [RFC 5849 section 1.1][rfc].
 ///
@@ -39,13 +41,13 @@ use alloc::string::String;
 /// token credentials, you first obtain a set of temporary credentials using the client
 /// credentials. And after the resource owner approves the authorization request, you use the
 /// temporary credentials to request a set of token credentials from the server.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg(feature = "alloc")]
 pub struct Credentials<T = String> {
     /// The unique identifier part of the credentials pair.
     pub identifier: T,
     /// The shared secret part of the credentials pair.
-    pub secret: T,
+    pub secret: Secret<T>,
 }
 
 // XXX: These almost-identical (modulo default type param) items should certainly be defined with a
@@ -72,18 +74,36 @@ pub struct Credentials<T = String> {
 /// token credentials, you first obtain a set of temporary credentials using the client
 /// credentials. And after the resource owner approves the authorization request, you use the
 /// temporary credentials to request a set of token credentials from the server.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg(not(feature = "alloc"))]
 pub struct Credentials<T> {
     /// The unique identifier part of the credentials pair.
     pub identifier: T,
     /// The shared secret part of the credentials pair.
-    pub secret: T,
+    pub secret: Secret<T>,
+}
+
+/// A response from the Temporary Credential Request and Token Request endpoints described in
+/// [RFC 5849 section 2][rfc], along with any extra parameters the server returned.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-2
+///
+/// Servers commonly return parameters other than the credentials pair, like
+/// `oauth_callback_confirmed`, `user_id` or `screen_name`. Unlike deserializing a [Credentials],
+/// deserializing this type keeps those parameters in `extras`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg(feature = "alloc")]
+pub struct CredentialsResponse<T = String> {
+    /// The credentials pair (`oauth_token` and `oauth_token_secret`).
+    pub credentials: Credentials<T>,
+    /// The parameters other than `oauth_token` and `oauth_token_secret`, in the order they
+    /// appeared in the response.
+    pub extras: Vec<(T, T)>,
 }
 
 /// A set of OAuth client credentials and token/temporary credentials used for authorizing requests
 /// on behalf of a resource owner.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg(feature = "alloc")]
 pub struct Token<C = String, T = C> {
     /// Client credentials.
@@ -94,7 +114,7 @@ pub struct Token<C = String, T = C> {
 
 /// A set of OAuth client credentials and token/temporary credentials used for authorizing requests
 /// on behalf of a resource owner.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg(not(feature = "alloc"))]
 pub struct Token<C, T = C> {
     /// Client credentials.
@@ -103,12 +123,51 @@ pub struct Token<C, T = C> {
     pub token: Credentials<T>,
 }
 
+/// A wrapper around a secret value whose `Debug` representation doesn't reveal the value.
+///
+/// `Credentials` stores its `secret` in this type. Use it for the secrets you store elsewhere as
+/// well, so that `#[derive(Debug)]` on the types embedding them doesn't leak the secrets into
+/// logs.
+///
+/// # Example
+///
+/// ```
+/// use oauth_credentials::Secret;
+///
+/// #[derive(Debug)]
+/// struct Client {
+///     consumer_key: String,
+///     consumer_secret: Secret<String>,
+/// }
+///
+/// let client = Client {
+///     consumer_key: "key".to_owned(),
+///     consumer_secret: Secret::new("secret".to_owned()),
+/// };
+/// assert_eq!(client.consumer_secret.expose(), "secret");
+/// assert_eq!(
+///     format!("{:?}", client),
+///     r#"Client { consumer_key: "key", consumer_secret: [redacted] }"#,
+/// );
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Secret<T>(pub T);
+
+/// An error returned by [`Credentials::from_env`] and [`Token::from_env`] when an environment
+/// variable is not present or not valid Unicode.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FromEnvError {
+    var: String,
+    source: std::env::VarError,
+}
+
 impl<T: AsRef<str>> Credentials<T> {
     /// Creates a new `Credentials`.
     pub fn new(identifier: T, secret: T) -> Self {
         Credentials {
             identifier: identifier,
-            secret: secret,
+            secret: Secret(secret),
         }
     }
 
@@ -119,7 +178,7 @@ impl<T: AsRef<str>> Credentials<T> {
 
     /// Returns the shared secret part of the credentials pair.
     pub fn secret(&self) -> &str {
-        self.secret.as_ref()
+        self.secret.expose()
     }
 
     /// Converts from `&Credentials<T>` to `Credentials<&str>`.
@@ -150,38 +209,60 @@ impl<T> Credentials<T> {
         F: FnMut(T) -> U,
         U: AsRef<str>,
     {
-        Credentials::new(f(self.identifier), f(self.secret))
+        Credentials::new(f(self.identifier), f(self.secret.0))
     }
 }
 
-impl<'a, T: AsRef<str>> From<&'a Credentials<T>> for Credentials<&'a str> {
-    fn from(credentials: &'a Credentials<T>) -> Self {
-        credentials.as_ref()
+#[cfg(feature = "std")]
+impl Credentials<String> {
+    /// Reads the credentials from the environment variables named `identifier_var` and
+    /// `secret_var`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use oauth_credentials::Credentials;
+    /// # std::env::set_var("APP_CONSUMER_KEY", "key");
+    /// # std::env::set_var("APP_CONSUMER_SECRET", "secret");
+    /// let client = Credentials::from_env("APP_CONSUMER_KEY", "APP_CONSUMER_SECRET").unwrap();
+    /// assert_eq!(client.identifier(), "key");
+    ///
+    /// let e = Credentials::from_env("APP_CONSUMER_KEY", "APP_UNDEFINED").unwrap_err();
+    /// assert_eq!(e.var_name(), "APP_UNDEFINED");
+    /// ```
+    pub fn from_env(identifier_var: &str, secret_var: &str) -> Result<Self, FromEnvError> {
+        Ok(Credentials::new(
+            try!(FromEnvError::var(identifier_var)),
+            try!(FromEnvError::var(secret_var)),
+        ))
     }
-}
-
-impl<T: Debug> Debug for Credentials<T> {
-    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
-        struct Hidden;
-        impl Debug for Hidden {
-            fn fmt<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
-                f.write_str("<hidden>")
-            }
-        }
 
-        #[derive(Debug)]
-        struct Credentials<T> {
-            #[allow(dead_code)]
-            identifier: T,
-            #[allow(dead_code)]
-            secret: Hidden,
-        }
+    /// Reads the shared secret of the credentials identified by `identifier` from the entry for
+    /// `service` and `identifier` in the platform's credential store.
+    ///
+    /// This uses the default credential store of the `keyring` crate, which depends on the
+    /// platform-specific features of `keyring` enabled in your dependency graph (e.g.
+    /// `apple-native` or `sync-secret-service`). Without any of them, `keyring` only provides a
+    /// mock store that doesn't persist the secrets.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use oauth_credentials::Credentials;
+    /// let client = Credentials::from_keyring("my-app", "consumer_key").unwrap();
+    /// assert_eq!(client.identifier(), "consumer_key");
+    /// ```
+    #[cfg(feature = "keyring")]
+    pub fn from_keyring(service: &str, identifier: &str) -> Result<Self, keyring::Error> {
+        let entry = try!(keyring::Entry::new(service, identifier));
+        let secret = try!(entry.get_password());
+        Ok(Credentials::new(identifier.into(), secret))
+    }
+}
 
-        Credentials {
-            identifier: &self.identifier,
-            secret: Hidden,
-        }
-        .fmt(f)
+impl<'a, T: AsRef<str>> From<&'a Credentials<T>> for Credentials<&'a str> {
+    fn from(credentials: &'a Credentials<T>) -> Self {
+        credentials.as_ref()
     }
 }
 
@@ -300,6 +381,42 @@ impl<T> Token<T> {
     }
 }
 
+#[cfg(feature = "std")]
+impl Token<String> {
+    /// Reads the client and token credentials from the environment variables of the given names.
+    ///
+    /// See [`Credentials::from_env`] for details.
+    pub fn from_env(
+        client_identifier_var: &str,
+        client_secret_var: &str,
+        token_var: &str,
+        token_secret_var: &str,
+    ) -> Result<Self, FromEnvError> {
+        Ok(Token::new(
+            try!(Credentials::from_env(
+                client_identifier_var,
+                client_secret_var
+            )),
+            try!(Credentials::from_env(token_var, token_secret_var)),
+        ))
+    }
+
+    /// Reads the client and token secrets from the platform's credential store.
+    ///
+    /// See [`Credentials::from_keyring`] for details.
+    #[cfg(feature = "keyring")]
+    pub fn from_keyring(
+        service: &str,
+        client_identifier: &str,
+        token: &str,
+    ) -> Result<Self, keyring::Error> {
+        Ok(Token::new(
+            try!(Credentials::from_keyring(service, client_identifier)),
+            try!(Credentials::from_keyring(service, token)),
+        ))
+    }
+}
+
 impl<'a, 'b> Token<&'a str, &'b str> {
     /// Creates a new `Token<&str, &str>` from a pair of `&Credentials<_>`.
     pub fn from_ref<C: AsRef<str>, T: AsRef<str>>(
@@ -315,3 +432,102 @@ impl<'a, C: AsRef<str>, T: AsRef<str>> From<&'a Token<C, T>> for Token<&'a str>
         token.as_ref()
     }
 }
+
+impl<T> Secret<T> {
+    /// Creates a new `Secret`.
+    pub fn new(secret: T) -> Self {
+        Secret(secret)
+    }
+
+    /// Unwraps the secret value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: AsRef<str>> Secret<T> {
+    /// Returns the secret as a string slice.
+    pub fn expose(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<T: AsRef<str>> AsRef<str> for Secret<T> {
+    fn as_ref(&self) -> &str {
+        self.expose()
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(secret: T) -> Self {
+        Secret(secret)
+    }
+}
+
+impl<T> Debug for Secret<T> {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+#[cfg(feature = "std")]
+impl FromEnvError {
+    fn var(name: &str) -> Result<String, Self> {
+        std::env::var(name).map_err(|source| FromEnvError {
+            var: name.into(),
+            source: source,
+        })
+    }
+
+    /// Returns the name of the environment variable that could not be read.
+    pub fn var_name(&self) -> &str {
+        &self.var
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for FromEnvError {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
+        write!(f, "environment variable `{}`: {}", self.var, self.source)
+    }
+}
+
+// `source` and `dyn` are not available on the oldest supported toolchains, so this implements the
+// deprecated `description` and `cause` instead.
+#[cfg(feature = "std")]
+impl std::error::Error for FromEnvError {
+    fn description(&self) -> &str {
+        self.source.description()
+    }
+
+    #[allow(bare_trait_objects)]
+    fn cause(&self) -> Option<&std::error::Error> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AsRef<str>> CredentialsResponse<T> {
+    /// Creates a new `CredentialsResponse` without extra parameters.
+    pub fn new(credentials: Credentials<T>) -> Self {
+        CredentialsResponse {
+            credentials: credentials,
+            extras: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the first extra parameter named `key`, if any.
+    pub fn extra(&self, key: &str) -> Option<&str> {
+        self.extras
+            .iter()
+            .find(|entry| entry.0.as_ref() == key)
+            .map(|entry| entry.1.as_ref())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<CredentialsResponse<T>> for Credentials<T> {
+    fn from(response: CredentialsResponse<T>) -> Self {
+        response.credentials
+    }
+}
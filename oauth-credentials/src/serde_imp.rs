@@ -3,10 +3,16 @@ extern crate serde;
 use std::fmt::{self, Formatter};
 use std::marker::PhantomData;
 
+#[cfg(feature = "alloc")]
+use self::serde::ser::SerializeMap;
 use self::serde::ser::SerializeStruct;
 use self::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use super::Credentials;
+#[cfg(feature = "alloc")]
+use super::CredentialsResponse;
+use super::{Credentials, Secret};
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
 
 enum Field {
     Identifier,
@@ -26,7 +32,7 @@ const SECRET: &'static str = "oauth_token_secret";
 /// # Example
 ///
 /// ```
-/// use oauth_credentials::Credentials;
+/// use oauth_credentials::{Credentials, Secret};
 ///
 /// # fn main() -> Result<(), serde_urlencoded::de::Error> {
 /// // Response body from a Temporary Credential Request endpoint.
@@ -36,7 +42,7 @@ const SECRET: &'static str = "oauth_token_secret";
 ///     deserialized.as_ref(),
 ///     Credentials {
 ///         identifier: "token",
-///         secret: "secret",
+///         secret: Secret("secret"),
 ///     }
 /// ));
 /// # Ok(())
@@ -59,7 +65,7 @@ const SECRET: &'static str = "oauth_token_secret";
 /// # #[macro_use]
 /// # extern crate serde_derive;
 /// #
-/// use oauth_credentials::Credentials;
+/// use oauth_credentials::{Credentials, Secret};
 /// use serde::Deserialize;
 ///
 /// #[derive(Deserialize)]
@@ -68,7 +74,7 @@ const SECRET: &'static str = "oauth_token_secret";
 ///     #[serde(rename = "client_identifier")]
 ///     identifier: T,
 ///     #[serde(rename = "client_secret")]
-///     secret: T,
+///     secret: Secret<T>,
 /// }
 ///
 /// #[derive(Deserialize)]
@@ -81,7 +87,7 @@ const SECRET: &'static str = "oauth_token_secret";
 ///     deserialized.as_ref(),
 ///     Credentials {
 ///         identifier: "client",
-///         secret: "secret",
+///         secret: Secret("secret"),
 ///     }
 /// ));
 /// # Ok(())
@@ -162,10 +168,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Credentials<T> {
 /// }
 ///
 /// # fn main() -> Result<(), serde_urlencoded::ser::Error> {
-/// let client = Credentials {
-///     identifier: "token",
-///     secret: "secret",
-/// };
+/// let client = Credentials::new("token", "secret");
 /// // Create a response body of a Temporary Credential Request endpoint.
 /// let response = TemporaryCredentialResponse {
 ///     credentials: client,
@@ -194,7 +197,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Credentials<T> {
 /// # #[macro_use]
 /// # extern crate serde_derive;
 /// #
-/// use oauth_credentials::Credentials;
+/// use oauth_credentials::{Credentials, Secret};
 /// use serde::Serialize;
 ///
 /// #[derive(Serialize)]
@@ -203,17 +206,14 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Credentials<T> {
 ///     #[serde(rename = "client_identifier")]
 ///     identifier: T,
 ///     #[serde(rename = "client_secret")]
-///     secret: T,
+///     secret: Secret<T>,
 /// }
 ///
 /// #[derive(Serialize)]
 /// struct Helper<'a>(#[serde(with = "CredentialsDef")] Credentials<&'a str>);
 ///
 /// # fn main() -> Result<(), serde_json::Error> {
-/// let client = Credentials {
-///     identifier: "client",
-///     secret: "secret",
-/// };
+/// let client = Credentials::new("client", "secret");
 /// let serialized = serde_json::to_string(&Helper(client.as_ref()))?;
 /// let expected = r#"{"client_identifier":"client","client_secret":"secret"}"#;
 /// assert_eq!(serialized, expected);
@@ -235,6 +235,122 @@ where
     }
 }
 
+/// Deserializes a `CredentialsResponse` from a response from the Temporary Credential Request
+/// and Token Request endpoints described in [RFC 5849 section 2][rfc].
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-2
+///
+/// # Example
+///
+/// ```
+/// use oauth_credentials::CredentialsResponse;
+///
+/// # fn main() -> Result<(), serde_urlencoded::de::Error> {
+/// // Response body from a Token Request endpoint.
+/// let response = b"oauth_token=token&oauth_token_secret=secret&user_id=42&screen_name=alice";
+/// let deserialized: CredentialsResponse = serde_urlencoded::from_bytes(response)?;
+/// assert_eq!(deserialized.credentials.identifier(), "token");
+/// assert_eq!(deserialized.credentials.secret(), "secret");
+/// assert_eq!(deserialized.extra("user_id"), Some("42"));
+/// assert_eq!(deserialized.extra("screen_name"), Some("alice"));
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+impl<'de, T> Deserialize<'de> for CredentialsResponse<T>
+where
+    T: Deserialize<'de> + AsRef<str>,
+{
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct Visitor<T>(PhantomData<T>);
+
+        impl<'de, T> de::Visitor<'de> for Visitor<T>
+        where
+            T: Deserialize<'de> + AsRef<str>,
+        {
+            type Value = CredentialsResponse<T>;
+
+            fn expecting<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut identifier = None;
+                let mut secret = None;
+                let mut extras = Vec::new();
+
+                while let Some(k) = try!(map.next_key::<T>()) {
+                    if k.as_ref() == IDENTIFIER {
+                        if identifier.is_some() {
+                            return Err(de::Error::duplicate_field(IDENTIFIER));
+                        }
+                        identifier = Some(try!(map.next_value()));
+                    } else if k.as_ref() == SECRET {
+                        if secret.is_some() {
+                            return Err(de::Error::duplicate_field(SECRET));
+                        }
+                        secret = Some(try!(map.next_value()));
+                    } else {
+                        extras.push((k, try!(map.next_value())));
+                    }
+                }
+
+                let identifier =
+                    try!(identifier.ok_or_else(|| de::Error::missing_field(IDENTIFIER)));
+                let secret = try!(secret.ok_or_else(|| de::Error::missing_field(SECRET)));
+
+                Ok(CredentialsResponse {
+                    credentials: Credentials {
+                        identifier: identifier,
+                        secret: secret,
+                    },
+                    extras: extras,
+                })
+            }
+        }
+
+        d.deserialize_map(Visitor(PhantomData))
+    }
+}
+
+/// Serializes a `CredentialsResponse` into a response of the Temporary Credential Request
+/// and Token Request endpoints described in [RFC 5849 section 2][rfc].
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-2
+///
+/// The extra parameters are serialized after the credentials pair, in order.
+#[cfg(feature = "alloc")]
+impl<T: Serialize> Serialize for CredentialsResponse<T> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = try!(s.serialize_map(Some(2 + self.extras.len())));
+        try!(s.serialize_entry(IDENTIFIER, &self.credentials.identifier));
+        try!(s.serialize_entry(SECRET, &self.credentials.secret));
+        for entry in &self.extras {
+            try!(s.serialize_entry(&entry.0, &entry.1));
+        }
+        s.end()
+    }
+}
+
+/// Serializes the secret value as-is.
+impl<T: Serialize> Serialize for Secret<T> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(s)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Secret<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        T::deserialize(d).map(Secret)
+    }
+}
+
 impl<'de> Deserialize<'de> for Field {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         struct Visitor;
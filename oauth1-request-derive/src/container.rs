@@ -3,5 +3,6 @@ use syn::ExprPath;
 def_meta! {
     pub struct ContainerMeta {
         pub krate as "crate": Option<ExprPath>,
+        pub validate: Option<ExprPath>,
     }
 }
@@ -0,0 +1,39 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::field::Field;
+
+/// The body of `Describe::describe` method, generated when the `reflect` feature is enabled.
+pub struct DescribeBody<'a> {
+    fields: &'a [Field],
+}
+
+impl<'a> DescribeBody<'a> {
+    pub fn new(fields: &'a [Field]) -> Self {
+        DescribeBody { fields }
+    }
+}
+
+impl<'a> ToTokens for DescribeBody<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let parameters = self.fields.iter().filter(|f| !f.meta.skip).map(|f| {
+            let name = f.name();
+            let optional = f.is_option() || f.meta.skip_if.is_some();
+            let encoded = f.meta.encoded;
+            quote! {
+                _oauth1_request::request::reflect::ParameterInfo::new(#name)
+                    .optional(#optional)
+                    .encoded(#encoded)
+            }
+        });
+
+        // The `ParameterInfo` constructors are `const fn`s, which are not subject to promotion to
+        // `'static`, so define the slice as a constant item.
+        tokens.extend(quote! {
+            const PARAMETERS: &[_oauth1_request::request::reflect::ParameterInfo] = &[
+                #(#parameters,)*
+            ];
+            PARAMETERS
+        });
+    }
+}
@@ -1,7 +1,7 @@
 use proc_macro2::{Literal, Span, TokenStream};
 use quote::{ToTokens, TokenStreamExt};
 use syn::ext::IdentExt;
-use syn::{ExprPath, Ident, LitBool, LitStr, Type};
+use syn::{ExprPath, Ident, LitBool, LitStr, PathArguments, Type};
 
 use crate::meta::UriSafe;
 
@@ -13,12 +13,14 @@ pub struct Field {
 
 def_meta! {
     pub struct FieldMeta {
+        pub allow_oauth_prefix: bool,
         pub encoded: bool,
         pub fmt: Option<ExprPath>,
         pub option: Option<LitBool>,
         pub rename: Option<UriSafe>,
         pub skip: bool,
         pub skip_if: Option<ExprPath>,
+        pub validate: Option<ExprPath>,
     }
 }
 
@@ -37,6 +39,15 @@ impl Field {
         Self { ident, ty, meta }
     }
 
+    /// Returns whether the field is an `Option` whose `None` value is not serialized.
+    pub fn is_option(&self) -> bool {
+        self.meta
+            .option
+            .as_ref()
+            .map(|v| v.value)
+            .unwrap_or_else(|| is_option(&self.ty))
+    }
+
     /// Returns the (`rename`-ed) field name.
     pub fn name(&self) -> Name<'_> {
         if let Some(ref name) = self.meta.rename {
@@ -79,3 +90,24 @@ impl<'a> ToTokens for Name<'a> {
         }
     }
 }
+
+fn is_option(mut ty: &Type) -> bool {
+    // Types that are interpolated through `macro_rules!` may be enclosed in a `Group`.
+    // <https://github.com/rust-lang/rust/pull/72388>
+    while let Type::Group(ref g) = *ty {
+        ty = &g.elem;
+    }
+
+    if let Type::Path(ref ty_path) = *ty {
+        let path = &ty_path.path;
+        path.leading_colon.is_none()
+            && path.segments.len() == 1
+            && path.segments[0].ident == "Option"
+            && match path.segments[0].arguments {
+                PathArguments::AngleBracketed(ref args) => args.args.len() == 1,
+                PathArguments::None | PathArguments::Parenthesized(_) => false,
+            }
+    } else {
+        false
+    }
+}
@@ -20,9 +20,11 @@
 mod meta;
 
 mod container;
+mod describe;
 mod field;
 mod method_body;
 mod util;
+mod validate;
 
 use proc_macro2::{Span, TokenStream};
 use proc_macro_crate::FoundCrate;
@@ -35,8 +37,10 @@ use syn::{
 };
 
 use self::container::ContainerMeta;
+use self::describe::DescribeBody;
 use self::field::Field;
 use self::method_body::MethodBody;
+use self::validate::ValidateBody;
 
 /// A derive macro for [`oauth1_request::Request`][Request] trait.
 ///
@@ -113,6 +117,34 @@ fn expand_derive_oauth1_authorize(mut input: DeriveInput) -> TokenStream {
 
     let mut fields: Vec<_> = fields.named.into_iter().map(Field::new).collect();
 
+    // Parameter names beginning with `oauth_` are reserved for the protocol parameters.
+    for f in &fields {
+        if f.meta.skip || f.meta.allow_oauth_prefix {
+            continue;
+        }
+        let name = f.name();
+        let (name, span) = (name.string_value(), name.span());
+        if name.starts_with("oauth_") {
+            emit_error!(
+                span,
+                "parameter name \"{}\" begins with reserved prefix `oauth_`; \
+                 add `#[oauth1(allow_oauth_prefix)]` to allow it",
+                name,
+            );
+        }
+    }
+
+    // Generate this before sorting the fields so that they are validated in the declaration order.
+    let validate = ValidateBody::new(meta.validate.as_ref(), &fields).map(|body| {
+        quote_spanned! {Span::mixed_site()=>
+            fn validate(
+                &self,
+            ) -> ::core::result::Result<(), _oauth1_request::request::ValidationError> {
+                #body
+            }
+        }
+    });
+
     fields.sort_by_cached_key(|f| f.name().string_value());
     fields.iter().fold(String::new(), |prev_name, f| {
         let name = f.name();
@@ -127,6 +159,22 @@ fn expand_derive_oauth1_authorize(mut input: DeriveInput) -> TokenStream {
 
     let body = MethodBody::new(&fields);
 
+    let describe = if cfg!(feature = "reflect") {
+        let body = DescribeBody::new(&fields);
+        Some(quote_spanned! {Span::mixed_site()=>
+            #[automatically_derived]
+            impl #impl_generics _oauth1_request::request::reflect::Describe for #name #ty_generics
+                #where_clause
+            {
+                fn describe() -> &'static [_oauth1_request::request::reflect::ParameterInfo] {
+                    #body
+                }
+            }
+        })
+    } else {
+        None
+    };
+
     quote_spanned! {Span::mixed_site()=>
         const _: () = {
             #use_oauth1_request
@@ -143,7 +191,11 @@ fn expand_derive_oauth1_authorize(mut input: DeriveInput) -> TokenStream {
                 {
                     #body
                 }
+
+                #validate
             }
+
+            #describe
         };
     }
 }
@@ -0,0 +1,53 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::{ExprPath, Ident};
+
+use crate::field::Field;
+
+/// The body of `Request::validate` method, generated when the container or any of the fields has
+/// a `validate` attribute.
+pub struct ValidateBody<'a> {
+    container: Option<&'a ExprPath>,
+    fields: &'a [Field],
+}
+
+impl<'a> ValidateBody<'a> {
+    /// Returns `None` if there is nothing to validate.
+    pub fn new(container: Option<&'a ExprPath>, fields: &'a [Field]) -> Option<Self> {
+        if container.is_none() && fields.iter().all(|f| f.meta.validate.is_none()) {
+            None
+        } else {
+            Some(ValidateBody { container, fields })
+        }
+    }
+}
+
+impl<'a> ToTokens for ValidateBody<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let this = Ident::new("self", Span::mixed_site());
+        let e = Ident::new("e", Span::mixed_site());
+
+        for f in self.fields {
+            if let Some(ref validate) = f.meta.validate {
+                let ident = &f.ident;
+                let name = f.name();
+                tokens.extend(quote_spanned! {validate.span()=>
+                    if let ::core::result::Result::Err(#e) = #validate(&#this.#ident) {
+                        return ::core::result::Result::Err(#e.with_parameter(#name));
+                    }
+                });
+            }
+        }
+
+        if let Some(validate) = self.container {
+            tokens.extend(quote_spanned! {validate.span()=>
+                #validate(#this)?;
+            });
+        }
+
+        tokens.extend(quote! {
+            ::core::result::Result::Ok(())
+        });
+    }
+}
@@ -66,6 +66,7 @@ assert_expand! {
 assert_expand! {
     #[derive(oauth::Request)]
     struct OAuthPrefix[][] {
+        #[oauth1(allow_oauth_prefix)]
         oauth_prefix: u64,
     }
     |this, mut ser| {
@@ -117,6 +118,51 @@ assert_expand! {
     }
 }
 
+assert_expand! {
+    #[derive(oauth::Request)]
+    struct ManyFields[][T = u64] {
+        a: u8,
+        b: u8,
+        c: u8,
+        d: u8,
+        e: u8,
+        f: u8,
+        g: u8,
+        h: u8,
+        i: u8,
+        j: u8,
+        k: u8,
+        l: u8,
+        m: u8,
+        n: u8,
+        #[oauth1(fmt = crate::common::fmt_str)]
+        p: &'static str = "p",
+        #[oauth1(option = true)]
+        q: std::option::Option<T>,
+        r: T,
+    }
+    |this, mut ser| {
+        ser.serialize_parameter("a", this.a);
+        ser.serialize_parameter("b", this.b);
+        ser.serialize_parameter("c", this.c);
+        ser.serialize_parameter("d", this.d);
+        ser.serialize_parameter("e", this.e);
+        ser.serialize_parameter("f", this.f);
+        ser.serialize_parameter("g", this.g);
+        ser.serialize_parameter("h", this.h);
+        ser.serialize_parameter("i", this.i);
+        ser.serialize_parameter("j", this.j);
+        ser.serialize_parameter("k", this.k);
+        ser.serialize_parameter("l", this.l);
+        ser.serialize_parameter("m", this.m);
+        ser.serialize_parameter("n", this.n);
+        ser.serialize_oauth_parameters();
+        ser.serialize_parameter("p", this.p);
+        ser.serialize_parameter("r", &this.r);
+        ser.end()
+    }
+}
+
 // Just checking that this compiles.
 #[derive(oauth::Request)]
 struct Unsized {
@@ -297,6 +343,62 @@ struct WeirdAttrs {
     _trailing_comma: (),
 }
 
+#[derive(oauth::Request)]
+#[oauth1(validate = Validated::check)]
+struct Validated {
+    // Declared before `alpha` though its parameter name sorts after it.
+    #[oauth1(validate = positive)]
+    zulu: i32,
+    #[oauth1(rename = "renamed", validate = positive)]
+    alpha: i32,
+}
+
+impl Validated {
+    fn check(&self) -> Result<(), oauth::request::ValidationError> {
+        if self.zulu == self.alpha {
+            Err(oauth::request::ValidationError::new("must differ"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn positive(x: &i32) -> Result<(), oauth::request::ValidationError> {
+    if *x > 0 {
+        Ok(())
+    } else {
+        Err(oauth::request::ValidationError::new("must be positive"))
+    }
+}
+
+#[test]
+fn validate() {
+    use oauth::Request;
+
+    let validate = |zulu, alpha| {
+        Validated { zulu, alpha }
+            .validate()
+            .map_err(|e| e.parameter())
+    };
+
+    assert_eq!(validate(1, 2), Ok(()));
+    // Field-level validation functions run in the order of declaration.
+    assert_eq!(validate(0, 0), Err(Some("zulu")));
+    assert_eq!(validate(1, 0), Err(Some("renamed")));
+    // The container-level validation function runs after the field-level ones.
+    assert_eq!(validate(1, 1), Err(None));
+
+    let builder = oauth::Builder::<_, _>::new(
+        oauth::Credentials::new("consumer", "secret"),
+        oauth::PLAINTEXT,
+    );
+    let request = Validated { zulu: 1, alpha: 0 };
+    match builder.try_authorize("GET", "https://example.com/", &request) {
+        Err(oauth::Error::Validation(e)) => assert_eq!(e.parameter(), Some("renamed")),
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
 fn fmt_option_str(s: &Option<&str>, f: &mut Formatter<'_>) -> fmt::Result {
     if let Some(s) = s {
         Display::fmt(s, f)
@@ -35,6 +35,10 @@ struct DuplicateNameValue {}
 #[oauth1(crate = oauth1_request, crate = oauth1_request)]
 struct DuplicateNameValue2 {}
 
+#[derive(oauth1_request::Request)]
+#[oauth1(validate = 0)]
+struct InvalidContainerPath {}
+
 #[derive(oauth1_request::Request)]
 struct Fields {
     #[oauth1(rename = 0)]
@@ -90,12 +94,26 @@ struct Fields {
     #[oauth1(fmt = |_, _| Ok(()))]
     invalid_path: u8,
 
+    #[oauth1(validate = |_| Ok(()))]
+    invalid_validate_path: u8,
+
     duplicate: u8,
     #[oauth1(rename = "duplicate")]
     duplicate_renamed: u8,
 
     #[oauth1(rename = "?")]
     uri_unsafe: u8,
+
+    oauth_prefix: u8,
+
+    #[oauth1(rename = "oauth_renamed")]
+    oauth_prefix_renamed: u8,
+
+    #[oauth1(allow_oauth_prefix)]
+    oauth_prefix_allowed: u8,
+
+    #[oauth1(skip)]
+    oauth_prefix_skipped: u8,
 }
 
 fn main() {}
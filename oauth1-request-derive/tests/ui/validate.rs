@@ -0,0 +1,40 @@
+use oauth1_request::request::ValidationError;
+
+#[derive(oauth1_request::Request)]
+#[oauth1(validate = container_arg_mismatch)]
+struct Container {}
+
+#[derive(oauth1_request::Request)]
+struct Fields {
+    #[oauth1(validate = arg_not_ref)]
+    arg_not_ref: u8,
+
+    #[oauth1(validate = arg_mismatch)]
+    arg_mismatch: u8,
+
+    #[oauth1(validate = ret_mismatch)]
+    ret_mismatch: u8,
+
+    #[oauth1(validate = NOT_FN)]
+    not_fn: u8,
+}
+
+const NOT_FN: () = ();
+
+fn container_arg_mismatch(_: &Fields) -> Result<(), ValidationError> {
+    Ok(())
+}
+
+fn arg_not_ref(_: u8) -> Result<(), ValidationError> {
+    Ok(())
+}
+
+fn arg_mismatch(_: &()) -> Result<(), ValidationError> {
+    Ok(())
+}
+
+fn ret_mismatch(_: &u8) -> bool {
+    true
+}
+
+fn main() {}
@@ -0,0 +1,96 @@
+//! Signing a sequence of requests that share the credentials and options, e.g. the chunks of an
+//! upload.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::string::String;
+use core::fmt::{self, Debug, Display, Formatter};
+
+use crate::signature_method::SignatureMethod;
+use crate::{Builder, Request};
+
+/// An iterator of `Authorization` header values, returned by
+/// [`Builder::authorize_each`](crate::Builder::authorize_each).
+///
+/// Each request is signed with a fresh nonce and timestamp, unless they are set explicitly on the
+/// `Builder`. The buffer of each header value is allocated with the length of the previous one,
+/// so that the headers of similar requests are written without reallocation.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+///
+/// let upload_uri = "https://upload.example.com/files/1234";
+/// let chunks = (0..3).map(|i| {
+///     let part = i.to_string();
+///     (upload_uri, oauth::ParameterList::new([("part", part)]))
+/// });
+/// for (i, authorization) in builder.authorize_each("PUT", chunks).enumerate() {
+///     // Upload the chunk to `{upload_uri}?part={i}` with the `Authorization` header.
+/// #   let _ = (i, authorization);
+/// }
+/// ```
+pub struct AuthorizeEach<'b, 'a, SM, C, T, I> {
+    builder: &'b Builder<'a, SM, C, T>,
+    method: &'b str,
+    requests: I,
+    capacity: usize,
+}
+
+impl<'b, 'a, SM, C, T, I> AuthorizeEach<'b, 'a, SM, C, T, I> {
+    pub(crate) fn new(builder: &'b Builder<'a, SM, C, T>, method: &'b str, requests: I) -> Self {
+        AuthorizeEach {
+            builder,
+            method,
+            requests,
+            capacity: 0,
+        }
+    }
+}
+
+impl<'b, 'a, SM, C, T, I, U, R> Iterator for AuthorizeEach<'b, 'a, SM, C, T, I>
+where
+    SM: SignatureMethod + Clone,
+    C: AsRef<str>,
+    T: AsRef<str>,
+    I: Iterator<Item = (U, R)>,
+    U: Display,
+    R: Request,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let (uri, request) = self.requests.next()?;
+        let buf = String::with_capacity(self.capacity);
+        let authorization = self
+            .builder
+            .authorize_with_buf(buf, self.method, uri, &request);
+        self.capacity = authorization.len();
+        Some(authorization)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.requests.size_hint()
+    }
+}
+
+impl<'b, 'a, SM, C, T, I> Debug for AuthorizeEach<'b, 'a, SM, C, T, I>
+where
+    SM: Debug,
+    C: Debug,
+    T: Debug,
+    I: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthorizeEach")
+            .field("builder", self.builder)
+            .field("method", &self.method)
+            .field("requests", &self.requests)
+            .finish()
+    }
+}
@@ -0,0 +1,263 @@
+//! The OAuth Request Body Hash extension ([draft][spec]).
+//!
+//! The extension protects the integrity of request bodies that are not `x-www-form-urlencoded`
+//! (and thus not covered by the signature) by signing their hash as an `oauth_body_hash` protocol
+//! parameter. The hash function is the one underlying the signature method, e.g. SHA-1 for
+//! `HMAC-SHA1` and `RSA-SHA1`.
+//!
+//! This module is only available when `body-hash` feature is activated.
+//!
+//! [spec]: https://datatracker.ietf.org/doc/html/draft-eaton-oauth-bodyhash-00
+//!
+//! ## Example
+//!
+#![cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#![cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+//! # extern crate oauth1_request as oauth;
+//! # extern crate sha1;
+//! #
+//! use oauth::body_hash::BodyHash;
+//! use sha1::Sha1;
+//!
+//! let body = br#"{"status":"Hello World!"}"#;
+//! let hash = BodyHash::new::<Sha1>(body);
+//! assert_eq!(hash.as_str(), "vjGPRED4tfAuOYl6YOwXz5tVjf0=");
+//!
+//! let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+//! let authorization = oauth::Builder::with_token(token, oauth::HMAC_SHA1)
+//!     .body_hash(hash.as_str())
+//!     .post("https://example.com/statuses", &());
+//!
+//! let expected = r#"oauth_body_hash="vjGPRED4tfAuOYl6YOwXz5tVjf0%3D""#;
+//! assert!(authorization.contains(expected));
+//! ```
+//!
+//! ## Combining with query parameters
+//!
+//! The body hash can be combined with request parameters, which are signed as usual. This covers
+//! providers that expect a `multipart/form-data` upload to be signed with the query parameters
+//! and the hash of the raw body, but not the form fields in the body: hash the raw body and pass
+//! only the query parameters as the request.
+//!
+#![cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#![cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+//! # extern crate oauth1_request as oauth;
+//! # extern crate sha1;
+//! #
+//! use oauth::body_hash::BodyHash;
+//! use sha1::Sha1;
+//!
+//! let body = b"--boundary\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nReport\r\n\
+//!              --boundary--\r\n";
+//! let hash = BodyHash::new::<Sha1>(&body[..]);
+//! let query = oauth::ParameterList::new([("folder", "reports")]);
+//!
+//! let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+//! let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+//! builder.body_hash(hash.as_str());
+//! let authorization = builder.post("https://example.com/upload", &query);
+//! let uri = oauth::to_query("https://example.com/upload".to_owned(), &query);
+//!
+//! assert_eq!(uri, "https://example.com/upload?folder=reports");
+//! assert!(authorization.contains("oauth_body_hash="));
+//! ```
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::Deref;
+use core::str;
+
+use digest::consts::U64;
+use digest::typenum::{IsLessOrEqual, True};
+use digest::Digest;
+
+// Length of padded Base64 encoding of a 64-byte hash value, the longest among the common hash
+// functions.
+const MAX_LEN: usize = 88;
+
+/// An `oauth_body_hash` value, i.e. the Base64-encoded hash of a request body.
+///
+/// Pass it to [`Builder::body_hash`](crate::Builder::body_hash) to sign a request with it.
+#[derive(Clone, Copy)]
+pub struct BodyHash {
+    buf: [u8; MAX_LEN],
+    len: usize,
+}
+
+/// An incremental hasher producing a [`BodyHash`], for request bodies that are not available in
+/// memory at once.
+///
+/// With `std` feature, `BodyHasher` also implements [`std::io::Write`], so you can pass it to
+/// `std::io::copy` or to a `Write` adapter that tees an upload stream.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "std", doc = " ```")]
+#[cfg_attr(not(feature = "std"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// # extern crate sha2;
+/// #
+/// use oauth::body_hash::{BodyHash, BodyHasher};
+/// use sha2::Sha256;
+///
+/// let mut hasher = BodyHasher::<Sha256>::new();
+/// let mut body: &[u8] = br#"{"status":"Hello World!"}"#;
+/// std::io::copy(&mut body, &mut hasher).unwrap();
+///
+/// assert_eq!(hasher.finalize(), BodyHash::new::<Sha256>(br#"{"status":"Hello World!"}"#));
+/// ```
+#[derive(Clone, Default)]
+pub struct BodyHasher<D> {
+    digest: D,
+}
+
+impl BodyHash {
+    /// Hashes `body` with the hash function `D`.
+    ///
+    /// Use an empty `body` for requests without an entity-body. The output of `D` must not be
+    /// longer than 64 bytes.
+    pub fn new<D>(body: impl AsRef<[u8]>) -> Self
+    where
+        D: Digest,
+        D::OutputSize: IsLessOrEqual<U64, Output = True>,
+    {
+        BodyHash::encode(&D::digest(body))
+    }
+
+    /// Creates a `BodyHash` from the raw (i.e. not Base64-encoded) hash value of a request body.
+    ///
+    /// This is useful if you have already computed the hash, e.g. while streaming the body.
+    ///
+    /// Returns `None` if `hash` is longer than 64 bytes.
+    pub fn from_hash(hash: &[u8]) -> Option<Self> {
+        if hash.len() <= 64 {
+            Some(BodyHash::encode(hash))
+        } else {
+            None
+        }
+    }
+
+    /// Encodes `hash`, which must not be longer than 64 bytes.
+    fn encode(hash: &[u8]) -> Self {
+        let mut buf = [0_u8; MAX_LEN];
+        let len = base64::encode_config_slice(hash, base64::STANDARD, &mut buf);
+        BodyHash { buf, len }
+    }
+
+    /// Returns the Base64-encoded hash value as a string slice.
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl AsRef<str> for BodyHash {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Debug for BodyHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BodyHash").field(&self.as_str()).finish()
+    }
+}
+
+impl Deref for BodyHash {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for BodyHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for BodyHash {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for BodyHash {}
+
+impl<D> BodyHasher<D>
+where
+    D: Digest,
+    D::OutputSize: IsLessOrEqual<U64, Output = True>,
+{
+    /// Creates a new `BodyHasher`.
+    ///
+    /// The output of `D` must not be longer than 64 bytes.
+    pub fn new() -> Self {
+        BodyHasher { digest: D::new() }
+    }
+
+    /// Feeds a chunk of the request body to the hasher.
+    pub fn update(&mut self, chunk: impl AsRef<[u8]>) -> &mut Self {
+        self.digest.update(chunk);
+        self
+    }
+
+    /// Returns the hash of the request body fed so far.
+    pub fn finalize(self) -> BodyHash {
+        BodyHash::encode(&self.digest.finalize())
+    }
+}
+
+impl<D> Debug for BodyHasher<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyHasher").finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D> std::io::Write for BodyHasher<D>
+where
+    D: Digest,
+    D::OutputSize: IsLessOrEqual<U64, Output = True>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use super::*;
+
+    #[test]
+    fn hello_world() {
+        let hash = BodyHash::new::<Sha256>("Hello World!");
+        assert_eq!(
+            hash.as_str(),
+            "f4OxZX/x/FO5LcGBSKHWXfwtSx+j1ncoSt3SABJtkGk="
+        );
+        assert_eq!(
+            BodyHash::from_hash(&Sha256::digest("Hello World!")),
+            Some(hash)
+        );
+        assert!(BodyHash::from_hash(&[0; 65]).is_none());
+
+        let mut hasher = BodyHasher::<Sha256>::new();
+        hasher.update("Hello").update("").update(" World!");
+        assert_eq!(hasher.finalize(), hash);
+    }
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn sha1() {
+        // The example of the draft.
+        let hash = BodyHash::new::<sha1::Sha1>("Hello World!");
+        assert_eq!(hash.as_str(), "Lve95gjOVATpfV8EL5X4nxwjKHE=");
+    }
+}
@@ -0,0 +1,30 @@
+//! Typed requests for the common endpoints of some OAuth 1.0 service providers.
+//!
+//! The requests are thin structs deriving [`Request`](crate::Request), with the endpoint URI
+//! as an associated constant (or a `uri` method if the URI has path parameters). They don't
+//! cover every parameter of the endpoints, but serve as examples of modelling an API with this
+//! crate.
+//!
+//! This module is only available when `contrib` feature is activated.
+//!
+//! ## Example
+//!
+#![cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#![cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+//! # extern crate oauth1_request as oauth;
+//! #
+//! use oauth::contrib::tumblr;
+//!
+//! let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+//! let request = tumblr::Dashboard {
+//!     limit: Some(5),
+//!     ..Default::default()
+//! };
+//! let authorization = oauth::get(tumblr::Dashboard::URI, &request, &token, oauth::HMAC_SHA1);
+//! let uri = oauth::to_query(tumblr::Dashboard::URI.to_owned(), &request);
+//! assert_eq!(uri, "https://api.tumblr.com/v2/user/dashboard?limit=5");
+//! # let _ = authorization;
+//! ```
+
+pub mod discogs;
+pub mod tumblr;
@@ -0,0 +1,75 @@
+//! Requests for the [Discogs API](https://www.discogs.com/developers).
+//!
+//! Discogs accepts the `PLAINTEXT` and `HMAC-SHA1` signature methods.
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::util::percent_encode;
+use crate::Request;
+
+/// The URI of the temporary credentials (request token) endpoint.
+pub const REQUEST_TOKEN_URI: &str = "https://api.discogs.com/oauth/request_token";
+/// The URI of the resource owner authorization endpoint.
+pub const AUTHORIZE_URI: &str = "https://www.discogs.com/oauth/authorize";
+/// The URI of the token credentials (access token) endpoint.
+pub const ACCESS_TOKEN_URI: &str = "https://api.discogs.com/oauth/access_token";
+
+/// `GET /oauth/identity`: Retrieves the identity of the authenticated user.
+#[derive(Clone, Copy, Debug, Default, Request)]
+#[oauth1(crate = crate)]
+pub struct Identity {}
+
+impl Identity {
+    /// The URI of the endpoint.
+    pub const URI: &'static str = "https://api.discogs.com/oauth/identity";
+}
+
+/// `GET /database/search`: Searches the Discogs database.
+#[derive(Clone, Copy, Debug, Default, Request)]
+#[oauth1(crate = crate)]
+pub struct Search<'a> {
+    /// The search query.
+    pub q: Option<&'a str>,
+    /// The type of the results: `"release"`, `"master"`, `"artist"` or `"label"`.
+    #[oauth1(rename = "type")]
+    pub kind: Option<&'a str>,
+    /// Search by artist name.
+    pub artist: Option<&'a str>,
+    /// Search by release title.
+    pub release_title: Option<&'a str>,
+    /// Search by label name.
+    pub label: Option<&'a str>,
+    /// Search by release year.
+    pub year: Option<u16>,
+    /// Search by barcode.
+    pub barcode: Option<&'a str>,
+    /// The page number of the results.
+    pub page: Option<u32>,
+    /// The number of results per page.
+    pub per_page: Option<u32>,
+}
+
+impl<'a> Search<'a> {
+    /// The URI of the endpoint.
+    pub const URI: &'static str = "https://api.discogs.com/database/search";
+}
+
+/// `GET /users/{username}/collection/folders`: Retrieves the folders of a user's collection.
+#[derive(Clone, Copy, Debug, Default, Request)]
+#[oauth1(crate = crate)]
+pub struct CollectionFolders<'a> {
+    /// The user whose collection to retrieve. This is a part of the URI.
+    #[oauth1(skip)]
+    pub username: &'a str,
+}
+
+impl<'a> CollectionFolders<'a> {
+    /// Returns the URI of the endpoint.
+    pub fn uri(&self) -> String {
+        format!(
+            "https://api.discogs.com/users/{}/collection/folders",
+            percent_encode(self.username),
+        )
+    }
+}
@@ -0,0 +1,83 @@
+//! Requests for the [Tumblr API](https://www.tumblr.com/docs/en/api/v2).
+//!
+//! Tumblr uses the `HMAC-SHA1` signature method.
+
+use alloc::format;
+use alloc::string::String;
+
+use crate::util::percent_encode;
+use crate::Request;
+
+/// The URI of the temporary credentials (request token) endpoint.
+pub const REQUEST_TOKEN_URI: &str = "https://www.tumblr.com/oauth/request_token";
+/// The URI of the resource owner authorization endpoint.
+pub const AUTHORIZE_URI: &str = "https://www.tumblr.com/oauth/authorize";
+/// The URI of the token credentials (access token) endpoint.
+pub const ACCESS_TOKEN_URI: &str = "https://www.tumblr.com/oauth/access_token";
+
+/// `GET user/info`: Retrieves the user's account information.
+#[derive(Clone, Copy, Debug, Default, Request)]
+#[oauth1(crate = crate)]
+pub struct UserInfo {}
+
+impl UserInfo {
+    /// The URI of the endpoint.
+    pub const URI: &'static str = "https://api.tumblr.com/v2/user/info";
+}
+
+/// `GET user/dashboard`: Retrieves the posts of the user's dashboard.
+#[derive(Clone, Copy, Debug, Default, Request)]
+#[oauth1(crate = crate)]
+pub struct Dashboard<'a> {
+    /// The number of posts to return (1-20).
+    pub limit: Option<u32>,
+    /// The post number to start at.
+    pub offset: Option<u32>,
+    /// The type of posts to return, e.g. `"text"`.
+    #[oauth1(rename = "type")]
+    pub kind: Option<&'a str>,
+    /// Return the posts after this ID.
+    pub since_id: Option<u64>,
+    /// Whether to return the reblog information.
+    pub reblog_info: Option<bool>,
+    /// Whether to return the notes information.
+    pub notes_info: Option<bool>,
+}
+
+impl<'a> Dashboard<'a> {
+    /// The URI of the endpoint.
+    pub const URI: &'static str = "https://api.tumblr.com/v2/user/dashboard";
+}
+
+/// `POST blog/{blog-identifier}/post`: Creates a post with the legacy form-encoded format.
+///
+/// Send the request with the `application/x-www-form-urlencoded` body created by
+/// [`to_form`](crate::to_form).
+#[derive(Clone, Copy, Debug, Default, Request)]
+#[oauth1(crate = crate)]
+pub struct CreatePost<'a> {
+    /// The blog to post to, e.g. `"example.tumblr.com"`. This is a part of the URI.
+    #[oauth1(skip)]
+    pub blog_identifier: &'a str,
+    /// The type of the post, e.g. `"text"`.
+    #[oauth1(rename = "type")]
+    pub kind: &'a str,
+    /// The state of the post: `"published"`, `"draft"`, `"queue"` or `"private"`.
+    pub state: Option<&'a str>,
+    /// Comma-separated tags of the post.
+    pub tags: Option<&'a str>,
+    /// The title of a text post.
+    pub title: Option<&'a str>,
+    /// The body of a text post, in HTML.
+    pub body: Option<&'a str>,
+}
+
+impl<'a> CreatePost<'a> {
+    /// Returns the URI of the endpoint.
+    pub fn uri(&self) -> String {
+        format!(
+            "https://api.tumblr.com/v2/blog/{}/post",
+            percent_encode(self.blog_identifier),
+        )
+    }
+}
@@ -0,0 +1,332 @@
+//! Signed requests to be sent later, e.g. by a worker of a job queue.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::string::String;
+
+use crate::param;
+use crate::util::{split_auth_param, AuthParams};
+use crate::verify::{self, TimestampError};
+
+/// A signed request captured to be sent later.
+///
+/// This holds everything needed to send the request: the HTTP request method, the URI (including
+/// the query part, if any), the `Authorization` header value and the body. With `serde` feature,
+/// the type implements `Serialize` and `Deserialize` as a struct with the fields `method`, `uri`,
+/// `authorization` and `body` (which may be `null`), so that it can be pushed to a job queue and
+/// sent by a worker, possibly written in another language.
+///
+/// Since servers reject requests with a stale `oauth_timestamp`, the worker should check that the
+/// request has not expired with [`check_expiry`](Self::check_expiry) before sending it.
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "hmac-sha1", feature = "std"), doc = " ```")]
+#[cfg_attr(not(all(feature = "hmac-sha1", feature = "std")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::deferred::DeferredSignedRequest;
+///
+/// let request = oauth::ParameterList::new([("status", "hello")]);
+/// let uri = "https://example.com/statuses/update";
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let authorization = oauth::post(uri, &request, &token, oauth::HMAC_SHA1);
+/// let deferred = DeferredSignedRequest::new(
+///     "POST".into(),
+///     uri.into(),
+///     authorization,
+///     Some(oauth::to_form(&request)),
+/// );
+///
+/// // In the worker:
+/// if deferred.check_expiry(5 * 60).is_ok() {
+///     // Send the request.
+/// # assert_eq!(deferred.body(), Some("status=hello"));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DeferredSignedRequest {
+    method: String,
+    uri: String,
+    authorization: String,
+    body: Option<String>,
+}
+
+impl DeferredSignedRequest {
+    /// Creates a `DeferredSignedRequest` from the parts of a signed request.
+    ///
+    /// `authorization` should be an `Authorization` header value produced by this crate. If the
+    /// OAuth protocol parameters are sent in the query or the body instead, pass an empty string.
+    pub fn new(method: String, uri: String, authorization: String, body: Option<String>) -> Self {
+        DeferredSignedRequest {
+            method,
+            uri,
+            authorization,
+            body,
+        }
+    }
+
+    /// Returns the HTTP request method.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the request URI.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the `Authorization` header value.
+    pub fn authorization(&self) -> &str {
+        &self.authorization
+    }
+
+    /// Returns the request body, if any.
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// Returns the `oauth_timestamp` value of the request.
+    ///
+    /// The timestamp is looked up in the `Authorization` header, the query part of the URI and
+    /// the body (assuming `application/x-www-form-urlencoded`), in this order.
+    pub fn timestamp(&self) -> Result<u64, TimestampError> {
+        if let Some(value) = header_parameter(&self.authorization, param::TIMESTAMP) {
+            return verify::parse_timestamp(value);
+        }
+        let query = self.uri.split('#').next().unwrap_or("");
+        let query = query.find('?').map(|i| &query[i + 1..]);
+        for pairs in query.into_iter().chain(self.body.as_deref()) {
+            if let Some(value) = pairs
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("oauth_timestamp="))
+            {
+                return verify::parse_timestamp(value);
+            }
+        }
+        Err(TimestampError::Missing)
+    }
+
+    doc_auto_cfg! {
+        /// Checks that the request's timestamp is no older than `max_age` seconds, and returns the
+        /// timestamp.
+        ///
+        /// Timestamps up to `max_age` seconds in the future are accepted as well, like
+        /// [`verify::check_timestamp`].
+        #[cfg(feature = "std")]
+        pub fn check_expiry(&self, max_age: u64) -> Result<u64, TimestampError> {
+            self.check_expiry_at(crate::serializer::auth::get_current_timestamp(), max_age)
+        }
+    }
+
+    /// Same as [`check_expiry`](Self::check_expiry), but checks the timestamp against `now` (in
+    /// seconds since the Unix epoch) instead of the current time.
+    pub fn check_expiry_at(&self, now: u64, max_age: u64) -> Result<u64, TimestampError> {
+        verify::check_age(self.timestamp()?, now, max_age)
+    }
+
+    /// Returns the method, URI, `Authorization` header value and body of the request.
+    pub fn into_parts(self) -> (String, String, String, Option<String>) {
+        (self.method, self.uri, self.authorization, self.body)
+    }
+}
+
+/// Finds the (percent-encoded) value of the parameter `key` in an `Authorization` header value.
+fn header_parameter<'a>(authorization: &'a str, key: &str) -> Option<&'a str> {
+    let params = authorization.strip_prefix("OAuth ")?;
+    AuthParams::new(params).find_map(|param| {
+        let (k, v) = split_auth_param(param)?;
+        if k == key {
+            Some(v)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(feature = "serde")]
+mod serde_imp {
+    use alloc::string::String;
+    use core::fmt::{self, Formatter};
+
+    use serde1::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+    use serde1::ser::{Serialize, SerializeStruct, Serializer};
+
+    use super::DeferredSignedRequest;
+
+    const NAME: &str = "DeferredSignedRequest";
+    const FIELDS: &[&str] = &["method", "uri", "authorization", "body"];
+
+    /// A `String` that can be deserialized without `serde/alloc` feature.
+    struct OwnedString(String);
+
+    impl Serialize for DeferredSignedRequest {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut s = serializer.serialize_struct(NAME, FIELDS.len())?;
+            s.serialize_field("method", &*self.method)?;
+            s.serialize_field("uri", &*self.uri)?;
+            s.serialize_field("authorization", &*self.authorization)?;
+            s.serialize_field("body", &self.body.as_deref())?;
+            s.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for DeferredSignedRequest {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct RequestVisitor;
+
+            impl<'de> Visitor<'de> for RequestVisitor {
+                type Value = DeferredSignedRequest;
+
+                fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.write_str("struct DeferredSignedRequest")
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut next = |i: usize| {
+                        seq.next_element::<Option<OwnedString>>()?
+                            .ok_or_else(|| de::Error::invalid_length(i, &self))
+                    };
+                    let method = next(0)?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                    let uri = next(1)?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    let authorization =
+                        next(2)?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                    let body = next(3)?;
+                    Ok(DeferredSignedRequest::new(
+                        method.0,
+                        uri.0,
+                        authorization.0,
+                        body.map(|b| b.0),
+                    ))
+                }
+
+                fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                    let mut fields: [Option<Option<OwnedString>>; 4] = [None, None, None, None];
+                    while let Some(key) = map.next_key::<OwnedString>()? {
+                        match FIELDS.iter().position(|&f| f == key.0) {
+                            Some(i) => {
+                                if fields[i].is_some() {
+                                    return Err(de::Error::duplicate_field(FIELDS[i]));
+                                }
+                                fields[i] = Some(map.next_value()?);
+                            }
+                            None => {
+                                map.next_value::<de::IgnoredAny>()?;
+                            }
+                        }
+                    }
+
+                    let [method, uri, authorization, body] = fields;
+                    let required = |field: Option<Option<OwnedString>>, i: usize| {
+                        field
+                            .flatten()
+                            .map(|s| s.0)
+                            .ok_or_else(|| de::Error::missing_field(FIELDS[i]))
+                    };
+                    Ok(DeferredSignedRequest::new(
+                        required(method, 0)?,
+                        required(uri, 1)?,
+                        required(authorization, 2)?,
+                        body.flatten().map(|b| b.0),
+                    ))
+                }
+            }
+
+            deserializer.deserialize_struct(NAME, FIELDS, RequestVisitor)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for OwnedString {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct StringVisitor;
+
+            impl<'de> Visitor<'de> for StringVisitor {
+                type Value = OwnedString;
+
+                fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.write_str("a string")
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<OwnedString, E> {
+                    Ok(OwnedString(v.into()))
+                }
+            }
+
+            deserializer.deserialize_string(StringVisitor)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn roundtrip() {
+            let request = DeferredSignedRequest::new(
+                "POST".into(),
+                "https://example.com/".into(),
+                r#"OAuth oauth_timestamp="1234567890""#.into(),
+                Some("a=b".into()),
+            );
+            let json = serde_json::to_value(&request).unwrap();
+            assert_eq!(
+                json,
+                serde_json::json!({
+                    "method": "POST",
+                    "uri": "https://example.com/",
+                    "authorization": r#"OAuth oauth_timestamp="1234567890""#,
+                    "body": "a=b",
+                }),
+            );
+            assert_eq!(
+                serde_json::from_value::<DeferredSignedRequest>(json).unwrap(),
+                request
+            );
+
+            let json = r#"{"uri":"/","method":"GET","authorization":"","extra":0}"#;
+            let request = serde_json::from_str::<DeferredSignedRequest>(json).unwrap();
+            assert_eq!(request.body(), None);
+            assert!(serde_json::from_str::<DeferredSignedRequest>(r#"{"uri":"/"}"#).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp() {
+        let header = r#"OAuth oauth_consumer_key="ck",oauth_timestamp="1000",oauth_signature="x""#;
+        let request = |authorization: &str, uri: &str, body: Option<&str>| {
+            DeferredSignedRequest::new(
+                "POST".into(),
+                uri.into(),
+                authorization.into(),
+                body.map(Into::into),
+            )
+        };
+
+        let r = request(header, "https://example.com/", None);
+        assert_eq!(r.timestamp(), Ok(1000));
+        let r = request(
+            r#"OAuth realm="a,oauth_timestamp=\"1\"",oauth_timestamp="1000""#,
+            "https://example.com/",
+            None,
+        );
+        assert_eq!(r.timestamp(), Ok(1000));
+        assert_eq!(r.check_expiry_at(1060, 60), Ok(1000));
+        assert!(matches!(
+            r.check_expiry_at(1061, 60),
+            Err(TimestampError::Expired { overdue: 1, .. }),
+        ));
+
+        let r = request("", "https://example.com/?oauth_timestamp=2000", None);
+        assert_eq!(r.timestamp(), Ok(2000));
+        let r = request("", "https://example.com/", Some("a=b&oauth_timestamp=3000"));
+        assert_eq!(r.timestamp(), Ok(3000));
+        let r = request("", "https://example.com/", Some("a=b"));
+        assert_eq!(r.timestamp(), Err(TimestampError::Missing));
+    }
+}
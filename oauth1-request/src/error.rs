@@ -0,0 +1,69 @@
+//! The error type of the fallible signing methods.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::request::ValidationError;
+use crate::serializer::auth::InsecureUriError;
+
+/// An error returned by the fallible signing methods like [`Builder::try_authorize`] and
+/// [`TryAuthorizer`](crate::serializer::auth::TryAuthorizer).
+///
+/// [`Builder::try_authorize`]: crate::Builder::try_authorize
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The request URI doesn't use `https` while
+    /// [`require_https`](crate::serializer::auth::Options::require_https) is set.
+    InsecureUri(InsecureUriError),
+    /// The protocol parameter appears more than once in the request while
+    /// [`reject_duplicate_parameters`](crate::serializer::auth::Options::reject_duplicate_parameters)
+    /// is set.
+    DuplicateParameter(&'static str),
+    /// The signature method with the name is not FIPS-approved while
+    /// [`fips`](crate::serializer::auth::Options::fips) is set.
+    NotFipsApproved(&'static str),
+    /// A `Display` implementation of a value in the request returned an error, or writing to the
+    /// output failed.
+    Format,
+    /// [`Request::validate`](crate::Request::validate) rejected the request.
+    Validation(ValidationError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::InsecureUri(ref e) => e.fmt(f),
+            Error::DuplicateParameter(name) => {
+                write!(f, "protocol parameter `{}` appears more than once", name)
+            }
+            Error::NotFipsApproved(name) => {
+                write!(f, "signature method `{}` is not FIPS-approved", name)
+            }
+            Error::Format => f.write_str("a formatting trait implementation returned an error"),
+            Error::Validation(ref e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Error::InsecureUri(ref e) => Some(e),
+            Error::DuplicateParameter(_) | Error::NotFipsApproved(_) | Error::Format => None,
+            Error::Validation(ref e) => Some(e),
+        }
+    }
+}
+
+impl From<InsecureUriError> for Error {
+    fn from(e: InsecureUriError) -> Self {
+        Error::InsecureUri(e)
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(e: ValidationError) -> Self {
+        Error::Validation(e)
+    }
+}
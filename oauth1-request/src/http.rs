@@ -0,0 +1,98 @@
+//! Integration with the [`http`](http1) crate.
+//!
+//! The functions in this module produce `Authorization` header values marked as sensitive
+//! ([`HeaderValue::set_sensitive`]), so that logging and tracing layers aware of the flag (e.g.
+//! those of `hyper` and `tower-http`) redact the credentials automatically.
+//!
+//! This module is only available when `http` feature is activated.
+
+use alloc::string::String;
+use core::fmt::Display;
+
+use http1::header::{HeaderName, HeaderValue, AUTHORIZATION};
+use http1::Method;
+use oauth_credentials::Token;
+
+use crate::signature_method::SignatureMethod;
+use crate::Request;
+
+/// Converts an `Authorization` header value produced by this crate into a `HeaderValue` marked as
+/// sensitive.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let token = oauth::Token::from_parts("consumer_key", "consumer_secret", "token", "token_secret");
+/// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+/// builder.callback("https://client.example.net/oauth/callback");
+///
+/// let value = oauth::http::header_value(builder.post("https://example.com/", &()));
+/// assert!(value.is_sensitive());
+/// ```
+///
+/// # Panics
+///
+/// Panics if `authorization` is not a valid header value. The values produced by this crate are
+/// always valid.
+pub fn header_value(authorization: String) -> HeaderValue {
+    let mut ret = HeaderValue::from_str(&authorization)
+        .expect("`authorization` should be a valid header value");
+    ret.set_sensitive(true);
+    ret
+}
+
+/// Same as [`header_value`] except that this returns the header name along with the value.
+pub fn header(authorization: String) -> (HeaderName, HeaderValue) {
+    (AUTHORIZATION, header_value(authorization))
+}
+
+/// Authorizes a request to `uri` with the given credentials, returning an `Authorization` header
+/// marked as sensitive.
+///
+/// This is the same as [`authorize`](crate::authorize) except for the types of the HTTP request
+/// method and the return value.
+///
+/// `uri` must not contain a query part, which would result in a wrong signature.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let token = oauth::Token::from_parts("consumer_key", "consumer_secret", "token", "token_secret");
+/// let (name, value) = oauth::http::authorize(
+///     &"GET".parse().unwrap(),
+///     "https://example.com/",
+///     &(),
+///     &token,
+///     oauth::HMAC_SHA1,
+/// );
+/// assert_eq!(name, "authorization");
+/// assert!(value.is_sensitive());
+/// ```
+pub fn authorize<U, R, C, T, SM>(
+    method: &Method,
+    uri: U,
+    request: &R,
+    token: &Token<C, T>,
+    signature_method: SM,
+) -> (HeaderName, HeaderValue)
+where
+    U: Display,
+    R: Request + ?Sized,
+    C: AsRef<str>,
+    T: AsRef<str>,
+    SM: SignatureMethod,
+{
+    header(crate::authorize(
+        method.as_str(),
+        uri,
+        request,
+        token,
+        signature_method,
+    ))
+}
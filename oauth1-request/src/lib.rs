@@ -77,6 +77,18 @@
 //!
 //! See [`Request`][oauth1_request_derive::Request] for more details on the derive macro.
 //!
+//! The derive macro is provided by the `derive` feature, which is enabled by default. If you
+//! implement `Request` by hand, you can opt out of the feature so that the procedural macro and its
+//! dependencies (`syn`, `quote`, etc.) are not compiled at all:
+//!
+//! ```toml
+//! [dependencies.oauth]
+//! version = "0.6"
+//! package = "oauth1-request"
+//! default-features = false
+//! features = ["hmac-sha1", "std"]
+//! ```
+//!
 //! If you want to authorize a request with dynamic keys, use
 //! [`oauth::ParameterList`][ParameterList].
 //!
@@ -113,6 +125,18 @@
 //!     .callback(callback)
 //!     .post(uri, &());
 //! ```
+//!
+//! ## Migrating from `oauth-client` or `oauth1`
+//!
+//! The `oauth-client` and `oauth1` crates represent both the client and token credentials as a
+//! `Token` with `key` and `secret` fields, which map to [`Credentials`] and [`Token`] as follows,
+//! so you can keep storing the credentials as before while migrating to this crate:
+//!
+//! ```ignore
+//! // `consumer` and `access` are `oauth_client::Token<'_>`s (or `oauth1::Token<'_>`s).
+//! let client = oauth::Credentials::new(&*consumer.key, &*consumer.secret);
+//! let token = oauth::Token::new(client, oauth::Credentials::new(&*access.key, &*access.secret));
+//! ```
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc(html_root_url = "https://docs.rs/oauth1-request/0.6.0")]
@@ -124,10 +148,32 @@ extern crate alloc;
 
 #[macro_use]
 mod util;
+mod error;
 
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub mod batch;
+    #[cfg(feature = "body-hash")]
+    pub mod body_hash;
+    #[cfg(feature = "contrib")]
+    pub mod contrib;
+    #[cfg(feature = "alloc")]
+    pub mod deferred;
+    #[cfg(feature = "http")]
+    pub mod http;
+    #[cfg(feature = "std")]
+    pub mod nonce;
+    #[cfg(feature = "alloc")]
+    pub mod prepare;
+    #[cfg(feature = "std")]
+    pub mod timestamp;
+}
+pub mod param;
+pub mod prelude;
 pub mod request;
 pub mod serializer;
 pub mod signature_method;
+pub mod verify;
 
 doc_auto_cfg! {
     /// A derive macro for [`Request`] trait.
@@ -163,6 +209,12 @@ doc_auto_cfg! {
     ///
     /// You can customize the behavior of the derive macro with the following field attributes:
     ///
+    /// - `#[oauth1(allow_oauth_prefix)]`
+    ///
+    /// Allow the parameter name to begin with `oauth_`. Such names are reserved for the protocol
+    /// parameters, so the derive macro rejects them by default, but you may need one for a
+    /// protocol extension that this crate does not support.
+    ///
     /// - `#[oauth1(encoded)]`
     ///
     /// Do not percent encode the value when serializing it.
@@ -194,6 +246,15 @@ doc_auto_cfg! {
     /// Call the function at `path` and do not serialize the field if the function returns `true`.
     /// The function must be callable as `fn(&T) -> bool`.
     ///
+    /// - `#[oauth1(validate = path)]`
+    ///
+    /// Call the function at `path` in the [`Request::validate`] implementation and return its
+    /// error (with [`ValidationError::with_parameter`] applied with the parameter name) if any.
+    /// The function must be callable as `fn(&T) -> Result<(), ValidationError>`. The fields are
+    /// validated in the order of declaration.
+    ///
+    /// [`ValidationError::with_parameter`]: request::ValidationError::with_parameter
+    ///
     /// ## Container attributes
     ///
     /// - `#[oauth1(crate = "name")]`
@@ -204,22 +265,37 @@ doc_auto_cfg! {
     /// exotic build tool where the crate name cannot be determined reliably.
     ///
     /// [package]: <https://doc.rust-lang.org/cargo/reference/specifying-dependencies.html#renaming-dependencies-in-cargotoml>
+    ///
+    /// - `#[oauth1(validate = path)]`
+    ///
+    /// Call the function at `path` in the [`Request::validate`] implementation after the
+    /// field-level validation functions. The function must be callable as
+    /// `fn(&Self) -> Result<(), ValidationError>`.
+    ///
+    /// `Request::validate` is only overridden when the container or any of the fields has a
+    /// `validate` attribute. See the [`request::validate`] module for an example.
     #[cfg(feature = "derive")]
     #[doc(inline)]
     pub use oauth1_request_derive::Request;
 }
 #[doc(no_inline)]
-pub use oauth_credentials::{Credentials, Token};
+pub use oauth_credentials::{Credentials, Secret, Token};
 
 doc_auto_cfg! {
+    pub use self::error::Error;
     pub use self::request::ParameterList;
     pub use self::request::Request;
-    #[cfg(feature = "hmac-sha1")]
+    pub use self::signature_method::available_methods;
+    #[cfg(feature = "ecdsa-p256-013")]
+    pub use self::signature_method::EcdsaP256Sha256;
+    #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
     pub use self::signature_method::HmacSha1;
     pub use self::signature_method::Plaintext;
     #[cfg(feature = "rsa-sha1-06")]
     pub use self::signature_method::RsaSha1;
-    #[cfg(feature = "hmac-sha1")]
+    #[cfg(feature = "rsa-sha256-06")]
+    pub use self::signature_method::RsaSha256;
+    #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
     pub use self::signature_method::HMAC_SHA1;
     #[cfg(feature = "alloc")]
     pub use self::signature_method::PLAINTEXT;
@@ -232,8 +308,12 @@ use core::fmt::{Display, Write};
 use core::num::NonZeroU64;
 use core::str;
 
+use rand::RngCore;
+
 use self::serializer::auth;
 use self::signature_method::SignatureMethod;
+#[cfg(feature = "async")]
+use self::signature_method::{AsyncError, AsyncSign, AsyncSignatureMethod};
 
 cfg_type_param_hack! {
     /// A builder for OAuth `Authorization` header string.
@@ -252,6 +332,19 @@ cfg_type_param_hack! {
     }
 }
 
+/// The sources of nondeterminism of signing a request, i.e. the clock for `oauth_timestamp` and
+/// the random number generator for `oauth_nonce`, passed to [`Builder::with_context`].
+///
+/// This lets deterministic simulation tests of a service control all the nondeterminism of
+/// signing from one place.
+#[derive(Clone, Debug)]
+pub struct SigningContext<R> {
+    /// The current time in seconds since the Unix epoch, used as the `oauth_timestamp` value.
+    pub now: NonZeroU64,
+    /// The random number generator to generate the `oauth_nonce` value with.
+    pub rng: R,
+}
+
 macro_rules! builder_authorize_shorthand {
     ($($name:ident($method:expr);)*) => {doc_auto_cfg! {$(
         #[doc = concat!("Authorizes a `", $method, "` request to `uri`,")]
@@ -308,7 +401,31 @@ macro_rules! builder_to_query_shorthand {
     )*};
 }
 
-impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+impl<'a, SM> Builder<'a, SM, &'static str> {
+    /// Creates a `Builder` that signs requests as an anonymous consumer, i.e. with an empty
+    /// consumer key and secret.
+    ///
+    /// Some public endpoints accept requests from such consumers. The requests still carry an
+    /// (empty) `oauth_consumer_key` parameter and are signed with the signing key `&` (or
+    /// `&token_secret` when a token is set).
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = " ```")]
+    #[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let builder = oauth::Builder::anonymous(oauth::PLAINTEXT);
+    /// let authorization = builder.get("https://example.com/public", &());
+    /// assert!(authorization.starts_with(r#"OAuth oauth_consumer_key="","#));
+    /// assert!(authorization.ends_with(r#"oauth_signature="%26""#));
+    /// ```
+    pub fn anonymous(signature_method: SM) -> Self {
+        Builder::new(Credentials::new("", ""), signature_method)
+    }
+}
+
+impl<'a, SM, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
     /// Creates a `Builder` that signs requests using the specified client credentials
     /// and signature method.
     pub fn new(client: Credentials<C>, signature_method: SM) -> Self {
@@ -333,6 +450,14 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
         self
     }
 
+    /// Sets/unsets the `oauth_body_hash` value of the OAuth Request Body Hash extension.
+    ///
+    /// See [`Options::body_hash`](serializer::auth::Options::body_hash) for details.
+    pub fn body_hash(&mut self, body_hash: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.body_hash(body_hash);
+        self
+    }
+
     /// Sets/unsets the `oauth_callback` URI.
     pub fn callback(&mut self, callback: impl Into<Option<&'a str>>) -> &mut Self {
         self.options.callback(callback);
@@ -356,23 +481,514 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
         self
     }
 
+    /// Calls `f` with a `Builder` that uses an `oauth_nonce` value generated with `rng`.
+    ///
+    /// By default, `Builder` generates a nonce with `rand::thread_rng()` (or `rand::rngs::OsRng`
+    /// if the `std` feature is disabled). This method lets you supply another random number
+    /// generator for a single signing call, e.g. a seeded one for reproducible tests or an
+    /// OS-specific CSPRNG. The generated nonce overrides the one set by [`nonce`](Self::nonce).
+    ///
+    /// Note that `rng` should be a cryptographically secure generator in production.
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// # use std::num::NonZeroU64;
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder.timestamp(NonZeroU64::new(1234567890));
+    ///
+    /// let authorize = |rng: &mut StepRng| {
+    ///     builder.with_rng(rng, |builder| builder.get("https://example.com/", &()))
+    /// };
+    /// assert_eq!(
+    ///     authorize(&mut StepRng::new(42, 1)),
+    ///     authorize(&mut StepRng::new(42, 1)),
+    /// );
+    /// ```
+    pub fn with_rng<R, F, O>(&self, rng: &mut R, f: F) -> O
+    where
+        R: RngCore + ?Sized,
+        SM: Clone,
+        F: FnOnce(&Builder<'_, SM, &str, &str>) -> O,
+    {
+        let mut nonce_buf = [0; auth::NONCE_LEN];
+        let mut options = self.options.clone();
+        options.nonce(auth::gen_nonce(&mut nonce_buf, rng));
+        self.with_options(options, f)
+    }
+
+    /// Calls `f` with a `Builder` that uses the `oauth_timestamp` and `oauth_nonce` values
+    /// derived from `context`.
+    ///
+    /// This is like [`with_rng`](Self::with_rng), but also takes the current time from `context`
+    /// instead of the system clock, so that the signed request is fully determined by `context`
+    /// (and the other parameters), e.g. for deterministic simulation testing. The values override
+    /// the ones set by [`nonce`](Self::nonce) and [`timestamp`](Self::timestamp).
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// # use std::num::NonZeroU64;
+    /// use oauth::SigningContext;
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    ///
+    /// let authorize = |seed| {
+    ///     let mut context = SigningContext {
+    ///         now: NonZeroU64::new(1234567890).unwrap(),
+    ///         rng: StepRng::new(seed, 1),
+    ///     };
+    ///     builder.with_context(&mut context, |builder| builder.get("https://example.com/", &()))
+    /// };
+    /// assert_eq!(authorize(42), authorize(42));
+    /// assert!(authorize(42).contains(r#"oauth_timestamp="1234567890""#));
+    /// ```
+    pub fn with_context<R, F, O>(&self, context: &mut SigningContext<R>, f: F) -> O
+    where
+        R: RngCore,
+        SM: Clone,
+        F: FnOnce(&Builder<'_, SM, &str, &str>) -> O,
+    {
+        let mut nonce_buf = [0; auth::NONCE_LEN];
+        let mut options = self.options.clone();
+        options.nonce(auth::gen_nonce(&mut nonce_buf, &mut context.rng));
+        options.timestamp(context.now);
+        self.with_options(options, f)
+    }
+
+    fn with_options<F, O>(&self, options: auth::Options<'_>, f: F) -> O
+    where
+        SM: Clone,
+        F: FnOnce(&Builder<'_, SM, &str, &str>) -> O,
+    {
+        let builder = Builder {
+            signature_method: self.signature_method.clone(),
+            client: self.client.as_ref(),
+            token: self.token.as_ref().map(Credentials::as_ref),
+            options,
+        };
+        f(&builder)
+    }
+
     /// Sets/unsets the `oauth_timestamp` value.
     ///
     /// By default, `Builder` uses the timestamp of the time when `authorize`-like method is called.
     /// This method overrides that behavior and forces the `Builder` to use the specified timestamp.
     ///
-    /// This method is for debugging/testing purpose only and should not be used in production.
+    /// This method is for debugging/testing purpose only and should not be used in production,
+    /// except with a value generated by a
+    /// [`TimestampSequencer`](timestamp::TimestampSequencer) (with `std` feature).
     pub fn timestamp(&mut self, timestamp: impl Into<Option<NonZeroU64>>) -> &mut Self {
         self.options.timestamp(timestamp);
         self
     }
 
+    /// Sets/unsets the signing key to be used verbatim instead of the one constructed from the
+    /// client and token secrets.
+    ///
+    /// This is for providers that deviate from the OAuth standard in how they construct the
+    /// signing key, e.g. by issuing secrets that contain `&` and expecting them not to be
+    /// percent-encoded. See [`SignatureMethod::sign_with_raw_key`] for details.
+    pub fn raw_signing_key(&mut self, key: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.raw_signing_key(key);
+        self
+    }
+
+    /// Sets parameters to be included in every request in addition to the request's own
+    /// parameters.
+    ///
+    /// This is useful for APIs that require some parameters (e.g. `format=json`) in all requests.
+    /// The parameters are signed along with the request's parameters. `to_form` and `to_query`
+    /// also write them to the output, but with `authorize`, you need to send them in the request
+    /// yourself.
+    ///
+    /// `parameters` is sorted when signing, so it can be in any order, but its keys should not
+    /// overlap with the keys of the request's parameters.
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// # use std::num::NonZeroU64;
+    /// let client = oauth::Credentials::new("consumer", "secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// builder
+    ///     .nonce("nonce")
+    ///     .timestamp(NonZeroU64::new(1234567890));
+    ///
+    /// let request = oauth::ParameterList::new([("api_version", "2"), ("format", "json"), ("q", "rust")]);
+    /// let expected = builder.get("https://example.com/search", &request);
+    ///
+    /// builder.default_parameters(&[("format", "json"), ("api_version", "2")]);
+    /// let request = oauth::ParameterList::new([("q", "rust")]);
+    /// assert_eq!(builder.get("https://example.com/search", &request), expected);
+    /// ```
+    pub fn default_parameters(&mut self, parameters: &'a [(&'a str, &'a str)]) -> &mut Self {
+        self.options.default_parameters(parameters);
+        self
+    }
+
+    doc_auto_cfg! {
+        /// Sets/unsets a pre-derived signing key to sign requests with.
+        ///
+        /// When set, the client and token secrets of the `Builder` are not used, so you can keep
+        /// the raw secrets out of the code path creating the `Builder` and pass empty secrets
+        /// instead. The key can be derived once with [`signature_method::signing_key_for`] and
+        /// cached, e.g. per token.
+        ///
+        #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+        #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// # use std::num::NonZeroU64;
+        /// use oauth::signature_method::signing_key_for;
+        ///
+        /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+        /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+        /// builder.nonce("nonce").timestamp(NonZeroU64::new(1234567890));
+        /// let expected = builder.get("https://example.com/", &());
+        ///
+        /// let key = signing_key_for("secret", Some("secret"));
+        /// let token = oauth::Token::from_parts("consumer", "", "token", "");
+        /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+        /// builder
+        ///     .signing_key(&key)
+        ///     .nonce("nonce")
+        ///     .timestamp(NonZeroU64::new(1234567890));
+        /// assert_eq!(builder.get("https://example.com/", &()), expected);
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn signing_key(
+            &mut self,
+            key: impl Into<Option<&'a signature_method::SigningKey>>,
+        ) -> &mut Self {
+            self.options
+                .raw_signing_key(key.into().map(signature_method::SigningKey::as_str));
+            self
+        }
+    }
+
+    /// Sets/unsets the host (and optionally the port, as `host:port`) to be used in the signature
+    /// base string in place of the authority part of the request URI.
+    ///
+    /// This is useful when the request is sent to a host other than the one the server expects,
+    /// e.g. a pre-production gateway that forwards the request with an overridden `Host` header.
+    /// The scheme and the path of the request URI are kept as is.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// # use std::num::NonZeroU64;
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder
+    ///     .nonce("nonce")
+    ///     .timestamp(NonZeroU64::new(1234567890));
+    ///
+    /// let expected = builder.get("https://api.example.com/items", &());
+    ///
+    /// builder.host("api.example.com");
+    /// let authorization = builder.get("https://gateway.internal:8443/items", &());
+    /// assert_eq!(authorization, expected);
+    /// ```
+    pub fn host(&mut self, host: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.host(host);
+        self
+    }
+
+    /// Sets whether to normalize the scheme and the authority part of the request URI in the
+    /// signature base string as the OAuth standard requires.
+    ///
+    /// By default, the request URI is signed as is, so `https://example.com:443/` and
+    /// `https://example.com/` are signed differently. Setting this to `true` lowercases the scheme
+    /// and the host and removes the default port of the scheme (`80` for `http` and `443` for
+    /// `https`), so that they are signed alike. The path and the query part are never changed.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// # use std::num::NonZeroU64;
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder
+    ///     .nonce("nonce")
+    ///     .timestamp(NonZeroU64::new(1234567890));
+    ///
+    /// let expected = builder.get("https://example.com/", &());
+    /// assert_ne!(builder.get("https://example.com:443/", &()), expected);
+    ///
+    /// builder.normalize_uri(true);
+    /// assert_eq!(builder.get("https://example.com:443/", &()), expected);
+    /// ```
+    pub fn normalize_uri(&mut self, normalize_uri: bool) -> &mut Self {
+        self.options.normalize_uri(normalize_uri);
+        self
+    }
+
+    /// Sets whether to include the default port of the scheme (`80` for `http` and `443` for
+    /// `https`) in the signature base string.
+    ///
+    /// By default, the port is signed as it appears in the request URI, or removed if it is the
+    /// default port and [`normalize_uri`](Self::normalize_uri) is set. Setting this to `true`
+    /// makes both `https://example.com:443/` and `https://example.com/` signed as
+    /// `https://example.com:443/`, for servers that (incorrectly) include the default port.
+    /// Non-default ports are always included.
+    pub fn include_default_port(&mut self, include_default_port: bool) -> &mut Self {
+        self.options.include_default_port(include_default_port);
+        self
+    }
+
+    /// Sets names of request parameters to be excluded from the signature.
+    ///
+    /// This is useful when an intermediary (e.g. a CDN) appends parameters like tracking
+    /// parameters or cache busters to the request after it is signed and the server ignores them
+    /// when verifying the signature. A name ending with `*` matches any parameter name that starts
+    /// with the part before the `*`. The `oauth_*` parameters are always signed.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// # use std::num::NonZeroU64;
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder
+    ///     .nonce("nonce")
+    ///     .timestamp(NonZeroU64::new(1234567890));
+    ///
+    /// let uri = "https://example.com/";
+    /// let expected = builder.get(uri, &oauth::ParameterList::new([("q", "rust")]));
+    ///
+    /// builder.ignored_parameters(&["utm_*"]);
+    /// let request = oauth::ParameterList::new([("q", "rust"), ("utm_source", "newsletter")]);
+    /// assert_eq!(builder.get(uri, &request), expected);
+    /// ```
+    pub fn ignored_parameters(&mut self, names: &'a [&'a str]) -> &mut Self {
+        self.options.ignored_parameters(names);
+        self
+    }
+
+    doc_auto_cfg! {
+        /// Sets whether to resolve dot-segments (`.` and `..`) in the path of the request URI
+        /// when building the signature base string.
+        ///
+        /// By default, the path is signed as is. Some servers resolve the dot-segments before
+        /// computing their base string, in which case you need to set this to `true`.
+        #[cfg(feature = "alloc")]
+        pub fn remove_dot_segments(&mut self, remove_dot_segments: bool) -> &mut Self {
+            self.options.remove_dot_segments(remove_dot_segments);
+            self
+        }
+    }
+
+    /// Sets whether to remove a trailing slash from the path of the request URI when building the
+    /// signature base string.
+    ///
+    /// By default, the trailing slash is preserved, so `https://example.com/items/` and
+    /// `https://example.com/items` are signed differently. A path consisting only of a slash is
+    /// always kept.
+    pub fn trim_trailing_slash(&mut self, trim_trailing_slash: bool) -> &mut Self {
+        self.options.trim_trailing_slash(trim_trailing_slash);
+        self
+    }
+
+    /// Sets whether to lowercase the path of the request URI when building the signature base
+    /// string.
+    ///
+    /// The scheme and the host are lowercased if [`normalize_uri`](Self::normalize_uri) is set,
+    /// while the path is signed as is by default since it is case-sensitive. Set this to `true`
+    /// for a server that (incorrectly) lowercases the whole URI before computing its base string.
+    /// The query part is never lowercased.
+    pub fn lowercase_path(&mut self, lowercase_path: bool) -> &mut Self {
+        self.options.lowercase_path(lowercase_path);
+        self
+    }
+
+    /// Sets whether to refuse to sign requests to URIs that don't use `https`, except for the
+    /// hosts set with [`http_allowed_hosts`](Self::http_allowed_hosts).
+    ///
+    /// This is only enforced by the fallible signing methods like
+    /// [`try_authorize`](Self::try_authorize), which return [`Error::InsecureUri`] on such a URI.
+    /// The other signing methods sign the request regardless.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder.require_https(true).http_allowed_hosts(&["localhost"]);
+    ///
+    /// assert!(builder.try_authorize("GET", "https://example.com/", &()).is_ok());
+    /// assert!(builder.try_authorize("GET", "http://localhost:8080/", &()).is_ok());
+    /// assert!(matches!(
+    ///     builder.try_authorize("GET", "http://example.com/", &()),
+    ///     Err(oauth::Error::InsecureUri(_)),
+    /// ));
+    /// ```
+    pub fn require_https(&mut self, require_https: bool) -> &mut Self {
+        self.options.require_https(require_https);
+        self
+    }
+
+    /// Sets the hosts that may be accessed over plain `http` when
+    /// [`require_https`](Self::require_https) is set, e.g. `localhost` for local development.
+    pub fn http_allowed_hosts(&mut self, hosts: &'a [&'a str]) -> &mut Self {
+        self.options.http_allowed_hosts(hosts);
+        self
+    }
+
+    /// Sets whether to refuse to sign requests in which a protocol parameter appears more than
+    /// once, e.g. when the request has an `oauth_token` field in addition to the token
+    /// credentials of the `Builder`.
+    ///
+    /// Such a request is rejected by the server, so this makes the mistake fail on the client
+    /// instead. See [`param::detect_duplicate`] for details.
+    ///
+    /// This is only enforced by the fallible signing methods like
+    /// [`try_authorize`](Self::try_authorize), which return [`Error::DuplicateParameter`] for
+    /// such a request. The other signing methods sign the request regardless.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder.reject_duplicate_parameters(true);
+    ///
+    /// let request = oauth::ParameterList::new([("oauth_token", "token")]);
+    /// assert_eq!(
+    ///     builder.try_authorize("GET", "https://example.com/", &request),
+    ///     Err(oauth::Error::DuplicateParameter(oauth::param::TOKEN)),
+    /// );
+    /// ```
+    pub fn reject_duplicate_parameters(&mut self, reject: bool) -> &mut Self {
+        self.options.reject_duplicate_parameters(reject);
+        self
+    }
+
+    /// Sets whether to refuse to sign requests with the signature methods that are not
+    /// FIPS-approved, like `HMAC-SHA1`, `RSA-SHA1` and `PLAINTEXT`. See
+    /// [`Options::fips`](serializer::auth::Options::fips) for details.
+    ///
+    /// This is only enforced by the fallible signing methods like
+    /// [`try_authorize`](Self::try_authorize), which return [`Error::NotFipsApproved`] for such
+    /// a signature method.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder.fips(true);
+    ///
+    /// assert_eq!(
+    ///     builder.try_authorize("GET", "https://example.com/", &()),
+    ///     Err(oauth::Error::NotFipsApproved("HMAC-SHA1")),
+    /// );
+    /// ```
+    pub fn fips(&mut self, fips: bool) -> &mut Self {
+        self.options.fips(fips);
+        self
+    }
+
+    doc_auto_cfg! {
+        /// Sets the position of the `oauth_signature` parameter in the `Authorization` header.
+        ///
+        /// By default, the signature is written after the other parameters. Changing the
+        /// position does not affect the signature itself.
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+        #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// # use std::num::NonZeroU64;
+        /// use oauth::serializer::auth::SignaturePosition;
+        ///
+        /// let client = oauth::Credentials::new("consumer", "secret");
+        /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+        /// builder
+        ///     .nonce("nonce")
+        ///     .timestamp(NonZeroU64::new(1234567890))
+        ///     .signature_position(SignaturePosition::First);
+        ///
+        /// assert_eq!(
+        ///     builder.get("https://example.com/", &()),
+        ///     "OAuth \
+        ///      oauth_signature=\"gA9ZjHFo9zgpGISvTKN6wW8yMFo%3D\",\
+        ///      oauth_consumer_key=\"consumer\",\
+        ///      oauth_nonce=\"nonce\",\
+        ///      oauth_signature_method=\"HMAC-SHA1\",\
+        ///      oauth_timestamp=\"1234567890\"",
+        /// );
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn signature_position(
+            &mut self,
+            position: serializer::auth::SignaturePosition,
+        ) -> &mut Self {
+            self.options.signature_position(position);
+            self
+        }
+    }
+
     /// Sets whether to include the `oauth_version` value in requests.
     pub fn version(&mut self, version: bool) -> &mut Self {
         self.options.version(version);
         self
     }
 
+    /// Sets/unsets the `realm` parameter of the `Authorization` header.
+    ///
+    /// The realm is not signed and is ignored by the methods producing a URI query or
+    /// an `x-www-form-urlencoded` string.
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let authorization = oauth::Builder::with_token(token, oauth::HMAC_SHA1)
+    ///     .realm("https://example.com/")
+    ///     .get("https://example.com/", &());
+    /// assert!(authorization.starts_with(r#"OAuth realm="https://example.com/",oauth_"#));
+    /// ```
+    pub fn realm(&mut self, realm: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.realm(realm);
+        self
+    }
+
+    doc_auto_cfg! {
+        /// Sets whether to emit an audit event via `tracing` for each signed request.
+        ///
+        /// See [`Options::audit_log`](serializer::auth::Options::audit_log) for the format of the
+        /// event.
+        #[cfg(feature = "tracing")]
+        pub fn audit_log(&mut self, audit_log: bool) -> &mut Self {
+            self.options.audit_log(audit_log);
+            self
+        }
+    }
+}
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
     builder_authorize_shorthand! {
         get("GET");
         put("PUT");
@@ -428,6 +1044,36 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
             request.serialize(serializer)
         }
 
+        /// Same as [`authorize`](Self::authorize), but returns an error if the request cannot be
+        /// signed, e.g. when `uri` is rejected by [`require_https`](Self::require_https), which
+        /// `authorize` doesn't enforce.
+        ///
+        /// The request is checked with [`Request::validate`] before it is signed.
+        #[cfg(feature = "alloc")]
+        pub fn try_authorize<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, Error>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            request.validate()?;
+            let serializer = serializer::auth::Authorizer::authorization(
+                method,
+                uri,
+                self.client.as_ref(),
+                self.token.as_ref().map(Credentials::as_ref),
+                &self.options,
+                self.signature_method.clone(),
+            );
+
+            request.serialize(serializer::auth::TryAuthorizer::new(serializer))
+        }
+
         /// Authorizes a request to `uri` with a custom HTTP request method, writing the OAuth protocol
         /// parameters to an `x-www-form-urlencoded` string along with the other request parameters.
         ///
@@ -450,6 +1096,65 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
 
             request.serialize(serializer)
         }
+
+        /// Same as [`to_form`](Self::to_form), but returns an error if the request cannot be
+        /// signed, e.g. when `uri` is rejected by [`require_https`](Self::require_https).
+        ///
+        /// The request is checked with [`Request::validate`] before it is signed.
+        #[cfg(feature = "alloc")]
+        pub fn try_to_form<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, Error>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            request.validate()?;
+            let serializer = serializer::auth::Authorizer::form(
+                method,
+                uri,
+                self.client.as_ref(),
+                self.token.as_ref().map(Credentials::as_ref),
+                &self.options,
+                self.signature_method.clone(),
+            );
+
+            request.serialize(serializer::auth::TryAuthorizer::new(serializer))
+        }
+
+        /// Authorizes a request to `uri` with a custom HTTP request method, returning the OAuth
+        /// protocol parameters and the other request parameters as key-value pairs.
+        ///
+        /// See [`Collector`](serializer::Collector) for the format of the pairs.
+        ///
+        /// `uri` must not contain a query part, which would result in a wrong signature.
+        #[cfg(feature = "alloc")]
+        pub fn to_pairs<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> alloc::vec::Vec<serializer::collector::Pair>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let serializer = serializer::Collector::new(
+                method,
+                uri,
+                self.client.as_ref(),
+                self.token.as_ref().map(Credentials::as_ref),
+                &self.options,
+                self.signature_method.clone(),
+            );
+
+            request.serialize(serializer)
+        }
     }
 
     /// Authorizes a request to `uri` with a custom HTTP request method, appending the OAuth
@@ -474,6 +1179,123 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
         request.serialize(serializer)
     }
 
+    /// Same as [`to_query`](Self::to_query), but returns an error if the request cannot be
+    /// signed, e.g. when `uri` is rejected by [`require_https`](Self::require_https).
+    ///
+    /// The request is checked with [`Request::validate`] before it is signed.
+    pub fn try_to_query<W, R>(&self, method: &str, uri: W, request: &R) -> Result<W, Error>
+    where
+        W: Display + Write,
+        R: Request + ?Sized,
+        SM: Clone,
+    {
+        request.validate()?;
+        let serializer = serializer::auth::Authorizer::query(
+            method,
+            uri,
+            self.client.as_ref(),
+            self.token.as_ref().map(Credentials::as_ref),
+            &self.options,
+            self.signature_method.clone(),
+        );
+
+        request.serialize(serializer::auth::TryAuthorizer::new(serializer))
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a sequence of requests with a custom HTTP request method, returning an
+        /// iterator of `Authorization` header values, one for each `(uri, request)` pair.
+        ///
+        /// This is useful for signing the chunks of an upload, which storage services typically
+        /// require to be signed individually. See [`AuthorizeEach`](batch::AuthorizeEach) for
+        /// details.
+        ///
+        /// The URIs must not contain a query part, which would result in wrong signatures.
+        #[cfg(feature = "alloc")]
+        pub fn authorize_each<'b, I, U, R>(
+            &'b self,
+            method: &'b str,
+            requests: I,
+        ) -> batch::AuthorizeEach<'b, 'a, SM, C, T, I::IntoIter>
+        where
+            I: IntoIterator<Item = (U, R)>,
+            U: Display,
+            R: Request,
+            SM: Clone,
+        {
+            batch::AuthorizeEach::new(self, method, requests.into_iter())
+        }
+
+        /// Authorizes a request to `uri` with both `self` and `other`, returning a pair of HTTP
+        /// `Authorization` header values.
+        ///
+        /// The request is serialized only once. This is useful when rotating the credentials or
+        /// migrating to another signature method: send the request with the header signed with
+        /// the new credentials and retry with the other header if the server rejects it.
+        ///
+        /// Each builder uses its own options, so the headers have different nonces and timestamps
+        /// unless they are set explicitly.
+        ///
+        /// `uri` must not contain a query part, which would result in wrong signatures.
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+        #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// use std::num::NonZeroU64;
+        ///
+        /// let new = oauth::Token::from_parts("consumer", "new_secret", "token", "secret");
+        /// let old = oauth::Token::from_parts("consumer", "old_secret", "token", "secret");
+        /// let mut new = oauth::Builder::with_token(new, oauth::HMAC_SHA1);
+        /// let mut old = oauth::Builder::with_token(old, oauth::HMAC_SHA1);
+        /// # new.nonce("nonce").timestamp(NonZeroU64::new(9999999999));
+        /// # old.nonce("nonce").timestamp(NonZeroU64::new(9999999999));
+        ///
+        /// let uri = "https://example.com/";
+        /// let request = oauth::ParameterList::new([("q", "rust")]);
+        /// let (authorization, fallback) = new.authorize_dual(&old, "GET", uri, &request);
+        /// assert_eq!(authorization, new.get(uri, &request));
+        /// assert_eq!(fallback, old.get(uri, &request));
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn authorize_dual<SM2, C2, T2, U, R>(
+            &self,
+            other: &Builder<'_, SM2, C2, T2>,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> (String, String)
+        where
+            SM2: SignatureMethod + Clone,
+            C2: AsRef<str>,
+            T2: AsRef<str>,
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let first = serializer::auth::Authorizer::authorization(
+                method,
+                &uri,
+                self.client.as_ref(),
+                self.token.as_ref().map(Credentials::as_ref),
+                &self.options,
+                self.signature_method.clone(),
+            );
+            let second = serializer::auth::Authorizer::authorization(
+                method,
+                &uri,
+                other.client.as_ref(),
+                other.token.as_ref().map(Credentials::as_ref),
+                &other.options,
+                other.signature_method.clone(),
+            );
+
+            request.serialize(serializer::Tee::new(first, second))
+        }
+    }
+
     /// Same as `authorize` except that this writes the resulting `Authorization` header value
     /// into `buf`.
     pub fn authorize_with_buf<W, U, R>(&self, buf: W, method: &str, uri: U, request: &R) -> W
@@ -645,6 +1467,143 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
 
         request.serialize(serializer)
     }
+
+    doc_auto_cfg! {
+        /// Collects the parameters of a request to `uri` without signing it, so that you can
+        /// inspect or modify them before signing it with
+        /// [`PreparedRequest::finalize`](prepare::PreparedRequest::finalize).
+        ///
+        /// `uri` must not contain a query part, which would result in a wrong signature.
+        #[cfg(feature = "alloc")]
+        pub fn prepare<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> prepare::PreparedRequest<SM::Sign>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let serializer = serializer::auth::Authorizer::authorization(
+                method,
+                uri,
+                self.client.as_ref(),
+                self.token.as_ref().map(Credentials::as_ref),
+                &self.options,
+                prepare::Prepare(self.signature_method.clone()),
+            );
+
+            let (sign, _) = request
+                .serialize(serializer::auth::IntoParts(serializer))
+                .expect("a Display implementation returned an error unexpectedly");
+            sign.into_prepared(&self.options)
+        }
+
+        /// Converts `self` into a `Builder` that passes the parameters of the signature base string
+        /// to `f` before signing them, e.g. to work around a server that deviates from
+        /// the standard.
+        ///
+        /// See [`MapParams`](signature_method::MapParams) for details.
+        #[cfg(feature = "alloc")]
+        pub fn map_params<F>(self, f: F) -> Builder<'a, signature_method::MapParams<SM, F>, C, T>
+        where
+            F: FnMut(&mut alloc::vec::Vec<(String, String)>),
+        {
+            Builder {
+                signature_method: signature_method::MapParams::new(self.signature_method, f),
+                client: self.client,
+                token: self.token,
+                options: self.options,
+            }
+        }
+    }
+}
+
+doc_auto_cfg! {
+    #[cfg(feature = "async")]
+    impl<'a, SM, C, T> Builder<'a, SM, C, T>
+    where
+        SM: AsyncSignatureMethod + Clone,
+        C: AsRef<str>,
+        T: AsRef<str>,
+    {
+        /// Same as `authorize` except that this signs the request with an `AsyncSignatureMethod`.
+        pub async fn authorize_async<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, AsyncError<<SM::Sign as AsyncSign>::Error>>
+        where
+            U: Display,
+            R: Request + ?Sized,
+        {
+            let signature_method = self.signature_method.clone();
+            signature_method::async_sign::serialize(request, signature_method, |sm| {
+                serializer::auth::Authorizer::authorization(
+                    method,
+                    uri,
+                    self.client.as_ref(),
+                    self.token.as_ref().map(Credentials::as_ref),
+                    &self.options,
+                    sm,
+                )
+            })
+            .await
+        }
+
+        /// Same as `to_form` except that this signs the request with an `AsyncSignatureMethod`.
+        pub async fn to_form_async<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, AsyncError<<SM::Sign as AsyncSign>::Error>>
+        where
+            U: Display,
+            R: Request + ?Sized,
+        {
+            let signature_method = self.signature_method.clone();
+            signature_method::async_sign::serialize(request, signature_method, |sm| {
+                serializer::auth::Authorizer::form(
+                    method,
+                    uri,
+                    self.client.as_ref(),
+                    self.token.as_ref().map(Credentials::as_ref),
+                    &self.options,
+                    sm,
+                )
+            })
+            .await
+        }
+
+        /// Same as `to_query` except that this signs the request with an `AsyncSignatureMethod`.
+        pub async fn to_query_async<W, R>(
+            &self,
+            method: &str,
+            uri: W,
+            request: &R,
+        ) -> Result<W, AsyncError<<SM::Sign as AsyncSign>::Error>>
+        where
+            W: Display + Write,
+            R: Request + ?Sized,
+        {
+            let signature_method = self.signature_method.clone();
+            signature_method::async_sign::serialize(request, signature_method, |sm| {
+                serializer::auth::Authorizer::query(
+                    method,
+                    uri,
+                    self.client.as_ref(),
+                    self.token.as_ref().map(Credentials::as_ref),
+                    &self.options,
+                    sm,
+                )
+            })
+            .await
+        }
+    }
 }
 
 macro_rules! authorize_shorthand {
@@ -723,6 +1682,38 @@ doc_auto_cfg! {
         inner(method, uri, request, token.as_ref(), signature_method)
     }
 
+    /// Same as [`authorize`] except that this signs the request with an
+    /// [`AsyncSignatureMethod`], e.g. one backed by a remote key management service.
+    #[cfg(feature = "async")]
+    pub async fn authorize_async<U, R, C, T, SM>(
+        method: &str,
+        uri: U,
+        request: &R,
+        token: &Token<C, T>,
+        signature_method: SM,
+    ) -> Result<String, AsyncError<<SM::Sign as AsyncSign>::Error>>
+    where
+        U: Display,
+        R: Request + ?Sized,
+        C: AsRef<str>,
+        T: AsRef<str>,
+        SM: AsyncSignatureMethod,
+    {
+        let token = token.as_ref();
+        let options = auth::Options::new();
+        signature_method::async_sign::serialize(request, signature_method, |sm| {
+            serializer::auth::Authorizer::authorization(
+                method,
+                uri,
+                token.client,
+                Some(token.token),
+                &options,
+                sm,
+            )
+        })
+        .await
+    }
+
     /// Serializes a `Request` to an `x-www-form-urlencoded` string.
     #[cfg(feature = "alloc")]
     pub fn to_form<R>(request: &R) -> String
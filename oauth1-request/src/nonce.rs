@@ -0,0 +1,158 @@
+//! Generation of `oauth_nonce` values that are unique across threads.
+//!
+//! This module is only available when `std` feature is activated.
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::Deref;
+use core::str;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::RngCore;
+
+use crate::serializer::auth::get_rng;
+
+// The counter is encoded as a `u64` regardless of the width of `usize`.
+const COUNTER_LEN: usize = 8;
+const RANDOM_LEN: usize = 6;
+const RAW_LEN: usize = COUNTER_LEN + RANDOM_LEN;
+// Length of unpadded Base64 encoding of `RAW_LEN` bytes.
+const NONCE_LEN: usize = 19;
+
+/// A generator of `oauth_nonce` values that never repeats within the process.
+///
+/// By default, `Builder` generates a random nonce, which is unique with an overwhelming
+/// probability. Some providers reject a request whose nonce has been used with the same timestamp
+/// (and credentials), so a client sending a large number of concurrent requests may want a
+/// stronger guarantee. `NonceGenerator` mixes a counter shared across threads into each nonce,
+/// so that the nonces it generates don't collide until the counter wraps around. The nonces also
+/// contain random bytes so that different processes are unlikely to produce the same nonce.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::nonce::NonceGenerator;
+///
+/// static NONCES: NonceGenerator = NonceGenerator::new();
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+///
+/// let nonce = NONCES.generate();
+/// builder.nonce(nonce.as_str());
+/// let authorization = builder.get("https://example.com/", &());
+///
+/// assert_ne!(NONCES.generate(), nonce);
+/// ```
+pub struct NonceGenerator {
+    counter: AtomicUsize,
+}
+
+/// An `oauth_nonce` value generated by a [`NonceGenerator`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Nonce {
+    buf: [u8; NONCE_LEN],
+}
+
+impl NonceGenerator {
+    /// Creates a new `NonceGenerator`.
+    pub const fn new() -> Self {
+        NonceGenerator {
+            counter: AtomicUsize::new(0),
+        }
+    }
+
+    /// Generates a new nonce.
+    pub fn generate(&self) -> Nonce {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed);
+
+        let mut raw = [0_u8; RAW_LEN];
+        raw[..COUNTER_LEN].copy_from_slice(&(count as u64).to_be_bytes());
+        get_rng().fill_bytes(&mut raw[COUNTER_LEN..]);
+
+        let mut buf = [0_u8; NONCE_LEN];
+        let len = base64::encode_config_slice(raw, base64::URL_SAFE_NO_PAD, &mut buf);
+        debug_assert_eq!(len, NONCE_LEN);
+
+        Nonce { buf }
+    }
+}
+
+impl Debug for NonceGenerator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonceGenerator").finish()
+    }
+}
+
+impl Default for NonceGenerator {
+    fn default() -> Self {
+        NonceGenerator::new()
+    }
+}
+
+impl Nonce {
+    /// Returns the nonce as a string slice.
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.buf).unwrap()
+    }
+}
+
+impl AsRef<str> for Nonce {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Debug for Nonce {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Nonce").field(&self.as_str()).finish()
+    }
+}
+
+impl Deref for Nonce {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for Nonce {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+    use std::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn unique_across_threads() {
+        let generator = Arc::new(NonceGenerator::new());
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = generator.clone();
+                thread::spawn(move || (0..1000).map(|_| generator.generate()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut nonces = HashSet::new();
+        for t in threads {
+            for nonce in t.join().unwrap() {
+                assert!(nonce
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b"-_".contains(&b)));
+                assert!(nonces.insert(nonce));
+            }
+        }
+        assert_eq!(nonces.len(), 4000);
+    }
+}
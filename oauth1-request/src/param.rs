@@ -0,0 +1,152 @@
+//! Names of the OAuth protocol parameters ([RFC 5849 section 3.1.][rfc]).
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.1
+//!
+//! These are the names used by the serializers and signature methods of this crate, which custom
+//! `Serializer` and `Sign` implementations can use instead of string literals.
+//!
+//! ## Example
+//!
+//! ```
+//! # extern crate oauth1_request as oauth;
+//! #
+//! use oauth::param;
+//!
+//! assert_eq!(param::CONSUMER_KEY, "oauth_consumer_key");
+//! assert!(param::SIGNATURE_METHOD.starts_with(param::PREFIX));
+//! ```
+
+/// The prefix shared by the names of all the protocol parameters.
+///
+/// Request parameters with this prefix are reserved for the protocol.
+pub const PREFIX: &str = "oauth_";
+
+/// The `oauth_body_hash` parameter of the OAuth Request Body Hash extension.
+pub const BODY_HASH: &str = "oauth_body_hash";
+/// The `oauth_callback` parameter.
+pub const CALLBACK: &str = "oauth_callback";
+/// The `oauth_consumer_key` parameter.
+pub const CONSUMER_KEY: &str = "oauth_consumer_key";
+/// The `oauth_nonce` parameter.
+pub const NONCE: &str = "oauth_nonce";
+/// The `oauth_signature` parameter.
+pub const SIGNATURE: &str = "oauth_signature";
+/// The `oauth_signature_method` parameter.
+pub const SIGNATURE_METHOD: &str = "oauth_signature_method";
+/// The `oauth_timestamp` parameter.
+pub const TIMESTAMP: &str = "oauth_timestamp";
+/// The `oauth_token` parameter.
+pub const TOKEN: &str = "oauth_token";
+/// The `oauth_verifier` parameter.
+pub const VERIFIER: &str = "oauth_verifier";
+/// The `oauth_version` parameter.
+pub const VERSION: &str = "oauth_version";
+
+/// The names of all the protocol parameters, in dictionary order.
+const ALL: [&str; 10] = [
+    BODY_HASH,
+    CALLBACK,
+    CONSUMER_KEY,
+    NONCE,
+    SIGNATURE,
+    SIGNATURE_METHOD,
+    TIMESTAMP,
+    TOKEN,
+    VERIFIER,
+    VERSION,
+];
+
+/// A set of protocol parameters seen so far, for detecting duplicates without allocating.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Seen(u16);
+
+/// The kinds of the protocol parameters that are fed to [`Sign::oauth_parameter`].
+///
+/// This excludes `oauth_signature`, which is not a part of the signature base string. Variants
+/// may be added as new protocol parameters are supported, so the enum is marked as
+/// `#[non_exhaustive]`.
+///
+/// [`Sign::oauth_parameter`]: crate::signature_method::Sign::oauth_parameter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OAuthParamKind {
+    /// The `oauth_body_hash` parameter.
+    BodyHash,
+    /// The `oauth_callback` parameter.
+    Callback,
+    /// The `oauth_consumer_key` parameter.
+    ConsumerKey,
+    /// The `oauth_nonce` parameter.
+    Nonce,
+    /// The `oauth_signature_method` parameter.
+    SignatureMethod,
+    /// The `oauth_timestamp` parameter.
+    Timestamp,
+    /// The `oauth_token` parameter.
+    Token,
+    /// The `oauth_verifier` parameter.
+    Verifier,
+    /// The `oauth_version` parameter.
+    Version,
+}
+
+impl OAuthParamKind {
+    /// Returns the name of the parameter, e.g. `"oauth_nonce"` for `OAuthParamKind::Nonce`.
+    pub fn name(self) -> &'static str {
+        match self {
+            OAuthParamKind::BodyHash => BODY_HASH,
+            OAuthParamKind::Callback => CALLBACK,
+            OAuthParamKind::ConsumerKey => CONSUMER_KEY,
+            OAuthParamKind::Nonce => NONCE,
+            OAuthParamKind::SignatureMethod => SIGNATURE_METHOD,
+            OAuthParamKind::Timestamp => TIMESTAMP,
+            OAuthParamKind::Token => TOKEN,
+            OAuthParamKind::Verifier => VERIFIER,
+            OAuthParamKind::Version => VERSION,
+        }
+    }
+}
+
+/// Returns the name of the first protocol parameter that appears more than once in `keys`, if
+/// any.
+///
+/// The OAuth standard ([RFC 5849 section 3.1.][rfc]) allows each protocol parameter to appear only
+/// once in a request, and servers reject a request otherwise. This typically happens when a
+/// request type has a field named like a protocol parameter, e.g. `oauth_token`, which the
+/// `Authorizer` also adds. Keys that are not protocol parameters are ignored.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.1
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::param;
+///
+/// let keys = ["oauth_token", "q", "q", "oauth_nonce", "oauth_token"];
+/// assert_eq!(param::detect_duplicate(&keys), Some(param::TOKEN));
+/// assert_eq!(param::detect_duplicate(&keys[..4]), None);
+/// ```
+pub fn detect_duplicate<I>(keys: I) -> Option<&'static str>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut seen = Seen::default();
+    keys.into_iter().find_map(|key| seen.insert(key.as_ref()))
+}
+
+impl Seen {
+    /// Records `key` and returns its name if it is a protocol parameter that has been recorded
+    /// before.
+    pub(crate) fn insert(&mut self, key: &str) -> Option<&'static str> {
+        let i = ALL.iter().position(|&name| name == key)?;
+        let bit = 1 << i;
+        if self.0 & bit != 0 {
+            return Some(ALL[i]);
+        }
+        self.0 |= bit;
+        None
+    }
+}
@@ -0,0 +1,35 @@
+//! A prelude re-exporting the traits and the most commonly used types of this crate.
+//!
+//! Glob-import this module to bring them into scope at once. Items are only added to this module
+//! when they are useful to most users, so the glob import is unlikely to conflict with your own
+//! items as the crate grows.
+//!
+//! ## Example
+//!
+#![cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#![cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+//! use oauth1_request::prelude::*;
+//!
+//! let request = ParameterList::new([("q", "rust")]);
+//! assert_eq!(request.serialize(oauth1_request::serializer::Urlencoder::form()), "q=rust");
+//!
+//! let token = Token::from_parts("consumer", "secret", "token", "secret");
+//! let authorization = Builder::with_token(token, HMAC_SHA1).get("https://example.com/", &request);
+//! assert!(authorization.starts_with("OAuth "));
+//! ```
+
+pub use crate::request::ParameterList;
+pub use crate::serializer::{Serializer, SerializerExt};
+pub use crate::signature_method::{Sign, Signature, SignatureMethod};
+// `Request` is the derive macro as well with `derive` feature.
+pub use crate::{Builder, Credentials, Request, Token};
+
+doc_auto_cfg! {
+    #[cfg(feature = "async")]
+    pub use crate::signature_method::{AsyncSign, AsyncSignatureMethod};
+    #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
+    pub use crate::signature_method::{HmacSha1, HMAC_SHA1};
+    pub use crate::signature_method::Plaintext;
+    #[cfg(feature = "alloc")]
+    pub use crate::signature_method::PLAINTEXT;
+}
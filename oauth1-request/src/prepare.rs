@@ -0,0 +1,438 @@
+//! Two-phase signing, which lets you inspect and modify a request before it is signed.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+use core::num::NonZeroU64;
+
+use percent_encoding::percent_decode_str;
+
+use crate::param;
+use crate::serializer::auth::{Encoding, HeaderEncoding, Options};
+use crate::signature_method::{DigestStrength, Sign, Signature, SignatureMethod};
+use crate::util::{percent_encode, PercentEncode};
+
+/// A request whose parameters have been collected but not signed yet, returned by
+/// [`Builder::prepare`](crate::Builder::prepare).
+///
+/// The parameters (including the `oauth_*` ones except for `oauth_signature`) are held as
+/// key-value pairs percent-encoded as in the request ([RFC 5849 section 3.6.][rfc]). Modifying
+/// them only affects the signature and the `oauth_*` parameters written to the `Authorization`
+/// header, so make sure to send the other parameters in the request as they are in the
+/// `PreparedRequest`.
+///
+/// Call [`finalize`](Self::finalize) to sign the request.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.6
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let request = oauth::ParameterList::new([("q", "a b")]);
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+/// builder.normalize_uri(true);
+/// let mut prepared = builder.prepare("GET", "https://example.com:443/search", &request);
+///
+/// assert_eq!(prepared.uri(), "https://example.com/search");
+/// assert!(prepared.parameters().contains(&("q".into(), "a%20b".into())));
+/// prepared.set_nonce("a-nonce-of-my-own");
+/// println!("{}", prepared.base_string());
+///
+/// let authorization = prepared.finalize().unwrap();
+/// assert!(authorization.contains(r#"oauth_nonce="a-nonce-of-my-own""#));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PreparedRequest<S> {
+    sign: S,
+    method: String,
+    uri: String,
+    parameters: Vec<(String, String)>,
+    // A blank `Authorization` header carrying the `realm` and the position of `oauth_signature`.
+    header: HeaderEncoding<String>,
+}
+
+/// A `SignatureMethod` that collects the signature base string for a `PreparedRequest`.
+pub(crate) struct Prepare<SM>(pub(crate) SM);
+
+pub(crate) struct PrepareSign<S>(PreparedRequest<S>);
+
+impl<S: Sign> PreparedRequest<S> {
+    /// Returns the HTTP request method.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the base string URI, i.e. the request URI normalized as in
+    /// [RFC 5849 section 3.4.1.2.][rfc]
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the parameters to be signed in dictionary order.
+    pub fn parameters(&self) -> &[(String, String)] {
+        &self.parameters
+    }
+
+    /// Returns a mutable reference to the parameters to be signed.
+    ///
+    /// The parameters are sorted again before signing, so added parameters need not be inserted
+    /// at the right position.
+    pub fn parameters_mut(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.parameters
+    }
+
+    /// Returns the `oauth_nonce` value, if any.
+    pub fn nonce(&self) -> Option<&str> {
+        self.get(param::NONCE)
+    }
+
+    /// Sets the `oauth_nonce` value.
+    pub fn set_nonce(&mut self, nonce: &str) -> &mut Self {
+        self.set(param::NONCE, percent_encode(nonce).to_string())
+    }
+
+    /// Returns the `oauth_timestamp` value, if any.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.get(param::TIMESTAMP).and_then(|t| t.parse().ok())
+    }
+
+    /// Sets the `oauth_timestamp` value.
+    pub fn set_timestamp(&mut self, timestamp: NonZeroU64) -> &mut Self {
+        self.set(param::TIMESTAMP, timestamp.to_string())
+    }
+
+    /// Returns the signature base string that [`finalize`](Self::finalize) would sign.
+    pub fn base_string(&self) -> String {
+        let mut parameters = self.parameters.clone();
+        parameters.sort();
+
+        let mut ret = String::new();
+        write!(ret, "{}&{}&", self.method, PercentEncode(&self.uri)).unwrap();
+        for (i, (key, value)) in parameters.iter().enumerate() {
+            if i > 0 {
+                ret.push_str("%26");
+            }
+            write!(ret, "{}%3D{}", PercentEncode(key), PercentEncode(value)).unwrap();
+        }
+        ret
+    }
+
+    /// Signs the request and returns an HTTP `Authorization` header value.
+    ///
+    /// Returns an error if formatting the signature fails, e.g. when the signing operation of
+    /// the signature method fails.
+    pub fn finalize(self) -> Result<String, fmt::Error> {
+        let (header, parameters, signature) = self.sign();
+        authorization(header, &parameters, signature)
+    }
+
+    /// Signs the request and returns the blank `Authorization` header, the sorted parameters and
+    /// the signature.
+    fn sign(self) -> (HeaderEncoding<String>, Vec<(String, String)>, S::Signature) {
+        let PreparedRequest {
+            mut sign,
+            method,
+            uri,
+            mut parameters,
+            header,
+        } = self;
+        parameters.sort();
+
+        sign.request_method(&method);
+        sign.uri(PercentEncode(&uri));
+        let mut key_buf = String::new();
+        for (i, (key, value)) in parameters.iter().enumerate() {
+            if i > 0 {
+                sign.delimiter();
+            }
+            key_buf.clear();
+            write!(key_buf, "{}", PercentEncode(key)).unwrap();
+            sign.parameter(&key_buf, PercentEncode(value));
+        }
+
+        (header, parameters, sign.end())
+    }
+
+    /// Signs the request and returns an HTTP `Authorization` header value along with an
+    /// equivalent `curl` command line, e.g. to share a failing request with the support team of
+    /// the server.
+    ///
+    /// The command is generated from the same parameters as the header, so it sends exactly what
+    /// has been signed. The request parameters are sent as an `x-www-form-urlencoded` body if the
+    /// method is `POST`, or in the query part of [`uri`](Self::uri) otherwise.
+    ///
+    /// The values of the parameters carrying credentials, i.e. `oauth_token`, `oauth_verifier`
+    /// and `x_auth_password`, are replaced with a placeholder in the command. So is the
+    /// `oauth_signature` value with `PLAINTEXT` signature method, whose signature consists of the
+    /// secrets themselves.
+    ///
+    /// Returns an error in the same cases as [`finalize`](Self::finalize).
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+    #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let request = oauth::ParameterList::new([("q", "it's")]);
+    ///
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// let prepared = builder.prepare("GET", "https://example.com/search", &request);
+    ///
+    /// let (authorization, curl) = prepared.finalize_with_curl().unwrap();
+    /// assert_eq!(
+    ///     curl,
+    ///     format!(
+    ///         "curl -X 'GET' -H 'Authorization: {}' 'https://example.com/search?q=it%27s'",
+    ///         authorization.replace(r#"oauth_token="token""#, r#"oauth_token="<redacted>""#),
+    ///     ),
+    /// );
+    /// ```
+    pub fn finalize_with_curl(self) -> Result<(String, String), fmt::Error> {
+        let redact_signature = self.sign.get_signature_method_name() == "PLAINTEXT";
+        let method = self.method.clone();
+        let uri = self.uri.clone();
+        let (blank_header, mut parameters, signature) = self.sign();
+
+        let authorization = self::authorization(blank_header.clone(), &parameters, &signature)?;
+
+        for (key, value) in &mut parameters {
+            if REDACTED_PARAMETERS.contains(&&**key) {
+                *value = REDACTED.into();
+            }
+        }
+
+        let mut query = String::new();
+        for (key, value) in &parameters {
+            if !key.starts_with(param::PREFIX) {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                write!(query, "{}={}", key, value).unwrap();
+            }
+        }
+
+        let mut curl = String::from("curl -X ");
+        push_quoted(&mut curl, &method);
+        curl.push_str(" -H ");
+        let mut header = String::from("Authorization: ");
+        if redact_signature {
+            header.push_str(&self::authorization(blank_header, &parameters, REDACTED)?);
+        } else {
+            header.push_str(&self::authorization(blank_header, &parameters, &signature)?);
+        }
+        push_quoted(&mut curl, &header);
+        let target = if query.is_empty() {
+            uri
+        } else if method == "POST" {
+            curl.push_str(" --data-raw ");
+            push_quoted(&mut curl, &query);
+            uri
+        } else {
+            uri + "?" + &query
+        };
+        curl.push(' ');
+        push_quoted(&mut curl, &target);
+
+        Ok((authorization, curl))
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|&(k, _)| k == key)
+            .map(|(_, v)| &**v)
+    }
+
+    fn set(&mut self, key: &str, value: String) -> &mut Self {
+        if let Some(pair) = self.parameters.iter_mut().find(|(k, _)| k == key) {
+            pair.1 = value;
+        } else {
+            self.parameters.push((key.into(), value));
+        }
+        self
+    }
+}
+
+impl<S: Sign> PrepareSign<S> {
+    pub(crate) fn into_prepared(self, options: &Options<'_>) -> PreparedRequest<S> {
+        let mut ret = self.0;
+        ret.header = HeaderEncoding::with_options(String::with_capacity(512), options);
+        ret
+    }
+}
+
+impl<SM: SignatureMethod> SignatureMethod for Prepare<SM> {
+    type Sign = PrepareSign<SM::Sign>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        PrepareSign::new(self.0.sign_with(client_secret, token_secret))
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+        PrepareSign::new(self.0.sign_with_raw_key(signing_key))
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.0.is_symmetric()
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        self.0.digest_strength()
+    }
+}
+
+impl<S: Sign> PrepareSign<S> {
+    fn new(sign: S) -> Self {
+        PrepareSign(PreparedRequest {
+            sign,
+            method: String::new(),
+            uri: String::new(),
+            parameters: Vec::new(),
+            header: HeaderEncoding::new(String::new()),
+        })
+    }
+}
+
+impl<S: Sign> Sign for PrepareSign<S> {
+    type Signature = NoSignature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.0.sign.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.0.method = method.into();
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.0.uri = decode(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        // The arguments are in the form of the signature base string, i.e. percent-encoded twice.
+        self.0.parameters.push((decode(key), decode(value)));
+    }
+
+    fn delimiter(&mut self) {}
+
+    fn end(self) -> NoSignature {
+        NoSignature
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.0.sign.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.0.sign.use_timestamp()
+    }
+}
+
+/// The parameters whose values are replaced with [`REDACTED`] by
+/// [`PreparedRequest::finalize_with_curl`].
+const REDACTED_PARAMETERS: &[&str] = &[param::TOKEN, param::VERIFIER, "x_auth_password"];
+
+const REDACTED: &str = "<redacted>";
+
+/// Writes the `oauth_*` parameters among `parameters` to an `Authorization` header value.
+fn authorization<V: Signature>(
+    mut encoding: HeaderEncoding<String>,
+    parameters: &[(String, String)],
+    signature: V,
+) -> Result<String, fmt::Error> {
+    parameters
+        .iter()
+        .filter(|(key, _)| key.starts_with(param::PREFIX))
+        .try_for_each(|(key, value)| encoding.oauth_parameter(key, value))
+        .and_then(|()| encoding.end(signature))
+}
+
+/// Appends `s` to `buf` as a single-quoted shell word.
+fn push_quoted(buf: &mut String, s: &str) {
+    buf.push('\'');
+    for c in s.chars() {
+        if c == '\'' {
+            buf.push_str("'\\''");
+        } else {
+            buf.push(c);
+        }
+    }
+    buf.push('\'');
+}
+
+/// The "signature" of a `PrepareSign`, which does not sign the request.
+pub(crate) struct NoSignature;
+
+impl Display for NoSignature {
+    fn fmt(&self, _: &mut Formatter<'_>) -> fmt::Result {
+        // A `NoSignature` is never written to the output.
+        Err(fmt::Error)
+    }
+}
+
+impl Signature for NoSignature {
+    fn write_to<W: Write + ?Sized>(&self, _: &mut W) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
+
+fn decode<T: Display>(input: T) -> String {
+    let input = input.to_string();
+    percent_decode_str(&input).decode_utf8_lossy().into_owned()
+}
+
+#[cfg(all(test, feature = "hmac-sha1"))]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use crate::{Builder, ParameterList, Token, HMAC_SHA1, PLAINTEXT};
+
+    #[test]
+    fn finalize_with_curl_plaintext() {
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let builder = Builder::with_token(token, PLAINTEXT);
+        let (authorization, curl) = builder
+            .prepare("GET", "https://example.com/", &())
+            .finalize_with_curl()
+            .unwrap();
+        assert!(authorization.contains(r#"oauth_signature="cs%26ts""#));
+        assert!(!curl.contains("cs%26ts"));
+        assert!(curl.contains(r#"oauth_signature="<redacted>""#));
+    }
+
+    #[test]
+    fn finalize_with_curl_credentials() {
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let request = ParameterList::new([("x_auth_password", "hunter2"), ("q", "rust")]);
+        let mut builder = Builder::with_token(token, HMAC_SHA1);
+        builder
+            .verifier("vf")
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1000));
+        let (authorization, curl) = builder
+            .prepare("POST", "https://example.com/", &request)
+            .finalize_with_curl()
+            .unwrap();
+        assert!(authorization.contains(r#"oauth_token="tk""#));
+        assert!(authorization.contains(r#"oauth_verifier="vf""#));
+        for secret in &["tk", "vf", "hunter2"] {
+            assert!(!curl.contains(secret), "{}", curl);
+        }
+        assert!(curl.contains(r#"oauth_token="<redacted>""#));
+        assert!(curl.contains("q=rust&x_auth_password=<redacted>"));
+        // The signature is not secret with `HMAC-SHA1`.
+        let signature = authorization.split("oauth_signature=").nth(1).unwrap();
+        assert!(curl.contains(signature.split(',').next().unwrap()));
+    }
+}
@@ -1,13 +1,18 @@
 //! Requests to be authorized with OAuth.
 
-pub mod parameter_list;
+doc_auto_cfg! {
+    pub mod parameter_list;
+    #[cfg(feature = "reflect")]
+    pub mod reflect;
+    pub mod validate;
+}
 
 pub use self::parameter_list::ParameterList;
+pub use self::validate::ValidationError;
 
 use core::fmt::Display;
 
-use crate::serializer::{Serializer, SerializerExt};
-use crate::util::OAuthParameter;
+use crate::serializer::{Serializer, SerializerDriver, SerializerExt};
 
 /// Types that represent an HTTP request to be authorized with OAuth.
 ///
@@ -23,6 +28,21 @@ pub trait Request {
     fn serialize<S>(&self, serializer: S) -> S::Output
     where
         S: Serializer;
+
+    /// Checks the parameters of the request before it is signed.
+    ///
+    /// The fallible signing methods like [`Builder::try_authorize`](crate::Builder::try_authorize)
+    /// call this and return [`Error::Validation`](crate::Error::Validation) without signing the
+    /// request if it returns an error. The other signing methods don't call it.
+    ///
+    /// The default implementation returns `Ok(())`. [`#[derive(Request)]`][derive] overrides it
+    /// when the struct or any of its fields has a `#[oauth1(validate = path)]` attribute. See the
+    /// [`validate`](validate) module for an example.
+    ///
+    /// [derive]: oauth1_request_derive::Request
+    fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
 }
 
 /// A wrapper type that implements [`Request`] with key-value pairs returned by the wrapped
@@ -73,6 +93,10 @@ where
     {
         (**self).serialize(serializer)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        (**self).validate()
+    }
 }
 
 impl<'a, R> Request for &'a mut R
@@ -85,6 +109,10 @@ where
     {
         (**self).serialize(serializer)
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        (**self).validate()
+    }
 }
 
 /// Authorizes a request with no query pairs.
@@ -110,6 +138,14 @@ impl<R: Request> Request for Option<R> {
             serializer.end()
         }
     }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(ref this) = *self {
+            this.validate()
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl<I, K, V> AssertSorted<I>
@@ -135,26 +171,14 @@ where
     K: AsRef<str>,
     V: Display,
 {
-    fn serialize<S>(&self, mut serializer: S) -> S::Output
+    fn serialize<S>(&self, serializer: S) -> S::Output
     where
         S: Serializer,
     {
-        let mut next_param = OAuthParameter::default();
-
+        let mut driver = SerializerDriver::new(serializer);
         for (k, v) in self.inner.clone() {
-            let k = k.as_ref();
-            while next_param < *k {
-                next_param.serialize(&mut serializer);
-                next_param = next_param.next();
-            }
-            serializer.serialize_parameter(k, v);
+            driver.serialize_parameter(k.as_ref(), v);
         }
-
-        while next_param != OAuthParameter::None {
-            next_param.serialize(&mut serializer);
-            next_param = next_param.next();
-        }
-
-        serializer.end()
+        driver.end()
     }
 }
@@ -8,10 +8,12 @@ use core::marker::PhantomData;
 
 use super::Request;
 use crate::serializer::Serializer;
+use crate::util::PercentEncode;
 
 /// A [`Request`] with dynamic list of key-value parameter pairs.
 ///
-/// This is like an array of `(K, V)` but the parameters are guaranteed to be sorted alphabetically.
+/// This is like an array of `(K, V)` but the parameters are guaranteed to be sorted alphabetically
+/// by their keys and percent-encoded values, as required by the signature base string.
 ///
 /// ## Example
 ///
@@ -239,8 +241,11 @@ where
     let (ref kl, ref vl) = *lhs.borrow();
     let (ref kr, ref vr) = *rhs.borrow();
     return inner(kl.as_ref(), vl, kr.as_ref(), vr);
+    // The signature base string sorts the parameters by their percent-encoded forms
+    // (RFC 5849 section 3.4.1.3.2.), which may differ from the order of the raw values (e.g. `"@"`
+    // and `"1"`). The keys are already percent-encoded.
     fn inner<V: Display>(kl: &str, vl: &V, kr: &str, vr: &V) -> Ordering {
-        (kl, fmt_cmp::Cmp(vl)).cmp(&(kr, fmt_cmp::Cmp(vr)))
+        (kl, fmt_cmp::Cmp(PercentEncode(vl))).cmp(&(kr, fmt_cmp::Cmp(PercentEncode(vr))))
     }
 }
 
@@ -0,0 +1,90 @@
+//! Run-time reflection of the parameters of a [`Request`].
+//!
+//! This module is only available when `reflect` feature is activated.
+
+use super::Request;
+
+/// A [`Request`] that can describe the parameters it serializes.
+///
+/// This trait is implemented by [`#[derive(Request)]`][oauth1_request_derive::Request] when the
+/// `reflect` feature is activated. It is useful for generating documentation or client-side
+/// validation from request types.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::request::reflect::{Describe, ParameterInfo};
+///
+/// #[derive(oauth::Request)]
+/// struct CreateComment {
+///     article_id: u64,
+///     #[oauth1(rename = "body")]
+///     text: String,
+///     #[oauth1(encoded)]
+///     tag: Option<&'static str>,
+///     #[oauth1(skip)]
+///     _draft: bool,
+/// }
+///
+/// assert_eq!(
+///     CreateComment::describe(),
+///     [
+///         ParameterInfo::new("article_id"),
+///         ParameterInfo::new("body"),
+///         ParameterInfo::new("tag").optional(true).encoded(true),
+///     ],
+/// );
+/// ```
+pub trait Describe: Request {
+    /// Returns the parameters of the request in the order they are serialized.
+    ///
+    /// The OAuth protocol parameters (`oauth_*`) are not included.
+    fn describe() -> &'static [ParameterInfo];
+}
+
+/// Information about a parameter of a [`Request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParameterInfo {
+    name: &'static str,
+    optional: bool,
+    encoded: bool,
+}
+
+impl ParameterInfo {
+    /// Creates a new `ParameterInfo` describing a required parameter named `name`.
+    pub const fn new(name: &'static str) -> Self {
+        ParameterInfo {
+            name,
+            optional: false,
+            encoded: false,
+        }
+    }
+
+    /// Sets whether the parameter may be absent from the serialized request.
+    pub const fn optional(self, optional: bool) -> Self {
+        ParameterInfo { optional, ..self }
+    }
+
+    /// Sets whether the value of the parameter is serialized as-is, without percent encoding.
+    pub const fn encoded(self, encoded: bool) -> Self {
+        ParameterInfo { encoded, ..self }
+    }
+
+    /// Returns the name of the parameter.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns whether the parameter may be absent from the serialized request.
+    pub const fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns whether the value of the parameter is serialized as-is, without percent encoding.
+    pub const fn is_encoded(&self) -> bool {
+        self.encoded
+    }
+}
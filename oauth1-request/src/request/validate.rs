@@ -0,0 +1,115 @@
+//! Validation of [`Request`](super::Request)s before they are signed.
+//!
+//! [`Request::validate`](super::Request::validate) lets API wrapper crates reject malformed requests (e.g. an out-of-range
+//! count or mutually exclusive parameters) with a consistent error type instead of sending them to
+//! the server. [`#[derive(Request)]`][oauth1_request_derive::Request] implements it with the
+//! functions given in `#[oauth1(validate = path)]` attributes, and the fallible signing methods
+//! like [`Builder::try_authorize`](crate::Builder::try_authorize) call it before signing.
+//!
+//! ## Example
+//!
+#![cfg_attr(all(feature = "alloc", feature = "derive"), doc = " ```")]
+#![cfg_attr(not(all(feature = "alloc", feature = "derive")), doc = " ```ignore")]
+//! # extern crate oauth1_request as oauth;
+//! #
+//! use oauth::request::ValidationError;
+//! use oauth::Request;
+//!
+//! #[derive(oauth::Request)]
+//! #[oauth1(validate = either_id_or_name)]
+//! struct GetUser<'a> {
+//!     #[oauth1(validate = valid_count)]
+//!     count: u32,
+//!     id: Option<u64>,
+//!     name: Option<&'a str>,
+//! }
+//!
+//! fn valid_count(count: &u32) -> Result<(), ValidationError> {
+//!     if (1..=200).contains(count) {
+//!         Ok(())
+//!     } else {
+//!         Err(ValidationError::new("must be between 1 and 200"))
+//!     }
+//! }
+//!
+//! fn either_id_or_name(request: &GetUser<'_>) -> Result<(), ValidationError> {
+//!     if request.id.is_some() == request.name.is_some() {
+//!         Err(ValidationError::new("exactly one of `id` and `name` must be set"))
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let request = GetUser { count: 0, id: Some(42), name: None };
+//! let e = request.validate().unwrap_err();
+//! assert_eq!(e.parameter(), Some("count"));
+//! assert_eq!(e.to_string(), "invalid parameter `count`: must be between 1 and 200");
+//!
+//! let request = GetUser { count: 20, id: None, name: None };
+//! assert_eq!(request.validate().unwrap_err().parameter(), None);
+//!
+//! let client = oauth::Credentials::new("consumer", "secret");
+//! let builder = oauth::Builder::<_, _>::new(client, oauth::PLAINTEXT);
+//! let uri = "https://example.com/users/show";
+//! assert!(matches!(
+//!     builder.try_authorize("GET", uri, &request),
+//!     Err(oauth::Error::Validation(_)),
+//! ));
+//!
+//! let request = GetUser { count: 20, id: None, name: Some("alice") };
+//! assert!(request.validate().is_ok());
+//! assert!(builder.try_authorize("GET", uri, &request).is_ok());
+//! ```
+
+use core::fmt::{self, Display, Formatter};
+
+/// An error returned by [`Request::validate`](super::Request::validate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    parameter: Option<&'static str>,
+    message: &'static str,
+}
+
+impl ValidationError {
+    /// Creates a new `ValidationError` with a message describing the problem.
+    pub const fn new(message: &'static str) -> Self {
+        ValidationError {
+            parameter: None,
+            message,
+        }
+    }
+
+    /// Sets the name of the parameter that caused the error.
+    ///
+    /// The derive macro calls this with the (`rename`-ed) field name for errors returned by
+    /// field-level validation functions.
+    pub const fn with_parameter(self, parameter: &'static str) -> Self {
+        ValidationError {
+            parameter: Some(parameter),
+            ..self
+        }
+    }
+
+    /// Returns the name of the parameter that caused the error, if any.
+    pub const fn parameter(&self) -> Option<&'static str> {
+        self.parameter
+    }
+
+    /// Returns the message describing the problem.
+    pub const fn message(&self) -> &'static str {
+        self.message
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(parameter) = self.parameter {
+            write!(f, "invalid parameter `{}`: {}", parameter, self.message)
+        } else {
+            f.write_str(self.message)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
@@ -2,15 +2,23 @@
 
 doc_auto_cfg! {
     pub mod auth;
+    #[cfg(feature = "alloc")]
+    pub mod collector;
+    pub mod driver;
     #[cfg(feature = "test")]
     pub mod recorder;
+    pub mod tee;
     pub mod urlencode;
 }
 
 doc_auto_cfg! {
     pub use auth::Authorizer;
+    #[cfg(feature = "alloc")]
+    pub use collector::Collector;
+    pub use driver::SerializerDriver;
     #[cfg(feature = "test")]
     pub use recorder::Recorder;
+    pub use tee::Tee;
     pub use urlencode::Urlencoder;
 }
 
@@ -48,6 +56,31 @@ pub use skip_serialize_oauth_parameters;
 ///
 /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
 ///
+/// ## Order of method calls
+///
+/// Implementations of `Serializer` can rely on the following guarantees, as long as the
+/// `Request` implementation is correct:
+///
+/// - Each `serialize_oauth_*` method is called exactly once, even if the corresponding parameter
+///   is not going to be included in the request (e.g. when there is no `oauth_callback` to
+///   send).
+/// - All the method calls, including `serialize_parameter` and `serialize_parameter_encoded`, are
+///   made in ascending dictionary order of the parameter names, e.g. `serialize_oauth_nonce` is
+///   called after `serialize_parameter("oauth_n", ..)` and before
+///   `serialize_parameter("oauth_o", ..)`.
+/// - `end` is called last.
+///
+/// [`SerializerDriver`] and [`SerializerExt::serialize_oauth_parameters`] help implementors of
+/// `Request` meet these requirements.
+///
+/// ## Stability
+///
+/// A new `serialize_oauth_*` method may be added to this trait in a minor version to support a
+/// new `oauth_*` parameter (e.g. `oauth_body_hash`). Such a method will be added with a default
+/// implementation that does nothing, so existing implementations of `Serializer` keep compiling
+/// and keep producing the same output. `SerializerDriver`, `serialize_oauth_parameters` and
+/// `#[derive(Request)]` will call the new method at its position in the dictionary order.
+///
 #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
 #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
 /// # extern crate oauth1_request as oauth;
@@ -102,7 +135,8 @@ pub trait Serializer {
 
     /// Serializes a key-value pair.
     ///
-    /// The serializer percent encodes the value, but not the key.
+    /// The serializer percent encodes the value, but not the key, which must be already
+    /// percent-encoded.
     ///
     /// # Panics
     ///
@@ -193,13 +227,17 @@ mod tests {
     extern crate std;
 
     use std::println;
-    use std::string::{String, ToString};
+    #[cfg(any(feature = "alloc", feature = "hmac-sha1"))]
+    use std::string::String;
+    use std::string::ToString;
 
     use super::*;
 
     #[cfg(feature = "hmac-sha1")]
     use crate::signature_method::HmacSha1;
-    use crate::signature_method::{Plaintext, Sign, SignatureMethod};
+    #[cfg(feature = "alloc")]
+    use crate::signature_method::Plaintext;
+    use crate::signature_method::{Sign, SignatureMethod};
     #[cfg(any(feature = "alloc", feature = "hmac-sha1"))]
     use crate::Credentials;
 
@@ -229,13 +267,24 @@ mod tests {
             println!("token_secret: {:?}", token_secret);
             InspectSign(self.0.sign_with(client_secret, token_secret))
         }
+
+        fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+            println!("signing_key: {:?}", signing_key);
+            InspectSign(self.0.sign_with_raw_key(signing_key))
+        }
     }
 
-    #[derive(Clone, Debug)]
-    struct AssertImpl<'a>(
-        #[cfg(feature = "hmac-sha1")] Authorizer<'a, HmacSha1, String>,
-        Authorizer<'a, Plaintext<String>, String>,
-    );
+    // Asserts that the serializers implement `Clone` and `Debug`.
+    #[cfg(any(feature = "alloc", feature = "hmac-sha1"))]
+    const _: fn() = || {
+        fn assert_impl<T: Clone + core::fmt::Debug>() {}
+        #[cfg(feature = "hmac-sha1")]
+        assert_impl::<Authorizer<'static, HmacSha1, auth::HeaderEncoding<String>>>();
+        #[cfg(feature = "alloc")]
+        assert_impl::<Authorizer<'static, Plaintext<String>, auth::FormEncoding<String>>>();
+        #[cfg(feature = "alloc")]
+        assert_impl::<Collector<'static, Plaintext<String>>>();
+    };
 
     impl<S: Sign> Sign for InspectSign<S> {
         type Signature = S::Signature;
@@ -381,6 +430,77 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn large_value() {
+        use core::fmt::{self, Formatter};
+        use core::num::NonZeroU64;
+        use std::format;
+
+        use hmac012::{Hmac, Mac};
+
+        use crate::serializer::auth;
+
+        const PIECE: &str = "a+b/";
+        const ENCODED_PIECE: &str = "a%252Bb%252F";
+        const REPEAT: usize = 10 * 1024 * 1024 / PIECE.len();
+
+        // A 10 MB value that is never materialized in memory at once.
+        struct Large;
+
+        impl Display for Large {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                for _ in 0..REPEAT {
+                    f.write_str(PIECE)?;
+                }
+                Ok(())
+            }
+        }
+
+        let client = Credentials::new(CK, CS);
+        let token = Credentials::new(AK, AS);
+        let mut options = auth::Options::new();
+        options.nonce(NONCE).timestamp(NonZeroU64::new(TIMESTAMP));
+        let mut auth = Authorizer::authorization_with_buf(
+            String::new(),
+            "POST",
+            "https://example.com/upload",
+            client,
+            Some(token),
+            &options,
+            crate::HMAC_SHA1,
+        );
+        auth.serialize_parameter("data", Large);
+        auth.serialize_oauth_parameters();
+        let authorization = auth.end();
+
+        let mut mac =
+            Hmac::<sha1::Sha1>::new_from_slice(format!("{}&{}", CS, AS).as_bytes()).unwrap();
+        mac.update(b"POST&https%3A%2F%2Fexample.com%2Fupload&data%3D");
+        for _ in 0..REPEAT {
+            mac.update(ENCODED_PIECE.as_bytes());
+        }
+        mac.update(
+            format!(
+                "%26oauth_consumer_key%3D{}\
+                 %26oauth_nonce%3D{}\
+                 %26oauth_signature_method%3DHMAC-SHA1\
+                 %26oauth_timestamp%3D{}\
+                 %26oauth_token%3D{}",
+                CK, NONCE, TIMESTAMP, AK,
+            )
+            .as_bytes(),
+        );
+        let expected = base64::encode(mac.finalize().into_bytes());
+        let expected = crate::util::percent_encode(&expected).to_string();
+
+        assert!(
+            authorization.ends_with(&format!("oauth_signature=\"{}\"", expected)),
+            "{}",
+            authorization,
+        );
+    }
+
     #[cfg(all(feature = "alloc", debug_assertions))]
     #[test]
     #[should_panic(
@@ -404,4 +524,114 @@ mod tests {
         ser.serialize_parameter_encoded("foo", true);
         ser.serialize_parameter("bar", "ばー！");
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn duplicate_is_not_checked_by_end() {
+        let client = Credentials::new(CK, CS);
+        let token = Credentials::new(AK, AS);
+        let mut options = auth::Options::default();
+        options.reject_duplicate_parameters(true);
+        let mut ser = Authorizer::authorization_with_buf(
+            String::new(),
+            "",
+            "",
+            client,
+            Some(token),
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+        ser.serialize_oauth_token();
+        ser.serialize_parameter("oauth_token", AK);
+        assert!(matches!(
+            ser.clone().try_end(),
+            Err(crate::Error::DuplicateParameter(crate::param::TOKEN)),
+        ));
+        ser.end();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn error_on_duplicate() {
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::default();
+        options.reject_duplicate_parameters(true);
+        let ser = Authorizer::authorization_with_buf(
+            String::new(),
+            "",
+            "",
+            client,
+            None,
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+        let mut ser = auth::TryAuthorizer::new(ser);
+        ser.serialize_parameter("oauth_consumer_key", CK);
+        ser.serialize_oauth_consumer_key();
+        ser.serialize_parameter("oauth_nonce", "nonce");
+        ser.serialize_parameter("oauth_nonce", "nonce");
+        assert_eq!(
+            ser.end(),
+            Err(crate::Error::DuplicateParameter(crate::param::CONSUMER_KEY)),
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn insecure_uri() {
+        use std::format;
+
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::default();
+        options.require_https(true);
+        let mut ser = Authorizer::authorization_with_buf(
+            String::new(),
+            "GET",
+            "http://example.com/",
+            client,
+            None,
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+        ser.serialize_oauth_consumer_key();
+        assert!(matches!(
+            ser.clone().try_end(),
+            Err(crate::Error::InsecureUri(_)),
+        ));
+        // The infallible `end` doesn't enforce the policy.
+        assert_eq!(
+            ser.end(),
+            format!(
+                r#"OAuth oauth_consumer_key="{}",oauth_signature="{}%26""#,
+                CK, CS
+            ),
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn error_on_failing_display() {
+        struct Failing;
+
+        impl Display for Failing {
+            fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                Err(core::fmt::Error)
+            }
+        }
+
+        let client = Credentials::new(CK, CS);
+        let options = auth::Options::default();
+        let ser = Authorizer::authorization(
+            "GET",
+            "https://example.com/",
+            client,
+            None,
+            &options,
+            HmacSha1::new(),
+        );
+        let mut ser = auth::TryAuthorizer::new(ser);
+        ser.serialize_parameter("foo", Failing);
+        ser.serialize_oauth_consumer_key();
+        assert_eq!(ser.end(), Err(crate::Error::Format));
+    }
 }
@@ -1,53 +1,114 @@
 //! An OAuth 1.0 `Authorization` header serializer.
 
-use core::fmt::{self, Display, Write};
+mod audit;
+mod base_string_uri;
+mod differential;
+pub mod encoding;
+mod require_https;
+#[cfg(feature = "serde")]
+mod serde_imp;
+
+#[cfg(feature = "alloc")]
+pub use self::encoding::SignaturePosition;
+pub use self::encoding::{Encoding, FormEncoding, HeaderEncoding, QueryEncoding};
+pub use self::require_https::InsecureUriError;
+
+use core::fmt::{self, Debug, Display, Formatter, Write};
 use core::num::NonZeroU64;
 use core::str;
 
 use rand::prelude::*;
 
-use crate::signature_method::{Sign, SignatureMethod};
+use crate::param;
+use crate::signature_method::{DigestStrength, Sign, SignatureMethod};
 use crate::util::*;
-use crate::Credentials;
+use crate::{Credentials, Error, Secret};
 
-use super::{Serializer, Urlencoder};
+pub(crate) use self::audit::Audit;
+pub(crate) use self::base_string_uri::BaseStringUri;
+use self::differential::Differential;
+use super::Serializer;
 
 cfg_type_param_hack! {
     /// A `Serializer` that signs a request and produces OAuth 1.0 `oauth_*` parameter values.
     ///
-    /// The resulting parameter values are either written to an HTTP `Authorization` header value or
-    /// URI query/`x-www-form-urlencoded` string (along with the other request parameters)
-    /// depending on the constructor you use.
+    /// The resulting parameter values are written to the output in the format determined by the
+    /// [`Encoding`] type parameter `E`, i.e. an HTTP `Authorization` header value
+    /// ([`HeaderEncoding`]), a URI query ([`QueryEncoding`]) or an `x-www-form-urlencoded` string
+    /// ([`FormEncoding`]), depending on the constructor you use.
     #[derive(Clone, Debug)]
     pub struct Authorizer<
         'a,
         SM: SignatureMethod,
-        #[cfg(feature = "alloc")] W = alloc::string::String,
-        #[cfg(not(feature = "alloc"))] W,
+        #[cfg(feature = "alloc")] E = HeaderEncoding<alloc::string::String>,
+        #[cfg(not(feature = "alloc"))] E,
     > {
         consumer_key: &'a str,
         token: Option<&'a str>,
         options: &'a Options<'a>,
-        data: Data<W>,
+        encoding: E,
         sign: SM::Sign,
         append_delim_to_sign: bool,
+        last_default_parameter: Option<usize>,
+        audit: Audit,
+        differential: Differential,
+        seen: param::Seen,
+        error: Option<Error>,
+        // A violation of the policy options like `require_https`, which is only reported by
+        // `try_end`.
+        rejection: Option<Error>,
         #[cfg(all(feature = "alloc", debug_assertions))]
         prev_key: alloc::string::String,
     }
 }
 
-#[derive(Clone, Debug)]
-enum Data<W> {
-    Authorization(W),
-    Urlencode(Urlencoder<W>),
-}
+/// A `Serializer` that signs a request with an [`Authorizer`] and returns an error if the request
+/// cannot be signed, e.g. when its URI is rejected by [`Options::check_https`].
+///
+/// Its output is that of [`Authorizer::try_end`].
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::auth::{Authorizer, Options, TryAuthorizer};
+/// use oauth::Request;
+///
+/// let client = oauth::Credentials::new("consumer", "secret");
+/// let mut options = Options::new();
+/// options.require_https(true);
+/// let authorizer = Authorizer::authorization(
+///     "GET",
+///     "http://example.com/",
+///     client,
+///     None,
+///     &options,
+///     oauth::HMAC_SHA1,
+/// );
+///
+/// let result = ().serialize(TryAuthorizer::new(authorizer));
+/// assert!(matches!(result, Err(oauth::Error::InsecureUri(_))));
+/// ```
+pub struct TryAuthorizer<'a, SM: SignatureMethod, E>(Authorizer<'a, SM, E>);
+
+/// A `Serializer` that returns the `Sign` and `Encoding` of the `Authorizer` instead of signing.
+#[cfg(feature = "alloc")]
+pub(crate) struct IntoParts<'a, SM: SignatureMethod, E>(pub(crate) Authorizer<'a, SM, E>);
 
 options! {
     /// Optional OAuth parameters.
-    #[derive(Clone, Debug, Default)]
+    #[derive(Clone, Default, PartialEq, Eq, Hash)]
     pub struct Options<'a> {
         /// Creates a blank `Options` with default values (`None`).
         new;
+        /// Sets `oauth_body_hash` parameter of the OAuth Request Body Hash extension.
+        ///
+        /// The value is the Base64-encoded hash of the request body, which can be computed with
+        /// [`BodyHash`](crate::body_hash::BodyHash) (with `body-hash` feature). It should only be
+        /// set for requests whose body is not `x-www-form-urlencoded`.
+        body_hash: Option<&'a str>,
         /// Sets `oauth_callback` parameter.
         callback: Option<&'a str>,
         /// Sets `oauth_verifier` parameter.
@@ -63,12 +124,232 @@ options! {
         timestamp: Option<NonZeroU64>,
         /// Sets whether to include `oauth_version="1.0"` parameter in the `Authorization` header.
         version: bool,
+        /// Sets the `realm` parameter of the `Authorization` header
+        /// ([RFC 5849 section 3.5.1.][rfc]).
+        ///
+        /// The realm is written first in the header and is not signed. It is ignored when
+        /// producing a URI query or an `x-www-form-urlencoded` string.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.5.1
+        realm: Option<&'a str>,
+        /// Sets the signing key to be used verbatim instead of the one constructed from the
+        /// client and token secrets.
+        ///
+        /// See [`SignatureMethod::sign_with_raw_key`] for details.
+        raw_signing_key: Option<&'a str>,
+        /// Sets parameters to be included in every request in addition to the request's own
+        /// parameters, e.g. `format=json` required by an API.
+        ///
+        /// The parameters are merged with the request's parameters in dictionary order and signed
+        /// along with them. When producing a URI query or an `x-www-form-urlencoded` string, they
+        /// are also written to the output. When producing an `Authorization` header, you need to
+        /// send them in the request yourself.
+        ///
+        /// The list doesn't need to be sorted, but its keys should not overlap with the keys of
+        /// the request's parameters.
+        default_parameters: &'a [(&'a str, &'a str)],
+        /// Sets names of request parameters to be excluded from the signature.
+        ///
+        /// This is useful when an intermediary (e.g. a CDN) appends parameters like tracking
+        /// parameters or cache busters to the request after it is signed and the server ignores
+        /// them when verifying the signature. A name ending with `*` matches any parameter name
+        /// that starts with the part before the `*`, e.g. `utm_*` matches `utm_source`.
+        ///
+        /// The `oauth_*` parameters are always signed.
+        ignored_parameters: &'a [&'a str],
+        /// Sets the host (and optionally the port, as `host:port`) to be used in the signature
+        /// base string in place of the authority part of the request URI.
+        ///
+        /// This is useful when the request is sent to a host other than the one the server
+        /// expects, e.g. a gateway that forwards the request with an overridden `Host` header.
+        host: Option<&'a str>,
+        /// Sets whether to normalize the scheme and the authority part of the request URI in the
+        /// signature base string as the OAuth standard ([RFC 5849 section 3.4.1.2.][rfc])
+        /// requires, i.e. to lowercase them and to remove the default port of the scheme (`80`
+        /// for `http` and `443` for `https`).
+        ///
+        /// The request URI is signed as is by default, for compatibility with servers that
+        /// compute their base string from the URI verbatim. Non-default ports are always kept.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+        normalize_uri: bool,
+        /// Sets whether to include the port in the signature base string even if it is the default
+        /// port of the scheme (`80` for `http` and `443` for `https`).
+        ///
+        /// The OAuth standard ([RFC 5849 section 3.4.1.2.][rfc]) says that the default port MUST
+        /// be excluded, which [`normalize_uri`](Options::normalize_uri) does. Some servers
+        /// (incorrectly) include it in their base string even if the request URI omits it.
+        /// Non-default ports are always included.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+        include_default_port: bool,
+        /// Sets whether to resolve dot-segments (`.` and `..`) in the path of the request URI
+        /// when building the signature base string ([RFC 3986 section 5.2.4.][rfc]).
+        ///
+        /// The path is used as is by default.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc3986#section-5.2.4
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        remove_dot_segments: bool,
+        /// Sets whether to remove a trailing slash from the path of the request URI when building
+        /// the signature base string.
+        ///
+        /// A path consisting only of a slash is kept as is. The trailing slash is preserved by
+        /// default.
+        trim_trailing_slash: bool,
+        /// Sets whether to lowercase the path of the request URI when building the signature base
+        /// string.
+        ///
+        /// The scheme and the host are lowercased if [`normalize_uri`](Options::normalize_uri)
+        /// is set as the OAuth standard ([RFC 5849 section 3.4.1.2.][rfc]) requires, while the
+        /// path and the query part are case-sensitive and kept as is by default. Only ASCII
+        /// letters are lowercased, including the hexadecimal digits of percent-encoded octets.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+        lowercase_path: bool,
+        /// Sets whether to refuse to sign requests to URIs that don't use `https`, except for the
+        /// hosts set with [`http_allowed_hosts`](Options::http_allowed_hosts).
+        ///
+        /// This is only enforced when the request is signed with [`Authorizer::try_end`] or
+        /// [`TryAuthorizer`], which return an [`Error::InsecureUri`] for such a URI. The
+        /// infallible [`Serializer::end`] signs the request regardless.
+        require_https: bool,
+        /// Sets the hosts (e.g. `localhost`) that may be accessed over plain `http` when
+        /// [`require_https`](Options::require_https) is set.
+        ///
+        /// The hosts are compared with the host of the request URI, ignoring ASCII case. IPv6
+        /// addresses need to be enclosed in brackets, e.g. `[::1]`.
+        http_allowed_hosts: &'a [&'a str],
+        /// Sets whether to refuse to sign requests in which a protocol parameter appears more than
+        /// once, e.g. when the request has an `oauth_token` parameter in addition to the one
+        /// added for the token credentials.
+        ///
+        /// Like [`require_https`](Options::require_https), this is only enforced by
+        /// [`Authorizer::try_end`] and [`TryAuthorizer`], which return an
+        /// [`Error::DuplicateParameter`] for such a request. See [`param::detect_duplicate`] for
+        /// details.
+        reject_duplicate_parameters: bool,
+        /// Sets whether to refuse to sign requests with the signature methods that are not
+        /// FIPS-approved, i.e. the ones whose
+        /// [`digest_strength`](SignatureMethod::digest_strength) is less than
+        /// [`DigestStrength::Sha256`], like `HMAC-SHA1`, `RSA-SHA1` and `PLAINTEXT`.
+        ///
+        /// Like [`require_https`](Options::require_https), this is only enforced by
+        /// [`Authorizer::try_end`] and [`TryAuthorizer`], which return an
+        /// [`Error::NotFipsApproved`] for such a request. Custom signature methods that don't
+        /// report their digest strength are refused as well.
+        fips: bool,
+        /// Sets the position of the `oauth_signature` parameter in the `Authorization` header.
+        ///
+        /// The signature is written after the other parameters by default. This only affects
+        /// the header produced by [`Authorizer::authorization`] and does not change the signature.
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+        signature_position: SignaturePosition,
+        /// Sets whether to emit an audit event for each signed request.
+        ///
+        /// The event is emitted via [`tracing`](tracing01) at the `INFO` level with the target
+        /// `oauth1_request::audit`. Its `audit` field holds a JSON object of the following form,
+        /// which is meant to be forwarded to a central log store (e.g. a SIEM):
+        ///
+        /// ```json
+        /// {
+        ///     "schema_version": 1,
+        ///     "action": "sign",
+        ///     "method": "GET",
+        ///     "host": "example.com",
+        ///     "path": "/resource",
+        ///     "parameters": ["oauth_consumer_key", "oauth_nonce", "...", "q"],
+        ///     "consumer_key": "dpf43f3p2l4k3l03",
+        ///     "signature_method": "HMAC-SHA1"
+        /// }
+        /// ```
+        ///
+        /// `parameters` lists the names of the signed parameters in the order they were signed.
+        /// Parameter values, secrets and the signature are never included. Members may be added
+        /// to the object without bumping `schema_version`.
+        ///
+        /// Verifying a request with
+        /// [`verify_request_with_options`](crate::verify::gateway::verify_request_with_options)
+        /// emits an event of the same form with `"action": "verify"`, where `parameters` lists
+        /// the names of the parameters of the request other than `oauth_signature` in the order
+        /// they appear in the `Authorization` header, the query and the form body. The event has
+        /// an `outcome` member, which is either `"success"` or `"failure"`, and in the latter
+        /// case, a `problem` member holding the [`Problem`](crate::verify::Problem) code (e.g.
+        /// `"signature_invalid"`). `consumer_key` and `signature_method` are `null` if the
+        /// verification failed before determining them.
+        #[cfg(feature = "tracing")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+        audit_log: bool,
+    }
+}
+
+impl<'a> Options<'a> {
+    /// Checks `uri` against the [`require_https`](Options::require_https) option.
+    ///
+    /// Returns an error if the option is set and `uri` uses a scheme other than `https` and its
+    /// host is not one of the [`http_allowed_hosts`](Options::http_allowed_hosts).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let mut options = oauth::serializer::auth::Options::new();
+    /// options.require_https(true).http_allowed_hosts(&["localhost"]);
+    ///
+    /// assert!(options.check_https("https://example.com/").is_ok());
+    /// assert!(options.check_https("http://localhost:8080/").is_ok());
+    /// assert!(options.check_https("http://example.com/").is_err());
+    /// ```
+    pub fn check_https<T: Display>(&self, uri: T) -> Result<(), InsecureUriError> {
+        if self.require_https {
+            require_https::check(uri, self.http_allowed_hosts)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> Debug for Options<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("Options");
+        f.field("body_hash", &self.body_hash)
+            .field("callback", &self.callback)
+            .field("verifier", &self.verifier)
+            .field("nonce", &self.nonce)
+            .field("timestamp", &self.timestamp)
+            .field("realm", &self.realm)
+            .field("version", &self.version)
+            .field("raw_signing_key", &self.raw_signing_key.map(|_| Secret(())))
+            .field("default_parameters", &self.default_parameters)
+            .field("ignored_parameters", &self.ignored_parameters)
+            .field("host", &self.host)
+            .field("normalize_uri", &self.normalize_uri)
+            .field("include_default_port", &self.include_default_port);
+        #[cfg(feature = "alloc")]
+        f.field("remove_dot_segments", &self.remove_dot_segments);
+        f.field("trim_trailing_slash", &self.trim_trailing_slash);
+        f.field("lowercase_path", &self.lowercase_path);
+        f.field("require_https", &self.require_https);
+        f.field("http_allowed_hosts", &self.http_allowed_hosts);
+        f.field(
+            "reject_duplicate_parameters",
+            &self.reject_duplicate_parameters,
+        );
+        f.field("fips", &self.fips);
+        #[cfg(feature = "alloc")]
+        f.field("signature_position", &self.signature_position);
+        #[cfg(feature = "tracing")]
+        f.field("audit_log", &self.audit_log);
+        f.finish()
     }
 }
 
 doc_auto_cfg! {
     #[cfg(feature = "alloc")]
-    impl<'a, SM: SignatureMethod> Authorizer<'a, SM> {
+    impl<'a, SM: SignatureMethod> Authorizer<'a, SM, HeaderEncoding<alloc::string::String>> {
         /// Creates an `Authorizer` that produces an HTTP `Authorization header value.
         ///
         /// `uri` must not contain a query part.
@@ -96,7 +377,10 @@ doc_auto_cfg! {
                 signature_method,
             )
         }
+    }
 
+    #[cfg(feature = "alloc")]
+    impl<'a, SM: SignatureMethod> Authorizer<'a, SM, FormEncoding<alloc::string::String>> {
         /// Creates an `Authorizer` that produces an `x-www-form-urlencoded` string.
         ///
         /// `uri` must not contain a query part.
@@ -119,7 +403,7 @@ doc_auto_cfg! {
     }
 }
 
-impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
+impl<'a, SM: SignatureMethod, W: Write + Display> Authorizer<'a, SM, QueryEncoding<W>> {
     /// Creates an `Authorizer` that appends a query part to `uri`.
     ///
     /// `uri` must not contain a query part.
@@ -135,19 +419,25 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
         token: Option<Credentials<&'a str>>,
         options: &'a Options<'a>,
         signature_method: SM,
-    ) -> Self
-    where
-        W: Display,
-    {
-        let sign = make_sign(method, &uri, client, token, signature_method);
-        let data = Data::Urlencode(Urlencoder::query(uri));
-        Authorizer::new_(data, sign, client, token, options)
+    ) -> Self {
+        let strength = signature_method.digest_strength();
+        let sign = make_sign(method, &uri, client, token, options, signature_method);
+        let audit = Audit::new(options, method, &uri);
+        let differential = Differential::new(options, method, &uri);
+        let error = options.check_https(&uri).err();
+        let encoding = QueryEncoding::new(uri);
+        let mut ret = Authorizer::new_(encoding, sign, audit, differential, client, token, options);
+        ret.rejection = error.map(Error::from);
+        ret.check_fips(strength);
+        ret
     }
+}
 
+impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, HeaderEncoding<W>> {
     /// Same as `authorization` except that this writes the resulting `Authorization` header value
     /// into `buf`.
     pub fn authorization_with_buf<T: Display>(
-        mut buf: W,
+        buf: W,
         method: &str,
         uri: T,
         client: Credentials<&'a str>,
@@ -155,12 +445,19 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
         options: &'a Options<'a>,
         signature_method: SM,
     ) -> Self {
-        buf.write_str("OAuth ").unwrap();
-        let data = Data::Authorization(buf);
-        let sign = make_sign(method, uri, client, token, signature_method);
-        Authorizer::new_(data, sign, client, token, options)
+        Authorizer::with_encoding(
+            HeaderEncoding::with_options(buf, options),
+            method,
+            uri,
+            client,
+            token,
+            options,
+            signature_method,
+        )
     }
+}
 
+impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, FormEncoding<W>> {
     /// Same with `form` except that this writes the resulting form string into `buf`.
     pub fn form_with_buf<T: Display>(
         buf: W,
@@ -171,14 +468,53 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
         options: &'a Options<'a>,
         signature_method: SM,
     ) -> Self {
-        let data = Data::Urlencode(Urlencoder::form_with_buf(buf));
-        let sign = make_sign(method, uri, client, token, signature_method);
-        Authorizer::new_(data, sign, client, token, options)
+        let encoding = FormEncoding::new(buf);
+        Authorizer::with_encoding(
+            encoding,
+            method,
+            uri,
+            client,
+            token,
+            options,
+            signature_method,
+        )
+    }
+}
+
+impl<'a, SM: SignatureMethod, E: Encoding> Authorizer<'a, SM, E> {
+    /// Creates an `Authorizer` that writes the output with `encoding`.
+    ///
+    /// `uri` must not contain a query part.
+    /// Otherwise, the serializer will produce a wrong signature.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `uri` contains a `'?'` character.
+    pub fn with_encoding<T: Display>(
+        encoding: E,
+        method: &str,
+        uri: T,
+        client: Credentials<&'a str>,
+        token: Option<Credentials<&'a str>>,
+        options: &'a Options<'a>,
+        signature_method: SM,
+    ) -> Self {
+        let audit = Audit::new(options, method, &uri);
+        let differential = Differential::new(options, method, &uri);
+        let error = options.check_https(&uri).err();
+        let strength = signature_method.digest_strength();
+        let sign = make_sign(method, uri, client, token, options, signature_method);
+        let mut ret = Authorizer::new_(encoding, sign, audit, differential, client, token, options);
+        ret.rejection = error.map(Error::from);
+        ret.check_fips(strength);
+        ret
     }
 
     fn new_(
-        data: Data<W>,
+        encoding: E,
         sign: SM::Sign,
+        audit: Audit,
+        differential: Differential,
         client: Credentials<&'a str>,
         token: Option<Credentials<&'a str>>,
         options: &'a Options<'a>,
@@ -189,9 +525,15 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
                     consumer_key: client.identifier,
                     token: token.map(|t| t.identifier),
                     options,
-                    data,
+                    encoding,
                     sign,
                     append_delim_to_sign: false,
+                    last_default_parameter: None,
+                    audit,
+                    differential,
+                    seen: param::Seen::default(),
+                    error: None,
+                    rejection: None,
                     prev_key: alloc::string::String::new(),
                 }
             } else {
@@ -199,9 +541,15 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
                     consumer_key: client.identifier,
                     token: token.map(|t| t.identifier),
                     options,
-                    data,
+                    encoding,
                     sign,
                     append_delim_to_sign: false,
+                    last_default_parameter: None,
+                    audit,
+                    differential,
+                    seen: param::Seen::default(),
+                    error: None,
+                    rejection: None,
                 }
             }
         }
@@ -213,6 +561,7 @@ fn make_sign<SM: SignatureMethod, T: Display>(
     uri: T,
     client: Credentials<&str>,
     token: Option<Credentials<&str>>,
+    options: &Options<'_>,
     signature_method: SM,
 ) -> SM::Sign {
     // This is a no_alloc-equivalent of `assert!(!uri.to_string().contains('?'))`.
@@ -232,31 +581,185 @@ fn make_sign<SM: SignatureMethod, T: Display>(
         write!(AssertNotContainQuestion, "{}", uri).unwrap();
     }
 
-    let mut ret = signature_method.sign_with(client.secret, token.map(|t| t.secret));
+    let mut ret = if let Some(key) = options.raw_signing_key {
+        signature_method.sign_with_raw_key(key)
+    } else {
+        signature_method.sign_with(
+            client.secret.into_inner(),
+            token.map(|t| t.secret.into_inner()),
+        )
+    };
     ret.request_method(method);
-    ret.uri(PercentEncode(uri));
+    ret.uri(PercentEncode(BaseStringUri::new(uri, options)));
 
     ret
 }
 
-impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
+impl<'a, SM: SignatureMethod, E: Encoding> Authorizer<'a, SM, E> {
+    /// Signs the request and writes the signature to the output.
+    ///
+    /// Unlike [`Serializer::end`], this enforces the policy options like
+    /// [`require_https`](Options::require_https), and returns an error if the request violates
+    /// them or if a `Display` implementation returns an error.
+    pub fn try_end(self) -> Result<E::Output, Error> {
+        let (sign, encoding) = self.try_into_parts()?;
+        encoding.end(sign.end()).map_err(|_| Error::Format)
+    }
+
+    /// Finishes serializing the parameters and returns the `Sign` and the `Encoding`, leaving
+    /// signing and writing the signature to the caller.
+    ///
+    /// Like [`Serializer::end`], this doesn't enforce the policy options.
+    ///
+    /// Returns an error if a `Display` implementation returns an error.
+    pub(crate) fn into_parts(mut self) -> Result<(SM::Sign, E), fmt::Error> {
+        self.rejection = None;
+        // Only `Error::Format` remains without the policy violations.
+        self.try_into_parts().map_err(|_| fmt::Error)
+    }
+
+    /// Same as `into_parts`, but returns the first error encountered while serializing the
+    /// request, including the violations of the policy options, instead of panicking.
+    pub(crate) fn try_into_parts(mut self) -> Result<(SM::Sign, E), Error> {
+        self.serialize_default_parameters_before(None);
+
+        if let Some(e) = self.rejection.take().or_else(|| self.error.take()) {
+            return Err(e);
+        }
+
+        let Self {
+            consumer_key,
+            options,
+            encoding,
+            sign,
+            audit,
+            differential,
+            ..
+        } = self;
+        audit.emit(consumer_key, sign.get_signature_method_name());
+        differential.check(options);
+
+        Ok((sign, encoding))
+    }
+
+    /// Records an error that makes the request unsignable. Only the first error is kept.
+    fn fail(&mut self, e: Error) {
+        if self.error.is_none() {
+            self.error = Some(e);
+        }
+    }
+
+    /// Checks the digest strength of the signature method against the `fips` option.
+    fn check_fips(&mut self, strength: DigestStrength) {
+        if self.options.fips && strength < DigestStrength::Sha256 {
+            let name = self.sign.get_signature_method_name();
+            self.reject(Error::NotFipsApproved(name));
+        }
+    }
+
+    /// Records a violation of the policy options. Only the first violation is kept.
+    fn reject(&mut self, e: Error) {
+        if self.rejection.is_none() {
+            self.rejection = Some(e);
+        }
+    }
+
     fn append_to_header_encoded<V: Display>(&mut self, k: &str, v: V) {
+        self.serialize_default_parameters_before(Some(k));
         self.check_dictionary_order(k);
-        match self.data {
-            Data::Authorization(ref mut header) => write!(header, r#"{}="{}","#, k, v).unwrap(),
-            Data::Urlencode(ref mut encoder) => encoder.serialize_parameter_encoded(k, v),
+        self.check_duplicate(k);
+        self.audit.parameter(k);
+        self.differential.oauth_parameter(k, &v);
+        if self.encoding.oauth_parameter(k, v).is_err() {
+            self.fail(Error::Format);
         }
         self.sign_delimiter();
     }
 
+    /// Serializes the default parameters whose keys are less than `key`, or all the remaining ones
+    /// if `key` is `None`.
+    fn serialize_default_parameters_before(&mut self, key: Option<&str>) {
+        let defaults = self.options.default_parameters;
+        while let Some(i) = self.next_default_parameter() {
+            let (k, v) = defaults[i];
+            match key {
+                Some(key) if k >= key => break,
+                _ => {}
+            }
+            self.last_default_parameter = Some(i);
+            self.check_dictionary_order(k);
+            self.check_duplicate(k);
+            if self.encoding.default_parameter(k, v).is_err() {
+                self.fail(Error::Format);
+            }
+            if self.is_ignored(k) {
+                continue;
+            }
+            self.audit.parameter(k);
+            self.sign_delimiter();
+            self.sign_parameter(k, DoublePercentEncode(v));
+        }
+    }
+
+    /// Returns the index of the default parameter following the last serialized one in dictionary
+    /// order, so that the list doesn't need to be sorted. The index breaks ties between identical
+    /// entries.
+    fn next_default_parameter(&self) -> Option<usize> {
+        let defaults = self.options.default_parameters;
+        let last = self.last_default_parameter.map(|i| (defaults[i], i));
+        (0..defaults.len())
+            .map(|i| (defaults[i], i))
+            .filter(|&entry| Some(entry) > last)
+            .min()
+            .map(|(_, i)| i)
+    }
+
+    fn is_ignored(&self, key: &str) -> bool {
+        self.options.ignored_parameters.iter().any(|&pattern| {
+            if let Some(prefix) = pattern.strip_suffix('*') {
+                key.starts_with(prefix)
+            } else {
+                key == pattern
+            }
+        })
+    }
+
+    fn sign_parameter<V: Display>(&mut self, key: &str, value: V) {
+        if !key.contains('%') {
+            self.differential.sign_parameter(key, &value);
+            return self.sign.parameter(key, value);
+        }
+
+        // The key is already percent-encoded, so it needs to be encoded once more in the signature
+        // base string, like the values.
+        self.differential.sign_parameter(PercentEncode(key), &value);
+        if self
+            .sign
+            .parameter_display_key(PercentEncode(key), value)
+            .is_err()
+        {
+            self.fail(Error::Format);
+        }
+    }
+
     fn sign_delimiter(&mut self) {
         if self.append_delim_to_sign {
+            self.differential.sign_delimiter();
             self.sign.delimiter();
         } else {
             self.append_delim_to_sign = true;
         }
     }
 
+    // An incremental version of `param::detect_duplicate`.
+    fn check_duplicate(&mut self, k: &str) {
+        if self.options.reject_duplicate_parameters {
+            if let Some(name) = self.seen.insert(k) {
+                self.reject(Error::DuplicateParameter(name));
+            }
+        }
+    }
+
     fn check_dictionary_order(&mut self, _k: &str) {
         #[cfg(all(feature = "alloc", debug_assertions))]
         {
@@ -275,61 +778,81 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
 }
 
 macro_rules! append_to_header {
-    (@inner $self:expr, $k:ident, $v:expr, $w:expr) => {{
+    (@inner $self:expr, $k:ident($name:expr), $v:expr, $w:expr) => {{
         let this = $self;
-        let k = concat!("oauth_", stringify!($k));
-        this.append_to_header_encoded(k, $v);
+        this.append_to_header_encoded($name, $v);
+        this.differential.sign_parameter($name, &$w);
         this.sign.$k($w);
     }};
-    ($self:expr, encoded $k:ident, $v:expr) => {{
+    ($self:expr, encoded $k:ident($name:expr), $v:expr) => {{
         let v = $v;
-        append_to_header!(@inner $self, $k, v, v);
+        append_to_header!(@inner $self, $k($name), v, v);
     }};
-    ($self:expr, $k:ident, $v:expr) => {{
+    ($self:expr, $k:ident($name:expr), $v:expr) => {{
         let v = $v;
-        append_to_header!(@inner $self, $k, percent_encode(v), DoublePercentEncode(v));
+        append_to_header!(@inner $self, $k($name), percent_encode(v), DoublePercentEncode(v));
     }};
 }
 
-impl<'a, SM: SignatureMethod, W: Write> Serializer for Authorizer<'a, SM, W> {
-    type Output = W;
+impl<'a, SM: SignatureMethod, E: Encoding> Serializer for Authorizer<'a, SM, E> {
+    type Output = E::Output;
 
     fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.serialize_default_parameters_before(Some(key));
         self.check_dictionary_order(key);
+        self.check_duplicate(key);
+        self.differential.parameter(key, &value);
+        if self.is_ignored(key) {
+            return;
+        }
+        self.audit.parameter(key);
         self.sign_delimiter();
-        self.sign.parameter(key, DoublePercentEncode(value));
+        self.sign_parameter(key, DoublePercentEncode(value));
     }
 
     fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
+        self.serialize_default_parameters_before(Some(key));
         self.check_dictionary_order(key);
+        self.check_duplicate(key);
+        self.differential.parameter_encoded(key, &value);
+        if self.is_ignored(key) {
+            return;
+        }
+        self.audit.parameter(key);
         self.sign_delimiter();
-        self.sign.parameter(key, PercentEncode(value));
+        self.sign_parameter(key, PercentEncode(value));
     }
 
     fn serialize_oauth_callback(&mut self) {
+        // `oauth_body_hash` comes right before `oauth_callback` in dictionary order.
+        if let Some(h) = self.options.body_hash {
+            append_to_header!(&mut *self, body_hash(param::BODY_HASH), h);
+        }
         if let Some(c) = self.options.callback {
-            append_to_header!(self, callback, c);
+            append_to_header!(self, callback(param::CALLBACK), c);
         }
     }
 
     fn serialize_oauth_consumer_key(&mut self) {
-        append_to_header!(self, consumer_key, self.consumer_key);
+        append_to_header!(self, consumer_key(param::CONSUMER_KEY), self.consumer_key);
     }
 
     fn serialize_oauth_nonce(&mut self) {
         if self.sign.use_nonce() {
             if let Some(n) = self.options.nonce {
-                append_to_header!(self, nonce, n);
+                append_to_header!(self, nonce(param::NONCE), n);
             } else {
                 let mut nonce_buf = Default::default();
-                append_to_header!(self, encoded nonce, gen_nonce(&mut nonce_buf, &mut get_rng()));
+                let nonce = gen_nonce(&mut nonce_buf, &mut get_rng());
+                append_to_header!(self, encoded nonce(param::NONCE), nonce);
             }
         }
     }
 
     fn serialize_oauth_signature_method(&mut self) {
         let v = self.sign.get_signature_method_name();
-        self.append_to_header_encoded("oauth_signature_method", v);
+        self.append_to_header_encoded(param::SIGNATURE_METHOD, v);
+        self.differential.sign_parameter(param::SIGNATURE_METHOD, v);
         self.sign.signature_method();
     }
 
@@ -340,47 +863,140 @@ impl<'a, SM: SignatureMethod, W: Write> Serializer for Authorizer<'a, SM, W> {
             } else {
                 get_current_timestamp()
             };
-            append_to_header!(self, encoded timestamp, t);
+            append_to_header!(self, encoded timestamp(param::TIMESTAMP), t);
         }
     }
 
     fn serialize_oauth_token(&mut self) {
         if let Some(t) = self.token {
-            append_to_header!(self, token, t);
+            append_to_header!(self, token(param::TOKEN), t);
         }
     }
 
     fn serialize_oauth_verifier(&mut self) {
         if let Some(v) = self.options.verifier {
-            append_to_header!(self, verifier, v);
+            append_to_header!(self, verifier(param::VERIFIER), v);
         }
     }
 
     fn serialize_oauth_version(&mut self) {
         if self.options.version {
-            self.append_to_header_encoded("oauth_version", "1.0");
+            self.append_to_header_encoded(param::VERSION, "1.0");
+            self.differential.sign_parameter(param::VERSION, "1.0");
             self.sign.version();
         }
     }
 
-    fn end(self) -> W {
-        let Self { data, sign, .. } = self;
+    /// Signs the request and writes the signature to the output.
+    ///
+    /// The policy options like [`require_https`](Options::require_https) are not enforced. Use
+    /// [`try_end`](Authorizer::try_end) or [`TryAuthorizer`] to enforce them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `Display` implementation returns an error, like `ToString::to_string` does.
+    fn end(self) -> E::Output {
+        self.into_parts()
+            .and_then(|(sign, encoding)| encoding.end(sign.end()))
+            .expect("a Display implementation returned an error unexpectedly")
+    }
+}
 
-        match data {
-            Data::Authorization(mut header) => {
-                header.write_str("oauth_signature=").unwrap();
-                write!(header, r#""{}""#, sign.end()).unwrap();
-                header
-            }
-            Data::Urlencode(mut encoder) => {
-                encoder.serialize_parameter_encoded("oauth_signature", sign.end());
-                encoder.end()
-            }
+macro_rules! forward_serializer {
+    () => {
+        fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
+            self.0.serialize_parameter(key, value);
+        }
+
+        fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
+            self.0.serialize_parameter_encoded(key, value);
+        }
+
+        fn serialize_oauth_callback(&mut self) {
+            self.0.serialize_oauth_callback();
+        }
+
+        fn serialize_oauth_consumer_key(&mut self) {
+            self.0.serialize_oauth_consumer_key();
+        }
+
+        fn serialize_oauth_nonce(&mut self) {
+            self.0.serialize_oauth_nonce();
+        }
+
+        fn serialize_oauth_signature_method(&mut self) {
+            self.0.serialize_oauth_signature_method();
+        }
+
+        fn serialize_oauth_timestamp(&mut self) {
+            self.0.serialize_oauth_timestamp();
         }
+
+        fn serialize_oauth_token(&mut self) {
+            self.0.serialize_oauth_token();
+        }
+
+        fn serialize_oauth_verifier(&mut self) {
+            self.0.serialize_oauth_verifier();
+        }
+
+        fn serialize_oauth_version(&mut self) {
+            self.0.serialize_oauth_version();
+        }
+    };
+}
+
+impl<'a, SM: SignatureMethod, E> TryAuthorizer<'a, SM, E> {
+    /// Creates a `TryAuthorizer` that signs a request with `authorizer`.
+    pub fn new(authorizer: Authorizer<'a, SM, E>) -> Self {
+        TryAuthorizer(authorizer)
+    }
+}
+
+impl<'a, SM, E> Clone for TryAuthorizer<'a, SM, E>
+where
+    SM: SignatureMethod + Clone,
+    SM::Sign: Clone,
+    E: Clone,
+{
+    fn clone(&self) -> Self {
+        TryAuthorizer(self.0.clone())
+    }
+}
+
+impl<'a, SM, E> Debug for TryAuthorizer<'a, SM, E>
+where
+    SM: SignatureMethod + Debug,
+    SM::Sign: Debug,
+    E: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TryAuthorizer").field(&self.0).finish()
+    }
+}
+
+impl<'a, SM: SignatureMethod, E: Encoding> Serializer for TryAuthorizer<'a, SM, E> {
+    type Output = Result<E::Output, Error>;
+
+    forward_serializer!();
+
+    fn end(self) -> Result<E::Output, Error> {
+        self.0.try_end()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, SM: SignatureMethod, E: Encoding> Serializer for IntoParts<'a, SM, E> {
+    type Output = Result<(SM::Sign, E), fmt::Error>;
+
+    forward_serializer!();
+
+    fn end(self) -> Result<(SM::Sign, E), fmt::Error> {
+        self.0.into_parts()
     }
 }
 
-fn get_current_timestamp() -> u64 {
+pub(crate) fn get_current_timestamp() -> u64 {
     cfg_if::cfg_if! {
         // `std::time::SystemTime::now` is not supported and panics on `wasm32-unknown-unknown` target
         if #[cfg(all(feature = "js", target_arch = "wasm32", target_os = "unknown"))] {
@@ -402,7 +1018,7 @@ fn get_current_timestamp() -> u64 {
     }
 }
 
-fn get_rng() -> impl RngCore + CryptoRng {
+pub(crate) fn get_rng() -> impl RngCore + CryptoRng {
     cfg_if::cfg_if! {
         if #[cfg(feature = "std")] {
             thread_rng()
@@ -421,9 +1037,12 @@ fn get_rng() -> impl RngCore + CryptoRng {
 //     P = 1 - (2^72 - 1)/(2^72) * (2^72 - 2)/(2^72) * ... * (2^72 - 999999)/(2^72)
 // (birthday problem), and the expected number of seconds it takes until getting a collision with
 // the same timestamp is 1/P.
-const NONCE_LEN: usize = 12;
+pub(crate) const NONCE_LEN: usize = 12;
 
-fn gen_nonce<'a, R: RngCore + CryptoRng>(buf: &'a mut [u8; NONCE_LEN], rng: &mut R) -> &'a str {
+pub(crate) fn gen_nonce<'a, R>(buf: &'a mut [u8; NONCE_LEN], rng: &mut R) -> &'a str
+where
+    R: RngCore + ?Sized,
+{
     let mut rand = [0_u8; NONCE_LEN * 3 / 4];
     rng.fill_bytes(&mut rand);
 
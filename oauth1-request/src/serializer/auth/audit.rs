@@ -0,0 +1,253 @@
+//! Audit events for signed and verified requests, emitted via `tracing` when
+//! `Options::audit_log` is set.
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "tracing")] {
+        use alloc::boxed::Box;
+        use alloc::string::{String, ToString};
+        use alloc::vec::Vec;
+        use core::fmt::{self, Display, Write};
+
+        use crate::verify::Problem;
+
+        use super::Options;
+
+        /// The `target` of the audit events.
+        const TARGET: &str = "oauth1_request::audit";
+
+        /// The version of the schema of the JSON object in the `audit` field of the events.
+        ///
+        /// This must be bumped whenever a member is removed or its meaning is changed.
+        const SCHEMA_VERSION: u32 = 1;
+
+        #[derive(Clone, Debug)]
+        pub struct Audit {
+            record: Option<Box<Record>>,
+        }
+
+        #[derive(Clone, Debug)]
+        struct Record {
+            action: &'static str,
+            method: String,
+            uri: String,
+            parameters: Vec<String>,
+            consumer_key: Option<String>,
+            signature_method: Option<String>,
+        }
+
+        impl Audit {
+            pub fn new<T: Display>(options: &Options<'_>, method: &str, uri: T) -> Self {
+                Audit::with_action(options, "sign", method, uri)
+            }
+
+            pub fn verification<T: Display>(options: &Options<'_>, method: &str, uri: T) -> Self {
+                Audit::with_action(options, "verify", method, uri)
+            }
+
+            fn with_action<T: Display>(
+                options: &Options<'_>,
+                action: &'static str,
+                method: &str,
+                uri: T,
+            ) -> Self {
+                let record = if options.audit_log
+                    && tracing01::enabled!(target: TARGET, tracing01::Level::INFO)
+                {
+                    Some(Box::new(Record {
+                        action,
+                        method: method.to_string(),
+                        uri: uri.to_string(),
+                        parameters: Vec::new(),
+                        consumer_key: None,
+                        signature_method: None,
+                    }))
+                } else {
+                    None
+                };
+                Audit { record }
+            }
+
+            pub fn parameter(&mut self, key: &str) {
+                if let Some(ref mut record) = self.record {
+                    record.parameters.push(key.to_string());
+                }
+            }
+
+            pub fn consumer_key(&mut self, consumer_key: &str) {
+                if let Some(ref mut record) = self.record {
+                    record.consumer_key = Some(consumer_key.to_string());
+                }
+            }
+
+            pub fn signature_method(&mut self, signature_method: &str) {
+                if let Some(ref mut record) = self.record {
+                    record.signature_method = Some(signature_method.to_string());
+                }
+            }
+
+            pub fn emit(mut self, consumer_key: &str, signature_method: &str) {
+                self.consumer_key(consumer_key);
+                self.signature_method(signature_method);
+                if let Some(record) = self.record {
+                    let mut json = String::new();
+                    write_json(&mut json, &record, None).unwrap();
+                    tracing01::info!(target: TARGET, audit = %json, "signed an OAuth 1.0 request");
+                }
+            }
+
+            /// Emits a verification event. `problem` is `None` if the verification succeeded.
+            pub fn emit_verification(self, problem: Option<Problem>) {
+                if let Some(record) = self.record {
+                    let mut json = String::new();
+                    write_json(&mut json, &record, Some(problem)).unwrap();
+                    tracing01::info!(
+                        target: TARGET,
+                        audit = %json,
+                        "verified an OAuth 1.0 request"
+                    );
+                }
+            }
+        }
+
+        fn write_json<W: Write>(
+            mut w: W,
+            record: &Record,
+            outcome: Option<Option<Problem>>,
+        ) -> fmt::Result {
+            let (host, path) = split_uri(&record.uri);
+            write!(w, r#"{{"schema_version":{},"action":"#, SCHEMA_VERSION)?;
+            write_json_str(&mut w, record.action)?;
+            w.write_str(r#","method":"#)?;
+            write_json_str(&mut w, &record.method)?;
+            w.write_str(r#","host":"#)?;
+            write_json_str(&mut w, host)?;
+            w.write_str(r#","path":"#)?;
+            write_json_str(&mut w, path)?;
+            w.write_str(r#","parameters":["#)?;
+            for (i, key) in record.parameters.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_json_str(&mut w, key)?;
+            }
+            w.write_str(r#"],"consumer_key":"#)?;
+            write_json_opt_str(&mut w, record.consumer_key.as_deref())?;
+            w.write_str(r#","signature_method":"#)?;
+            write_json_opt_str(&mut w, record.signature_method.as_deref())?;
+            match outcome {
+                Some(None) => w.write_str(r#","outcome":"success""#)?,
+                Some(Some(problem)) => {
+                    w.write_str(r#","outcome":"failure","problem":"#)?;
+                    write_json_str(&mut w, problem.as_str())?;
+                }
+                None => {}
+            }
+            w.write_char('}')
+        }
+
+        fn write_json_opt_str<W: Write>(mut w: W, s: Option<&str>) -> fmt::Result {
+            match s {
+                Some(s) => write_json_str(w, s),
+                None => w.write_str("null"),
+            }
+        }
+
+        fn write_json_str<W: Write>(mut w: W, s: &str) -> fmt::Result {
+            w.write_char('"')?;
+            for c in s.chars() {
+                match c {
+                    '"' => w.write_str("\\\"")?,
+                    '\\' => w.write_str("\\\\")?,
+                    c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+                    c => w.write_char(c)?,
+                }
+            }
+            w.write_char('"')
+        }
+
+        /// Splits an absolute URI into its host (including the port, if any) and path.
+        fn split_uri(uri: &str) -> (&str, &str) {
+            let rest = uri.find("://").map_or(uri, |i| &uri[i + 3..]);
+            let (authority, path) = match rest.find('/') {
+                Some(i) => rest.split_at(i),
+                None => (rest, ""),
+            };
+            let host = authority.rfind('@').map_or(authority, |i| &authority[i + 1..]);
+            (host, path)
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn json() {
+                let mut record = Record {
+                    action: "sign",
+                    method: "GET".into(),
+                    uri: "https://user@example.com:8443/a\"b".into(),
+                    parameters: alloc::vec!["oauth_consumer_key".into(), "q".into()],
+                    consumer_key: Some("key\n".into()),
+                    signature_method: Some("HMAC-SHA1".into()),
+                };
+                let mut json = String::new();
+                write_json(&mut json, &record, None).unwrap();
+                assert_eq!(
+                    json,
+                    r#"{"schema_version":1,"action":"sign","method":"GET","host":"example.com:8443","path":"/a\"b","parameters":["oauth_consumer_key","q"],"consumer_key":"key\u000a","signature_method":"HMAC-SHA1"}"#,
+                );
+
+                record.action = "verify";
+                record.signature_method = None;
+                json.clear();
+                write_json(&mut json, &record, Some(Some(Problem::SignatureInvalid))).unwrap();
+                assert_eq!(
+                    json,
+                    r#"{"schema_version":1,"action":"verify","method":"GET","host":"example.com:8443","path":"/a\"b","parameters":["oauth_consumer_key","q"],"consumer_key":"key\u000a","signature_method":null,"outcome":"failure","problem":"signature_invalid"}"#,
+                );
+            }
+
+            #[test]
+            fn split() {
+                assert_eq!(split_uri("https://example.com"), ("example.com", ""));
+                assert_eq!(split_uri("https://example.com/"), ("example.com", "/"));
+                assert_eq!(split_uri("http://u:p@example.com:80/a/b"), ("example.com:80", "/a/b"));
+            }
+        }
+    } else {
+        use core::fmt::Display;
+
+        #[cfg(feature = "alloc")]
+        use crate::verify::Problem;
+
+        use super::Options;
+
+        #[derive(Clone, Debug)]
+        pub struct Audit;
+
+        impl Audit {
+            pub fn new<T: Display>(_: &Options<'_>, _: &str, _: T) -> Self {
+                Audit
+            }
+
+            // The verification methods are only used by `verify::gateway`, which requires `alloc`.
+            #[cfg(feature = "alloc")]
+            pub fn verification<T: Display>(_: &Options<'_>, _: &str, _: T) -> Self {
+                Audit
+            }
+
+            pub fn parameter(&mut self, _: &str) {}
+
+            #[cfg(feature = "alloc")]
+            pub fn consumer_key(&mut self, _: &str) {}
+
+            #[cfg(feature = "alloc")]
+            pub fn signature_method(&mut self, _: &str) {}
+
+            pub fn emit(self, _: &str, _: &str) {}
+
+            #[cfg(feature = "alloc")]
+            pub fn emit_verification(self, _: Option<Problem>) {}
+        }
+    }
+}
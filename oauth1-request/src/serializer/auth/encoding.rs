@@ -0,0 +1,299 @@
+//! Output formats of [`Authorizer`](super::Authorizer).
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+use crate::param;
+use crate::serializer::Urlencoder;
+use crate::signature_method::Signature;
+
+use super::Options;
+
+/// An output format of an [`Authorizer`](super::Authorizer).
+///
+/// `Authorizer` takes care of signing the request and determining the `oauth_*` parameter
+/// values, and an `Encoding` writes them to the output. The encodings provided by this crate are
+/// [`HeaderEncoding`], [`QueryEncoding`] and [`FormEncoding`], and you can implement the trait to
+/// support another output format and pass it to [`Authorizer::with_encoding`].
+///
+/// The methods return an error if writing to the output or formatting a value fails, e.g. when
+/// the signature cannot be formatted because a `Display` implementation of a request parameter
+/// returned an error. `Authorizer` reports it as [`Error::Format`](crate::Error::Format).
+///
+/// [`Authorizer::with_encoding`]: super::Authorizer::with_encoding
+pub trait Encoding {
+    /// The type of the value returned by `Authorizer::end`.
+    type Output;
+
+    /// Writes an `oauth_*` parameter. `value` is already percent-encoded.
+    fn oauth_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result;
+
+    /// Writes a parameter set by [`Options::default_parameters`](super::Options::default_parameters).
+    /// `value` is not percent-encoded.
+    fn default_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result;
+
+    /// Writes the `oauth_signature` parameter and returns the output.
+    ///
+    /// `signature` is already percent-encoded.
+    fn end<V: Signature>(self, signature: V) -> Result<Self::Output, fmt::Error>;
+}
+
+/// An [`Encoding`] that produces an HTTP `Authorization` header value.
+///
+/// The default parameters are not written to the output since they are not allowed in the
+/// header.
+#[derive(Clone, Debug)]
+pub struct HeaderEncoding<W> {
+    buf: W,
+    // Errors from writing to `buf` in `new` and `realm` are deferred until the next parameter is
+    // written.
+    result: fmt::Result,
+    #[cfg(feature = "alloc")]
+    position: SignaturePosition,
+    // The parameters to be written after `oauth_signature`, each followed by a comma.
+    #[cfg(feature = "alloc")]
+    tail: String,
+}
+
+doc_auto_cfg! {
+    /// The position of the `oauth_signature` parameter in an `Authorization` header produced by
+    /// [`HeaderEncoding`].
+    ///
+    /// The position does not affect the signature since the OAuth standard does not care about
+    /// the order of the parameters in the header.
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum SignaturePosition {
+        /// Writes `oauth_signature` after all the other parameters. This is the default.
+        Last,
+        /// Writes `oauth_signature` before all the other parameters.
+        First,
+        /// Writes `oauth_signature` in dictionary order along with the other parameters, i.e.
+        /// between `oauth_nonce` and `oauth_signature_method`.
+        Sorted,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for SignaturePosition {
+    fn default() -> Self {
+        SignaturePosition::Last
+    }
+}
+
+/// An [`Encoding`] that appends a query part to a URI.
+#[derive(Clone, Debug)]
+pub struct QueryEncoding<W> {
+    encoder: Urlencoder<W>,
+}
+
+/// An [`Encoding`] that produces an `x-www-form-urlencoded` string.
+#[derive(Clone, Debug)]
+pub struct FormEncoding<W> {
+    encoder: Urlencoder<W>,
+}
+
+impl<W: Write> HeaderEncoding<W> {
+    /// Creates a `HeaderEncoding` that writes the header value into `buf`.
+    pub fn new(mut buf: W) -> Self {
+        let result = buf.write_str("OAuth ");
+        HeaderEncoding {
+            buf,
+            result,
+            #[cfg(feature = "alloc")]
+            position: SignaturePosition::Last,
+            #[cfg(feature = "alloc")]
+            tail: String::new(),
+        }
+    }
+
+    /// Writes the `realm` parameter ([RFC 5849 section 3.5.1.][rfc]), which is not signed.
+    ///
+    /// This must be called before any other parameter is written.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.5.1
+    pub fn realm(&mut self, realm: &str) {
+        fn write_realm<W: Write>(buf: &mut W, realm: &str) -> fmt::Result {
+            buf.write_str(r#"realm=""#)?;
+            for c in realm.chars() {
+                if c == '"' || c == '\\' {
+                    buf.write_char('\\')?;
+                }
+                buf.write_char(c)?;
+            }
+            buf.write_str(r#"","#)
+        }
+
+        if self.result.is_ok() {
+            self.result = write_realm(&mut self.buf, realm);
+        }
+    }
+
+    /// Creates a `HeaderEncoding` that writes the header value into `buf` with the `realm` and
+    /// the position of `oauth_signature` set in `options`.
+    pub(crate) fn with_options(buf: W, options: &Options<'_>) -> Self {
+        #[cfg(feature = "alloc")]
+        let mut ret = HeaderEncoding::with_signature_position(buf, options.signature_position);
+        #[cfg(not(feature = "alloc"))]
+        let mut ret = HeaderEncoding::new(buf);
+        if let Some(realm) = options.realm {
+            ret.realm(realm);
+        }
+        ret
+    }
+
+    doc_auto_cfg! {
+        /// Creates a `HeaderEncoding` that writes the header value into `buf` with
+        /// `oauth_signature` placed at `position`.
+        #[cfg(feature = "alloc")]
+        pub fn with_signature_position(buf: W, position: SignaturePosition) -> Self {
+            let mut ret = HeaderEncoding::new(buf);
+            ret.position = position;
+            ret
+        }
+    }
+}
+
+impl<W: Write> Encoding for HeaderEncoding<W> {
+    type Output = W;
+
+    fn oauth_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result {
+        self.result?;
+        #[cfg(feature = "alloc")]
+        {
+            let after_signature = match self.position {
+                SignaturePosition::Last => false,
+                SignaturePosition::First => true,
+                SignaturePosition::Sorted => key > param::SIGNATURE,
+            };
+            // The parameters come in dictionary order, so every parameter following one written
+            // to the tail goes to the tail as well.
+            if after_signature || !self.tail.is_empty() {
+                return write!(self.tail, r#"{}="{}","#, key, value);
+            }
+        }
+        write!(self.buf, r#"{}="{}","#, key, value)
+    }
+
+    fn default_parameter<V: Display>(&mut self, _key: &str, _value: V) -> fmt::Result {
+        Ok(())
+    }
+
+    fn end<V: Signature>(mut self, signature: V) -> Result<W, fmt::Error> {
+        self.result?;
+        self.buf.write_str(r#"oauth_signature=""#)?;
+        signature.write_to(&mut self.buf)?;
+        self.buf.write_str(r#"""#)?;
+        #[cfg(feature = "alloc")]
+        {
+            if let Some(tail) = self.tail.strip_suffix(',') {
+                write!(self.buf, ",{}", tail)?;
+            }
+        }
+        Ok(self.buf)
+    }
+}
+
+impl<W: Write> QueryEncoding<W> {
+    /// Creates a `QueryEncoding` that appends a query part to `uri`.
+    ///
+    /// `uri` must not contain a query part.
+    pub fn new(uri: W) -> Self {
+        QueryEncoding {
+            encoder: Urlencoder::query(uri),
+        }
+    }
+}
+
+impl<W: Write> FormEncoding<W> {
+    /// Creates a `FormEncoding` that writes the form string into `buf`.
+    pub fn new(buf: W) -> Self {
+        FormEncoding {
+            encoder: Urlencoder::form_with_buf(buf),
+        }
+    }
+}
+
+macro_rules! impl_urlencoded {
+    ($($E:ident),*) => {$(
+        impl<W: Write> Encoding for $E<W> {
+            type Output = W;
+
+            fn oauth_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result {
+                self.encoder.write_parameter_encoded(key, value)
+            }
+
+            fn default_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result {
+                self.encoder.write_parameter(key, value)
+            }
+
+            fn end<V: Signature>(mut self, signature: V) -> Result<W, fmt::Error> {
+                self.encoder.write_parameter_encoded(param::SIGNATURE, signature)?;
+                Ok(self.encoder.into_inner())
+            }
+        }
+    )*};
+}
+
+impl_urlencoded!(QueryEncoding, FormEncoding);
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_position() {
+        let encode = |position| {
+            let mut encoding = HeaderEncoding::with_signature_position(String::new(), position);
+            encoding
+                .oauth_parameter("oauth_consumer_key", "ck")
+                .unwrap();
+            encoding.oauth_parameter("oauth_nonce", "n").unwrap();
+            encoding
+                .oauth_parameter("oauth_signature_method", "HMAC-SHA1")
+                .unwrap();
+            encoding.oauth_parameter("oauth_token", "tk").unwrap();
+            encoding.end("sig").unwrap()
+        };
+
+        assert_eq!(
+            encode(SignaturePosition::Last),
+            "OAuth oauth_consumer_key=\"ck\",oauth_nonce=\"n\",\
+             oauth_signature_method=\"HMAC-SHA1\",oauth_token=\"tk\",oauth_signature=\"sig\"",
+        );
+        assert_eq!(
+            encode(SignaturePosition::First),
+            "OAuth oauth_signature=\"sig\",oauth_consumer_key=\"ck\",oauth_nonce=\"n\",\
+             oauth_signature_method=\"HMAC-SHA1\",oauth_token=\"tk\"",
+        );
+        assert_eq!(
+            encode(SignaturePosition::Sorted),
+            "OAuth oauth_consumer_key=\"ck\",oauth_nonce=\"n\",oauth_signature=\"sig\",\
+             oauth_signature_method=\"HMAC-SHA1\",oauth_token=\"tk\"",
+        );
+
+        let mut encoding =
+            HeaderEncoding::with_signature_position(String::new(), Default::default());
+        encoding
+            .oauth_parameter("oauth_consumer_key", "ck")
+            .unwrap();
+        assert_eq!(
+            encoding.end("sig").unwrap(),
+            "OAuth oauth_consumer_key=\"ck\",oauth_signature=\"sig\"",
+        );
+    }
+
+    #[test]
+    fn realm() {
+        let mut encoding = HeaderEncoding::new(String::new());
+        encoding.realm(r#"Say "\hi""#);
+        encoding
+            .oauth_parameter("oauth_consumer_key", "ck")
+            .unwrap();
+        assert_eq!(
+            encoding.end("sig").unwrap(),
+            r#"OAuth realm="Say \"\\hi\"",oauth_consumer_key="ck",oauth_signature="sig""#,
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use serde1::ser::{Serialize, SerializeStruct, Serializer};
+
+use super::Options;
+#[cfg(feature = "alloc")]
+use super::SignaturePosition;
+
+/// Serializes the options as a struct with the same field names as the setters of `Options`,
+/// e.g. for snapshot tests.
+///
+/// `raw_signing_key` is omitted since it is a secret.
+impl<'a> Serialize for Options<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len =
+            18 + 2 * usize::from(cfg!(feature = "alloc")) + usize::from(cfg!(feature = "tracing"));
+        let mut s = serializer.serialize_struct("Options", len)?;
+        s.serialize_field("body_hash", &self.body_hash)?;
+        s.serialize_field("callback", &self.callback)?;
+        s.serialize_field("verifier", &self.verifier)?;
+        s.serialize_field("nonce", &self.nonce)?;
+        s.serialize_field("timestamp", &self.timestamp.map(|t| t.get()))?;
+        s.serialize_field("version", &self.version)?;
+        s.serialize_field("realm", &self.realm)?;
+        s.skip_field("raw_signing_key")?;
+        s.serialize_field("default_parameters", &self.default_parameters)?;
+        s.serialize_field("ignored_parameters", &self.ignored_parameters)?;
+        s.serialize_field("host", &self.host)?;
+        s.serialize_field("normalize_uri", &self.normalize_uri)?;
+        s.serialize_field("include_default_port", &self.include_default_port)?;
+        #[cfg(feature = "alloc")]
+        s.serialize_field("remove_dot_segments", &self.remove_dot_segments)?;
+        s.serialize_field("trim_trailing_slash", &self.trim_trailing_slash)?;
+        s.serialize_field("lowercase_path", &self.lowercase_path)?;
+        s.serialize_field("require_https", &self.require_https)?;
+        s.serialize_field("http_allowed_hosts", &self.http_allowed_hosts)?;
+        s.serialize_field(
+            "reject_duplicate_parameters",
+            &self.reject_duplicate_parameters,
+        )?;
+        s.serialize_field("fips", &self.fips)?;
+        #[cfg(feature = "alloc")]
+        s.serialize_field("signature_position", &self.signature_position)?;
+        #[cfg(feature = "tracing")]
+        s.serialize_field("audit_log", &self.audit_log)?;
+        s.end()
+    }
+}
+
+/// Serializes the position as a unit variant, e.g. `"Last"` in JSON.
+#[cfg(feature = "alloc")]
+impl Serialize for SignaturePosition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (index, variant) = match *self {
+            SignaturePosition::Last => (0, "Last"),
+            SignaturePosition::First => (1, "First"),
+            SignaturePosition::Sorted => (2, "Sorted"),
+        };
+        serializer.serialize_unit_variant("SignaturePosition", index, variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let mut options = Options::new();
+        options
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(42))
+            .raw_signing_key("secret")
+            .default_parameters(&[("format", "json")]);
+
+        let json = serde_json::to_value(&options).unwrap();
+        assert_eq!(json["nonce"], "nonce");
+        assert_eq!(json["timestamp"], 42);
+        assert_eq!(json["callback"], serde_json::Value::Null);
+        assert_eq!(
+            json["default_parameters"],
+            serde_json::json!([["format", "json"]])
+        );
+        assert!(json.get("raw_signing_key").is_none());
+        #[cfg(feature = "alloc")]
+        assert_eq!(json["signature_position"], "Last");
+    }
+}
@@ -0,0 +1,226 @@
+//! A serializer that collects the parameters of a signed request into key-value pairs.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use oauth_credentials::Credentials;
+
+use crate::param;
+use crate::signature_method::{Signature, SignatureMethod};
+use crate::util::PercentEncode;
+
+use super::auth::{Authorizer, Encoding, Options};
+use super::Serializer;
+
+/// A key-value pair produced by a [`Collector`].
+pub type Pair = (Cow<'static, str>, Cow<'static, str>);
+
+/// A `Serializer` that signs a request and produces its parameters, including the `oauth_*` ones,
+/// as a `Vec` of key-value pairs.
+///
+/// This lets you hand the parameters to an HTTP client or a templating layer of your choice
+/// instead of an `Authorization` header string.
+///
+/// The pairs are in dictionary order of the keys, and the keys and values are percent-encoded as
+/// in the request ([RFC 5849 section 3.6.][rfc]), like in a URI query produced by
+/// [`Builder::to_query`](crate::Builder::to_query). If your HTTP client percent-encodes the
+/// parameters by itself, decode them first to avoid encoding them twice.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.6
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::num::NonZeroU64;
+///
+/// use oauth::serializer::auth::Options;
+/// use oauth::serializer::collector::Collector;
+/// use oauth::Request;
+///
+/// let request = oauth::ParameterList::new([("q", "a b")]);
+///
+/// let client = oauth::Credentials::new("consumer", "secret");
+/// let mut options = Options::new();
+/// options.nonce("nonce").timestamp(NonZeroU64::new(1234567890));
+/// let collector = Collector::new(
+///     "GET",
+///     "https://example.com/search",
+///     client,
+///     None,
+///     &options,
+///     oauth::HMAC_SHA1,
+/// );
+///
+/// let pairs = request.serialize(collector);
+/// let keys: Vec<_> = pairs.iter().map(|(k, _)| &**k).collect();
+/// assert_eq!(
+///     keys,
+///     [
+///         "oauth_consumer_key",
+///         "oauth_nonce",
+///         "oauth_signature",
+///         "oauth_signature_method",
+///         "oauth_timestamp",
+///         "q",
+///     ],
+/// );
+/// assert_eq!(pairs[5].1, "a%20b");
+/// ```
+pub struct Collector<'a, SM: SignatureMethod> {
+    authorizer: Authorizer<'a, SM, PairEncoding>,
+    parameters: Vec<Pair>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct PairEncoding {
+    pairs: Vec<Pair>,
+}
+
+impl<'a, SM: SignatureMethod> Collector<'a, SM> {
+    /// Creates a `Collector` that signs a request to `uri`.
+    ///
+    /// `uri` must not contain a query part.
+    /// Otherwise, the serializer will produce a wrong signature.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `uri` contains a `'?'` character.
+    pub fn new<T: Display>(
+        method: &str,
+        uri: T,
+        client: Credentials<&'a str>,
+        token: Option<Credentials<&'a str>>,
+        options: &'a Options<'a>,
+        signature_method: SM,
+    ) -> Self {
+        Collector {
+            authorizer: Authorizer::with_encoding(
+                PairEncoding::default(),
+                method,
+                uri,
+                client,
+                token,
+                options,
+                signature_method,
+            ),
+            parameters: Vec::new(),
+        }
+    }
+}
+
+impl<'a, SM> Clone for Collector<'a, SM>
+where
+    SM: SignatureMethod + Clone,
+    SM::Sign: Clone,
+{
+    fn clone(&self) -> Self {
+        Collector {
+            authorizer: self.authorizer.clone(),
+            parameters: self.parameters.clone(),
+        }
+    }
+}
+
+impl<'a, SM> Debug for Collector<'a, SM>
+where
+    SM: SignatureMethod + Debug,
+    SM::Sign: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collector")
+            .field("authorizer", &self.authorizer)
+            .field("parameters", &self.parameters)
+            .finish()
+    }
+}
+
+impl<'a, SM: SignatureMethod> Serializer for Collector<'a, SM> {
+    type Output = Vec<Pair>;
+
+    fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
+        let pair = (
+            key.to_string().into(),
+            PercentEncode(&value).to_string().into(),
+        );
+        self.parameters.push(pair);
+        self.authorizer.serialize_parameter(key, value);
+    }
+
+    fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
+        let pair = (key.to_string().into(), value.to_string().into());
+        self.parameters.push(pair);
+        self.authorizer.serialize_parameter_encoded(key, value);
+    }
+
+    fn serialize_oauth_callback(&mut self) {
+        self.authorizer.serialize_oauth_callback();
+    }
+
+    fn serialize_oauth_consumer_key(&mut self) {
+        self.authorizer.serialize_oauth_consumer_key();
+    }
+
+    fn serialize_oauth_nonce(&mut self) {
+        self.authorizer.serialize_oauth_nonce();
+    }
+
+    fn serialize_oauth_signature_method(&mut self) {
+        self.authorizer.serialize_oauth_signature_method();
+    }
+
+    fn serialize_oauth_timestamp(&mut self) {
+        self.authorizer.serialize_oauth_timestamp();
+    }
+
+    fn serialize_oauth_token(&mut self) {
+        self.authorizer.serialize_oauth_token();
+    }
+
+    fn serialize_oauth_verifier(&mut self) {
+        self.authorizer.serialize_oauth_verifier();
+    }
+
+    fn serialize_oauth_version(&mut self) {
+        self.authorizer.serialize_oauth_version();
+    }
+
+    fn end(self) -> Vec<Pair> {
+        let mut pairs = self.authorizer.end();
+        pairs.extend(self.parameters);
+        // The sort is stable, so parameters with the same key keep the order of serialization.
+        pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        pairs
+    }
+}
+
+impl Encoding for PairEncoding {
+    type Output = Vec<Pair>;
+
+    fn oauth_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result {
+        let mut buf = String::new();
+        write!(buf, "{}", value)?;
+        self.pairs.push((key.to_string().into(), buf.into()));
+        Ok(())
+    }
+
+    fn default_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result {
+        let mut buf = String::new();
+        write!(buf, "{}", PercentEncode(value))?;
+        self.pairs.push((key.to_string().into(), buf.into()));
+        Ok(())
+    }
+
+    fn end<V: Signature>(mut self, signature: V) -> Result<Vec<Pair>, fmt::Error> {
+        let mut value = String::new();
+        signature.write_to(&mut value)?;
+        self.pairs.push((param::SIGNATURE.into(), value.into()));
+        Ok(self.pairs)
+    }
+}
@@ -0,0 +1,98 @@
+//! A wrapper of a `Serializer` that calls the `serialize_oauth_*` methods at the right time.
+
+use core::fmt::Display;
+
+use crate::util::OAuthParameter;
+
+use super::Serializer;
+
+/// A wrapper of a [`Serializer`] that calls the `serialize_oauth_*` methods in the order
+/// required by the `Serializer` trait.
+///
+/// `SerializerDriver` keeps track of which `oauth_*` parameters have been serialized and
+/// serializes the ones preceding each parameter in dictionary order before the parameter itself.
+/// This is useful for implementing [`Request`](crate::Request) by hand, since the implementation
+/// doesn't need to know the set of `oauth_*` parameters and keeps working when a new one is
+/// introduced.
+///
+/// The parameters must still be serialized in ascending dictionary order.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::{Serializer, SerializerDriver};
+///
+/// struct Search<'a> {
+///     q: &'a str,
+///     page: u32,
+/// }
+///
+/// impl<'a> oauth::Request for Search<'a> {
+///     fn serialize<S: Serializer>(&self, serializer: S) -> S::Output {
+///         let mut driver = SerializerDriver::new(serializer);
+///         driver.serialize_parameter("page", self.page);
+///         driver.serialize_parameter("q", self.q);
+///         driver.end()
+///     }
+/// }
+///
+/// let request = Search { q: "rust", page: 2 };
+/// assert_eq!(oauth::to_form(&request), "page=2&q=rust");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SerializerDriver<S> {
+    serializer: S,
+    next_param: OAuthParameter,
+}
+
+impl<S: Serializer> SerializerDriver<S> {
+    /// Creates a new `SerializerDriver` that drives `serializer`.
+    pub fn new(serializer: S) -> Self {
+        SerializerDriver {
+            serializer,
+            next_param: OAuthParameter::default(),
+        }
+    }
+
+    /// Serializes the `oauth_*` parameters preceding `key`, and then the key-value pair.
+    ///
+    /// See [`Serializer::serialize_parameter`].
+    pub fn serialize_parameter<V>(&mut self, key: &str, value: V)
+    where
+        V: Display,
+    {
+        self.serialize_oauth_parameters_before(key);
+        self.serializer.serialize_parameter(key, value);
+    }
+
+    /// Serializes the `oauth_*` parameters preceding `key`, and then the key-value pair, treating
+    /// the value as already percent encoded.
+    ///
+    /// See [`Serializer::serialize_parameter_encoded`].
+    pub fn serialize_parameter_encoded<V>(&mut self, key: &str, value: V)
+    where
+        V: Display,
+    {
+        self.serialize_oauth_parameters_before(key);
+        self.serializer.serialize_parameter_encoded(key, value);
+    }
+
+    /// Serializes the remaining `oauth_*` parameters and finalizes the serialization.
+    pub fn end(mut self) -> S::Output {
+        while self.next_param != OAuthParameter::None {
+            self.next_param.serialize(&mut self.serializer);
+            self.next_param = self.next_param.next();
+        }
+        self.serializer.end()
+    }
+
+    fn serialize_oauth_parameters_before(&mut self, key: &str) {
+        while self.next_param < *key {
+            self.next_param.serialize(&mut self.serializer);
+            self.next_param = self.next_param.next();
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! A serializer that forwards the parameters to two serializers.
+
+use core::fmt::Display;
+
+use super::Serializer;
+
+/// A `Serializer` that forwards every method call to two serializers and produces a pair of their
+/// outputs.
+///
+/// This lets you serialize a `Request` into two outputs in a single pass, e.g. to sign the
+/// request and build its `x-www-form-urlencoded` body at the same time.
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::auth::{self, Authorizer};
+/// use oauth::serializer::{Tee, Urlencoder};
+/// use oauth::Request;
+///
+/// let request = oauth::ParameterList::new([("status", "Hello, world!")]);
+///
+/// let uri = "https://example.com/statuses/update.json";
+/// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+/// let options = auth::Options::new();
+/// let authorizer =
+///     Authorizer::authorization("POST", uri, client, None, &options, oauth::HMAC_SHA1);
+///
+/// let (authorization, form) = request.serialize(Tee::new(authorizer, Urlencoder::form()));
+/// assert!(authorization.starts_with("OAuth "));
+/// assert_eq!(form, "status=Hello%2C%20world%21");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: Serializer, B: Serializer> Tee<A, B> {
+    /// Creates a `Tee` that forwards the method calls to `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        Tee { first, second }
+    }
+
+    /// Returns a reference to the first serializer.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns a reference to the second serializer.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Returns the pair of the underlying serializers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+macro_rules! forward {
+    ($($method:ident,)*) => {$(
+        fn $method(&mut self) {
+            self.first.$method();
+            self.second.$method();
+        }
+    )*};
+}
+
+impl<A: Serializer, B: Serializer> Serializer for Tee<A, B> {
+    type Output = (A::Output, B::Output);
+
+    fn serialize_parameter<V>(&mut self, key: &str, value: V)
+    where
+        V: Display,
+    {
+        self.first.serialize_parameter(key, &value);
+        self.second.serialize_parameter(key, value);
+    }
+
+    fn serialize_parameter_encoded<V>(&mut self, key: &str, value: V)
+    where
+        V: Display,
+    {
+        self.first.serialize_parameter_encoded(key, &value);
+        self.second.serialize_parameter_encoded(key, value);
+    }
+
+    forward! {
+        serialize_oauth_callback,
+        serialize_oauth_consumer_key,
+        serialize_oauth_nonce,
+        serialize_oauth_signature_method,
+        serialize_oauth_timestamp,
+        serialize_oauth_token,
+        serialize_oauth_verifier,
+        serialize_oauth_version,
+    }
+
+    fn end(self) -> Self::Output {
+        (self.first.end(), self.second.end())
+    }
+}
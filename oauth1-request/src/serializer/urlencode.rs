@@ -1,6 +1,6 @@
 //! A URI query/`x-www-form-urlencoded` string serializer.
 
-use core::fmt::Write;
+use core::fmt::{self, Display, Write};
 
 use crate::util::PercentEncode;
 
@@ -59,15 +59,38 @@ where
         }
     }
 
-    fn append_delim(&mut self) {
+    /// Same as `serialize_parameter`, but returns an error from the `Display` implementation or
+    /// the underlying writer instead of panicking.
+    pub(crate) fn write_parameter<V: Display>(&mut self, key: &str, value: V) -> fmt::Result {
+        self.append_delim()?;
+        write!(self.data, "{}={}", key, PercentEncode(&value))
+    }
+
+    /// Same as `serialize_parameter_encoded`, but returns an error from the `Display`
+    /// implementation or the underlying writer instead of panicking.
+    pub(crate) fn write_parameter_encoded<V: Display>(
+        &mut self,
+        key: &str,
+        value: V,
+    ) -> fmt::Result {
+        self.append_delim()?;
+        write!(self.data, "{}={}", key, value)
+    }
+
+    pub(crate) fn into_inner(self) -> W {
+        self.data
+    }
+
+    fn append_delim(&mut self) -> fmt::Result {
         match self.next_append {
             Append::None => self.next_append = Append::Ampersand,
             Append::Question => {
-                self.data.write_char('?').unwrap();
+                self.data.write_char('?')?;
                 self.next_append = Append::Ampersand;
             }
-            Append::Ampersand => self.data.write_char('&').unwrap(),
+            Append::Ampersand => self.data.write_char('&')?,
         }
+        Ok(())
     }
 }
 
@@ -76,18 +99,16 @@ impl<W: Write> Serializer for Urlencoder<W> {
 
     fn serialize_parameter<V>(&mut self, key: &str, value: V)
     where
-        V: core::fmt::Display,
+        V: Display,
     {
-        self.append_delim();
-        write!(self.data, "{}={}", key, PercentEncode(&value)).unwrap();
+        self.write_parameter(key, value).unwrap();
     }
 
     fn serialize_parameter_encoded<V>(&mut self, key: &str, value: V)
     where
-        V: core::fmt::Display,
+        V: Display,
     {
-        self.append_delim();
-        write!(self.data, "{}={}", key, value).unwrap();
+        self.write_parameter_encoded(key, value).unwrap();
     }
 
     super::skip_serialize_oauth_parameters!();
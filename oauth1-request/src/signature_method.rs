@@ -5,35 +5,347 @@
 //! The OAuth standard allows for servers to implement their own custom signature methods.
 //! So the module provides an abstraction over signature methods so that users can implement those
 //! custom methods by themselves.
+//!
+//! For environments whose crypto policy forbids the SHA-1-based signature methods and `PLAINTEXT`,
+//! [`Options::fips`](crate::serializer::auth::Options::fips) makes the fallible signing methods
+//! reject them at runtime.
+
+pub mod any_method;
+pub mod tee;
 
 doc_auto_cfg! {
+    #[cfg(feature = "async")]
+    pub mod async_sign;
+    #[cfg(feature = "alloc")]
+    pub mod base_string;
+    #[cfg(feature = "alloc")]
+    pub mod boxed;
+    #[cfg(feature = "test-util")]
+    pub mod conformance;
+    #[cfg(feature = "ecdsa-p256-013")]
+    pub mod ecdsa_p256_013;
+    #[cfg(feature = "alloc")]
+    pub mod external;
+    #[cfg(feature = "hmac")]
+    pub mod hmac;
     #[cfg(feature = "hmac-sha1")]
     pub mod hmac_sha1;
+    /// The `HMAC-SHA1` signature method ([RFC 5849 section 3.4.2.][rfc]) backed by the
+    /// [`ring`](super::ring) module.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.2
+    ///
+    /// This module is only available when `hmac-sha1-ring` feature is activated and `hmac-sha1`
+    /// feature is not, in which case the RustCrypto-based implementation is used instead.
+    #[cfg(all(feature = "hmac-sha1-ring", not(feature = "hmac-sha1")))]
+    pub mod hmac_sha1 {
+        pub use super::ring::{HmacSha1, HmacSha1Key, HmacSha1Sign, HmacSha1Signature, HMAC_SHA1};
+    }
+    #[cfg(feature = "alloc")]
+    pub mod identity;
+    #[cfg(feature = "alloc")]
+    pub mod map_params;
+    #[cfg(feature = "openssl")]
+    pub mod openssl;
     pub mod plaintext;
+    #[cfg(feature = "ring")]
+    pub mod ring;
     #[cfg(feature = "rsa-sha1-06")]
     pub mod rsa_sha1_06;
+    #[cfg(feature = "rsa-sha256-06")]
+    pub mod rsa_sha256_06;
+    #[cfg(feature = "test")]
+    pub mod test;
 }
 
-#[cfg(any(feature = "hmac-sha1", feature = "rsa-sha1-06"))]
+#[cfg(any(feature = "hmac-sha1", feature = "openssl", feature = "ring"))]
+mod backend;
+#[cfg(any(
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "rsa-sha256-06",
+    feature = "rsa-sha1-06",
+))]
 mod digest_common;
+mod dyn_sign;
 #[cfg(feature = "either")]
 mod either;
+#[cfg(any(feature = "rsa-sha256-06", feature = "rsa-sha1-06"))]
+mod rsa06_common;
+#[cfg(any(feature = "openssl", feature = "ring"))]
+mod sha1_key;
+#[cfg(feature = "alloc")]
+mod signing_key;
+
+pub use self::any_method::AnyMethod;
+pub use self::tee::Tee;
 
 doc_auto_cfg! {
-    #[cfg(feature = "hmac-sha1")]
+    #[cfg(feature = "async")]
+    pub use self::async_sign::{AsyncError, AsyncSign, AsyncSignatureMethod};
+    #[cfg(any(feature = "hmac-sha1", feature = "openssl", feature = "ring"))]
+    pub use self::backend::SignatureBackend;
+    #[cfg(feature = "alloc")]
+    pub use self::base_string::{BaseStringSign, SignBaseString};
+    #[cfg(feature = "alloc")]
+    pub use self::boxed::BoxSignatureMethod;
+    #[cfg(feature = "ecdsa-p256-013")]
+    pub use self::ecdsa_p256_013::EcdsaP256Sha256;
+    #[cfg(feature = "hmac")]
+    pub use self::hmac::Hmac;
+    #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
     pub use self::hmac_sha1::HmacSha1;
-    #[cfg(feature = "hmac-sha1")]
+    #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
     pub use self::hmac_sha1::HMAC_SHA1;
+    #[cfg(feature = "alloc")]
+    pub use self::identity::Identity;
+    #[cfg(feature = "alloc")]
+    pub use self::map_params::MapParams;
     pub use self::plaintext::Plaintext;
     #[cfg(feature = "alloc")]
     pub use self::plaintext::PLAINTEXT;
     #[cfg(feature = "rsa-sha1-06")]
     pub use self::rsa_sha1_06::RsaSha1;
+    #[cfg(feature = "rsa-sha256-06")]
+    pub use self::rsa_sha256_06::RsaSha256;
+    #[cfg(feature = "alloc")]
+    pub use self::signing_key::{signing_key_for, SigningKey};
 }
 
 use core::fmt::{self, Display, Write};
 
-use crate::util::percent_encode;
+use crate::param::OAuthParamKind;
+use crate::util::{percent_encode, DoublePercentEncodeBytes};
+
+/// Returns the names of the signature methods provided by this crate with the enabled features,
+/// in dictionary order.
+///
+/// The names are the values of the `oauth_signature_method` parameter, e.g. `"HMAC-SHA1"`. Note
+/// that the list only reflects the signature methods of this crate and is not affected by custom
+/// `SignatureMethod` implementations.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// assert!(oauth::available_methods().contains(&"HMAC-SHA1"));
+/// ```
+pub fn available_methods() -> &'static [&'static str] {
+    const METHODS: &[&str] = &[
+        #[cfg(feature = "ecdsa-p256-013")]
+        "ECDSA-SHA256",
+        #[cfg(any(feature = "hmac-sha1", feature = "openssl", feature = "ring"))]
+        "HMAC-SHA1",
+        "PLAINTEXT",
+        #[cfg(any(feature = "openssl", feature = "rsa-sha1-06"))]
+        "RSA-SHA1",
+        #[cfg(any(feature = "openssl", feature = "rsa-sha256-06"))]
+        "RSA-SHA256",
+    ];
+    METHODS
+}
+
+/// Returns the signature method of this crate named `name`, if any.
+///
+/// `name` is the value of the `oauth_signature_method` parameter, e.g. `"HMAC-SHA1"`, and is
+/// matched case-sensitively. This only covers the signature methods that don't need a key other
+/// than the client and token secrets, i.e. `HMAC-SHA1` and `PLAINTEXT`, and returns `None` for the
+/// others (e.g. `RSA-SHA1`) as well as for the methods disabled by the features.
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{from_name, AnyMethod};
+///
+/// assert!(matches!(from_name("HMAC-SHA1"), Some(AnyMethod::HmacSha1(_))));
+/// assert!(from_name("hmac-sha1").is_none());
+/// assert!(from_name("RSA-SHA1").is_none());
+/// ```
+pub fn from_name(name: &str) -> Option<AnyMethod> {
+    match name {
+        #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
+        "HMAC-SHA1" => Some(HMAC_SHA1.into()),
+        #[cfg(feature = "alloc")]
+        "PLAINTEXT" => Some(PLAINTEXT.into()),
+        _ => None,
+    }
+}
+
+/// The names of the signature methods in the order of preference used by [`negotiate`] and
+/// [`negotiate_with`], strongest first.
+pub const PREFERENCE_ORDER: &[&str] = &[
+    "ECDSA-SHA256",
+    "RSA-SHA256",
+    "RSA-SHA1",
+    "HMAC-SHA1",
+    "PLAINTEXT",
+];
+
+/// Selects the strongest signature method among the ones advertised by the server.
+///
+/// `advertised` is the list of the signature method names accepted by the server, e.g. from
+/// a discovery document or the `oauth_acceptable_signature_methods` parameter of an error
+/// response. The strongest one according to [`PREFERENCE_ORDER`] that [`from_name`] can construct
+/// is returned, so this only selects `HMAC-SHA1` or `PLAINTEXT`. Use [`negotiate_with`] to take
+/// signature methods that need a key (e.g. `RSA-SHA1`) into account.
+///
+/// Returns `None` if none of the advertised signature methods are available.
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::negotiate;
+///
+/// let method = negotiate(vec!["PLAINTEXT", "HMAC-SHA1", "RSA-SHA1"]).unwrap();
+/// assert_eq!(method.name(), "HMAC-SHA1");
+/// assert!(negotiate(vec!["RSA-SHA1"]).is_none());
+/// ```
+pub fn negotiate<'a, I>(advertised: I) -> Option<AnyMethod>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    strongest(advertised, |name| from_name(name).is_some()).and_then(from_name)
+}
+
+/// Selects the strongest signature method among `candidates` that is advertised by the server.
+///
+/// This is the same as [`negotiate`], except that the signature methods are chosen from
+/// `candidates`, e.g. an `RSA-SHA1` method with your private key and `HMAC-SHA1`.
+///
+/// Returns `None` if none of the advertised signature methods are in `candidates`.
+pub fn negotiate_with<'a, I>(advertised: I, candidates: &[AnyMethod]) -> Option<AnyMethod>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let name = strongest(advertised, |name| {
+        candidates.iter().any(|method| method.name() == name)
+    })?;
+    candidates
+        .iter()
+        .find(|method| method.name() == name)
+        .cloned()
+}
+
+fn strongest<'a, I, F>(advertised: I, mut available: F) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: FnMut(&str) -> bool,
+{
+    advertised
+        .into_iter()
+        .filter(|name| available(name))
+        .filter_map(|name| Some((PREFERENCE_ORDER.iter().position(|&n| n == name)?, name)))
+        .min_by_key(|&(rank, _)| rank)
+        .map(|(_, name)| name)
+}
+
+/// Checks whether `signature` formats to `encoded` in constant time.
+///
+/// `encoded` is the value of the `oauth_signature` parameter percent-encoded as in the request,
+/// i.e. in the form the signatures of this crate are formatted with `Display`. Use this instead of
+/// comparing the `Display` output with `==` when verifying a signature, which leaks the position
+/// of the first mismatch through the timing. The comparison doesn't allocate.
+///
+/// This returns `false` if formatting `signature` fails.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{verify_signature, Sign, SignatureMethod};
+///
+/// let signature = oauth::PLAINTEXT.sign_with("secret", Some("token secret")).end();
+/// assert!(verify_signature(&signature, "secret%26token%2520secret"));
+/// assert!(!verify_signature(&signature, "secret%26"));
+/// ```
+pub fn verify_signature<S: Display>(signature: S, encoded: &str) -> bool {
+    struct ConstantTimeEq<'a> {
+        expected: &'a [u8],
+        pos: usize,
+        diff: u8,
+    }
+
+    impl<'a> Write for ConstantTimeEq<'a> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for b in s.bytes() {
+                // Keep on consuming the input on a length mismatch so that the time doesn't
+                // depend on the content of `expected`.
+                let e = self.expected.get(self.pos).copied().unwrap_or(!b);
+                self.diff |= e ^ b;
+                self.pos = self.pos.saturating_add(1);
+            }
+            Ok(())
+        }
+    }
+
+    let mut w = ConstantTimeEq {
+        expected: encoded.as_bytes(),
+        pos: 0,
+        diff: 0,
+    };
+    write!(w, "{}", signature).is_ok() && w.pos == encoded.len() && w.diff == 0
+}
+
+/// The strength of the digest algorithm underlying a signature method, as reported by
+/// [`SignatureMethod::digest_strength`].
+///
+/// The variants are ordered from the weakest to the strongest, so that a verification policy can
+/// be expressed as a comparison.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{DigestStrength, SignatureMethod};
+///
+/// fn is_acceptable<SM: SignatureMethod>(signature_method: &SM) -> bool {
+///     signature_method.digest_strength() >= DigestStrength::Sha256
+/// }
+///
+/// assert!(!is_acceptable(&oauth::HMAC_SHA1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum DigestStrength {
+    /// The signature method does not report its strength. This is less than any other variant.
+    Unknown,
+    /// The signature method does not use a digest algorithm, like `PLAINTEXT`.
+    None,
+    /// SHA-1 or a digest algorithm with the same output length (160 bits).
+    Sha1,
+    /// SHA-256 or a digest algorithm with the same output length (256 bits).
+    Sha256,
+    /// SHA-384 or a digest algorithm with the same output length (384 bits).
+    Sha384,
+    /// SHA-512 or a digest algorithm with the same output length (512 bits).
+    Sha512,
+}
+
+impl DigestStrength {
+    /// Returns the variant corresponding to a digest output of `len` bytes, or `Unknown` if
+    /// there is none.
+    pub fn from_output_len(len: usize) -> Self {
+        match len {
+            20 => DigestStrength::Sha1,
+            32 => DigestStrength::Sha256,
+            48 => DigestStrength::Sha384,
+            64 => DigestStrength::Sha512,
+            _ => DigestStrength::Unknown,
+        }
+    }
+}
 
 /// Types that represent a signature method.
 ///
@@ -44,25 +356,94 @@ pub trait SignatureMethod {
 
     /// Creates a `Self::Sign` that signs a signature base string with the given shared-secrets.
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign;
+
+    /// Creates a `Self::Sign` that signs a signature base string with `signing_key` as-is.
+    ///
+    /// Normally, the signing key is constructed by percent-encoding the client and token secrets
+    /// and joining them with `&` ([RFC 5849 section 3.4.2.][rfc]). This method is an escape hatch
+    /// for providers that deviate from the specification, e.g. by expecting secrets containing `&`
+    /// to be used without encoding.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.2
+    ///
+    /// Signature methods that don't use the shared-secrets (like `RSA-SHA1`) may ignore the key.
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign;
+
+    /// Returns whether the signature method signs with the shared-secrets (like `HMAC-SHA1` and
+    /// `PLAINTEXT`) rather than with a private key (like `RSA-SHA1`).
+    ///
+    /// The default implementation returns `true`, so that a signature method that doesn't report
+    /// it is rejected by a policy requiring asymmetric signature methods.
+    fn is_symmetric(&self) -> bool {
+        true
+    }
+
+    /// Returns the strength of the digest algorithm used by the signature method.
+    ///
+    /// The default implementation returns `DigestStrength::Unknown`.
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Unknown
+    }
+}
+
+/// Signature methods that can be configured with an option of type `O`.
+///
+/// This lets the options of a signature method be set without knowing its concrete type. In
+/// particular, `Either<L, R>` (with `either` feature) implements `Configure<O>` when both `L` and
+/// `R` do, passing the option to whichever variant is selected, so a signature method chosen at
+/// runtime remains configurable. Adapters like `MapParams` pass the option to the underlying
+/// signature method.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "either", doc = " ```")]
+#[cfg_attr(not(feature = "either"), doc = " ```ignore")]
+/// # extern crate either;
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use either::Either;
+/// use oauth::signature_method::Configure;
+///
+/// /// An option to use the URL-safe Base64 alphabet.
+/// struct UrlSafe(bool);
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct Method {
+///     url_safe: bool,
+/// }
+///
+/// impl Configure<UrlSafe> for Method {
+///     fn configure(&mut self, option: UrlSafe) {
+///         self.url_safe = option.0;
+///     }
+/// }
+///
+/// let mut method: Either<Method, Method> = Either::Right(Method::default());
+/// method.configure(UrlSafe(true));
+/// assert_eq!(method, Either::Right(Method { url_safe: true }));
+/// ```
+pub trait Configure<O> {
+    /// Applies `option` to `self`.
+    fn configure(&mut self, option: O);
 }
 
 macro_rules! provide {
-    ($(#[doc = $doc:expr])+ $name:ident, $($rest:tt)*) => {
+    ($(#[doc = $doc:expr])+ $name:ident($kind:ident), $($rest:tt)*) => {
         $(#[doc = $doc])+
         fn $name<V: Display>(&mut self, value: V) {
-            self.parameter(concat!("oauth_", stringify!($name)), value);
+            self.oauth_parameter(OAuthParamKind::$kind, value);
         }
         provide! { $($rest)* }
     };
-    ($name:ident, $($rest:tt)*) => {
+    ($name:ident($kind:ident), $($rest:tt)*) => {
         provide! {
             #[doc = concat!(
 "Feeds `self` with the `oauth_", stringify!($name), "` parameter part of the signature base string.
 
-The default implementation forwards to the `parameter` method with `\"oauth_",
-stringify!($name), "\"` as the first argument."
+The default implementation forwards to the `oauth_parameter` method with `OAuthParamKind::",
+stringify!($kind), "` as the first argument."
             )]
-            $name, $($rest)*
+            $name($kind), $($rest)*
         }
     };
     () => {};
@@ -122,7 +503,7 @@ stringify!($name), "\"` as the first argument."
 /// ```
 pub trait Sign {
     /// The URL-encoded representation of `oauth_signature` string the algorithm produces.
-    type Signature: Display;
+    type Signature: Signature;
 
     /// Returns the `oauth_signature_method` string for the signature method associated with the
     /// algorithm.
@@ -132,6 +513,10 @@ pub trait Sign {
     fn request_method(&mut self, method: &str);
 
     /// Feeds `self` with the base string URI part of the signature base string.
+    ///
+    /// If formatting `uri` fails, implementations should not panic but make the `Display`
+    /// implementation of the resulting `Self::Signature` return the error instead. The same
+    /// applies to the `value` argument of the `parameter` method.
     fn uri<T: Display>(&mut self, uri: T);
 
     /// Feeds `self` with a key-value parameter pair of the signature base string.
@@ -140,13 +525,81 @@ pub trait Sign {
     /// by `format!("{}%3D{}", key, value)`.
     fn parameter<V: Display>(&mut self, key: &str, value: V);
 
+    /// Feeds `self` with a key-value parameter pair of the signature base string, where `value`
+    /// is the raw bytes of the parameter value rather than its percent-encoded form.
+    ///
+    /// `key` is in the same form as that of the `parameter` method. This is equivalent to calling
+    /// `parameter` with `value` percent-encoded twice, which is what the default implementation
+    /// does. Signature methods that hash the signature base string as bytes override this to
+    /// encode `value` into the hash directly instead of going through a `Formatter`, so that a
+    /// binary value doesn't need to be converted to a string.
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.parameter(key, DoublePercentEncodeBytes(value));
+    }
+
+    /// Feeds `self` with a key-value parameter pair of the signature base string, where `key` is
+    /// a `Display` value rather than a `str`.
+    ///
+    /// This is used for a parameter whose key is percent-encoded in the request, which has to be
+    /// percent-encoded once more in the signature base string. Signature methods that feed the
+    /// signature base string to a hash function override this to write `key` into it directly.
+    /// The default implementation formats `key` into a buffer and forwards to the `parameter`
+    /// method.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if formatting `key` fails, which makes signing the request fail with
+    /// [`Error::Format`](crate::Error::Format). Without `alloc` feature, the buffer of the default
+    /// implementation holds 256 bytes, and a longer `key` is reported as an error as well.
+    fn parameter_display_key<K: Display, V: Display>(&mut self, key: K, value: V) -> fmt::Result {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "alloc")] {
+                let mut buf = alloc::string::String::new();
+                write!(buf, "{}", key)?;
+                self.parameter(&buf, value);
+            } else {
+                struct Buf {
+                    buf: [u8; 256],
+                    len: usize,
+                }
+
+                impl Write for Buf {
+                    fn write_str(&mut self, s: &str) -> fmt::Result {
+                        let dst = self.buf.get_mut(self.len..self.len + s.len()).ok_or(fmt::Error)?;
+                        dst.copy_from_slice(s.as_bytes());
+                        self.len += s.len();
+                        Ok(())
+                    }
+                }
+
+                let mut buf = Buf { buf: [0; 256], len: 0 };
+                write!(buf, "{}", key)?;
+                self.parameter(core::str::from_utf8(&buf.buf[..buf.len]).unwrap(), value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Feeds `self` with a protocol parameter part of the signature base string.
+    ///
+    /// The methods for the individual protocol parameters like `nonce` and `timestamp` forward to
+    /// this method by default. A `Sign` that treats the protocol parameters specially should
+    /// override this method rather than the individual ones, so that it keeps working when a new
+    /// [`OAuthParamKind`] is added.
+    ///
+    /// The default implementation forwards to the `parameter` method with `kind.name()` as the
+    /// first argument.
+    fn oauth_parameter<V: Display>(&mut self, kind: OAuthParamKind, value: V) {
+        self.parameter(kind.name(), value);
+    }
+
     /// Feeds `self` with the delimiter (`%26`) between parameters.
     fn delimiter(&mut self);
 
     /// Finalizes the signing process and returns the resulting signature.
     fn end(self) -> Self::Signature;
 
-    provide! { callback, consumer_key, nonce, }
+    provide! { body_hash(BodyHash), callback(Callback), consumer_key(ConsumerKey), nonce(Nonce), }
 
     /// Whether the signature method uses the `oauth_nonce` parameter.
     ///
@@ -161,19 +614,20 @@ pub trait Sign {
     /// Feeds `self` with the `oauth_signature_method` parameter part of the
     /// signature base string.
     ///
-    /// The default implementation forwards to the `parameter` method with
-    /// `"oauth_signature_method"` and `self.get_signature_method_name()` as the arguments.
+    /// The default implementation forwards to the `oauth_parameter` method with
+    /// `OAuthParamKind::SignatureMethod` and `self.get_signature_method_name()` as the arguments.
     fn signature_method(&mut self) {
-        self.parameter("oauth_signature_method", self.get_signature_method_name());
+        let name = self.get_signature_method_name();
+        self.oauth_parameter(OAuthParamKind::SignatureMethod, name);
     }
 
     /// Feeds `self` with the `oauth_timestamp` parameter part of the
     /// signature base string.
     ///
-    /// The default implementation forwards to the `parameter` method with
-    /// `"oauth_timestamp"` as the first argument.
+    /// The default implementation forwards to the `oauth_parameter` method with
+    /// `OAuthParamKind::Timestamp` as the first argument.
     fn timestamp(&mut self, value: u64) {
-        self.parameter("oauth_timestamp", value);
+        self.oauth_parameter(OAuthParamKind::Timestamp, value);
     }
 
     /// Whether the signature method uses the `oauth_nonce` parameter.
@@ -186,14 +640,165 @@ pub trait Sign {
         true
     }
 
-    provide! { token, verifier, }
+    provide! { token(Token), verifier(Verifier), }
 
     /// Feeds `self` with the `oauth_version` parameter part of the signature base string.
     ///
-    /// The default implementation forwards to the `parameter` method with
-    /// `"oauth_version"` and `"1.0"` as the arguments.
+    /// The default implementation forwards to the `oauth_parameter` method with
+    /// `OAuthParamKind::Version` and `"1.0"` as the arguments.
     fn version(&mut self) {
-        self.parameter("oauth_version", "1.0");
+        self.oauth_parameter(OAuthParamKind::Version, "1.0");
+    }
+}
+
+/// A signature produced by a [`Sign`].
+///
+/// The `Display` implementation writes the signature percent-encoded as in the request, and
+/// [`write_to`](Self::write_to) writes the same string to a [`Write`] directly, which lets the
+/// `Authorization` header be assembled without going through a `Formatter`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::fmt::{self, Display, Formatter};
+///
+/// use oauth::signature_method::Signature;
+///
+/// struct MySignature(String);
+///
+/// impl Display for MySignature {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+///         f.write_str(&self.0)
+///     }
+/// }
+///
+/// // The default `write_to` formats the signature with `Display`.
+/// impl Signature for MySignature {}
+///
+/// let mut buf = String::new();
+/// MySignature("sig%3D".into()).write_to(&mut buf).unwrap();
+/// assert_eq!(buf, "sig%3D");
+/// ```
+pub trait Signature: Display {
+    /// Writes the signature to `dst`, percent-encoded in the same way as the `Display`
+    /// implementation.
+    ///
+    /// The default implementation formats `self` with `Display`.
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        write!(dst, "{}", self)
+    }
+}
+
+impl Signature for str {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        dst.write_str(self)
+    }
+}
+
+impl<T: Signature + ?Sized> Signature for &T {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        (**self).write_to(dst)
+    }
+}
+
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    impl Signature for alloc::string::String {
+        fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+            dst.write_str(self)
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "alloc",
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "openssl",
+    feature = "rsa-sha256-06",
+    any(feature = "ring", feature = "rsa-sha1-06"),
+))]
+struct Base64PercentEncodeDisplay<A>(A);
+
+#[cfg(any(
+    feature = "alloc",
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "openssl",
+    feature = "rsa-sha256-06",
+    any(feature = "ring", feature = "rsa-sha1-06"),
+))]
+impl<A: AsRef<[u8]>> Display for Base64PercentEncodeDisplay<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = crate::util::PercentEncode(base64::display::Base64Display::with_config(
+            self.0.as_ref(),
+            base64::STANDARD,
+        ));
+        Display::fmt(&d, f)
+    }
+}
+
+#[cfg(any(
+    feature = "alloc",
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "openssl",
+    feature = "rsa-sha256-06",
+    any(feature = "ring", feature = "rsa-sha1-06"),
+))]
+impl<A: AsRef<[u8]>> Base64PercentEncodeDisplay<A> {
+    /// Writes the base64-encoded bytes to `dst`, percent-encoding the `+`, `/` and `=` characters.
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        // Encode the input in chunks of a multiple of 3 bytes so that no padding is inserted in
+        // the middle.
+        const CHUNK: usize = 3 * 64;
+        let mut buf = [0; CHUNK / 3 * 4];
+        for chunk in self.0.as_ref().chunks(CHUNK) {
+            let len = base64::encode_config_slice(chunk, base64::STANDARD, &mut buf);
+            let mut rest = core::str::from_utf8(&buf[..len]).unwrap();
+            while let Some(i) = rest.find(&['+', '/', '='][..]) {
+                dst.write_str(&rest[..i])?;
+                dst.write_str(match rest.as_bytes()[i] {
+                    b'+' => "%2B",
+                    b'/' => "%2F",
+                    _ => "%3D",
+                })?;
+                rest = &rest[i + 1..];
+            }
+            dst.write_str(rest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `bytes` to `dst` as lowercase hexadecimal digits, for the signature methods supporting
+/// hex output.
+#[cfg(any(feature = "hmac", feature = "openssl", feature = "ring"))]
+fn write_hex<W: Write + ?Sized>(bytes: &[u8], dst: &mut W) -> fmt::Result {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    for &b in bytes {
+        dst.write_char(char::from(DIGITS[usize::from(b >> 4)]))?;
+        dst.write_char(char::from(DIGITS[usize::from(b & 0xF)]))?;
+    }
+    Ok(())
+}
+
+/// Displays the signature bytes in base64 without percent-encoding.
+#[cfg(any(feature = "hmac-sha1", feature = "openssl", feature = "ring"))]
+struct Base64Display<'a>(Result<&'a [u8], fmt::Error>);
+
+#[cfg(any(feature = "hmac-sha1", feature = "openssl", feature = "ring"))]
+impl<'a> Display for Base64Display<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Ok(bytes) => Display::fmt(
+                &base64::display::Base64Display::with_config(bytes, base64::STANDARD),
+                f,
+            ),
+            Err(e) => Err(e),
+        }
     }
 }
 
@@ -209,3 +814,60 @@ fn write_signing_key<W: Write>(
     }
     Ok(())
 }
+
+/// Writes the signing key to a new `String`.
+///
+/// The buffer is allocated with the maximum length of the percent-encoded key up front, so that
+/// it isn't reallocated while being written, which would leave a copy of the key in the freed
+/// memory where `zeroize` feature cannot wipe it.
+#[cfg(feature = "alloc")]
+fn signing_key_string(client_secret: &str, token_secret: Option<&str>) -> alloc::string::String {
+    let len = client_secret.len() + token_secret.map_or(0, str::len);
+    let mut key = alloc::string::String::with_capacity(3 * len + 1);
+    write_signing_key(&mut key, client_secret, token_secret).unwrap();
+    key
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn base64_write_to() {
+        // Cover inputs spanning multiple chunks and every padding length.
+        let input: Vec<u8> = (0..=255).cycle().take(3 * 64 * 2 + 2).collect();
+        for len in 0..input.len() {
+            let signature = Base64PercentEncodeDisplay(&input[..len]);
+            let mut buf = String::new();
+            signature.write_to(&mut buf).unwrap();
+            assert_eq!(buf, signature.to_string());
+        }
+    }
+
+    #[test]
+    fn capabilities() {
+        {
+            assert!(PLAINTEXT.is_symmetric());
+            assert_eq!(PLAINTEXT.digest_strength(), DigestStrength::None);
+        }
+        #[cfg(feature = "hmac-sha1")]
+        {
+            let sm = Identity::new(HMAC_SHA1);
+            assert_eq!(sm.digest_strength(), DigestStrength::None);
+            let sm = Tee::new(HMAC_SHA1, sm);
+            assert!(sm.is_symmetric());
+            assert_eq!(sm.digest_strength(), DigestStrength::Sha1);
+            let sm = BoxSignatureMethod::new(sm);
+            assert_eq!(sm.digest_strength(), DigestStrength::Sha1);
+        }
+
+        assert!(DigestStrength::Unknown < DigestStrength::None);
+        assert!(DigestStrength::None < DigestStrength::Sha1);
+        assert!(DigestStrength::Sha1 < DigestStrength::Sha256);
+        assert_eq!(DigestStrength::from_output_len(32), DigestStrength::Sha256);
+        assert_eq!(DigestStrength::from_output_len(16), DigestStrength::Unknown);
+    }
+}
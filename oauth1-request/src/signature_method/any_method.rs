@@ -0,0 +1,336 @@
+//! A signature method that can be any of the built-in signature methods.
+
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::param::OAuthParamKind;
+
+use super::dyn_sign::DynSign;
+use super::{DigestStrength, Sign, Signature, SignatureMethod};
+
+macro_rules! any_method {
+    ($(
+        $(#[doc = $doc:expr])*
+        #[cfg($cfg:meta)]
+        $Variant:ident($SM:ty) = $name:literal,
+    )*) => {
+        /// A `SignatureMethod` that can be any of the signature methods provided by this crate
+        /// with the enabled features.
+        ///
+        /// This may be useful when you want to decide on a method to use at runtime. Unlike
+        /// `Either` (with `either` feature), this covers any number of signature methods without
+        /// nesting, and unlike [`BoxSignatureMethod`](super::BoxSignatureMethod), this doesn't
+        /// require heap allocation. Use [`from_name`](super::from_name) to get one from the name of
+        /// a signature method.
+        ///
+        /// The set of variants depends on the enabled features, so the enum is marked as
+        /// `#[non_exhaustive]`.
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(
+            all(feature = "alloc", feature = "hmac-sha1"),
+            doc = " ```"
+        )]
+        #[cfg_attr(
+            not(all(feature = "alloc", feature = "hmac-sha1")),
+            doc = " ```ignore"
+        )]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// use oauth::signature_method::{self, AnyMethod};
+        ///
+        /// // e.g. read from a configuration file.
+        /// let name = "PLAINTEXT";
+        ///
+        /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+        /// let signature_method: AnyMethod = signature_method::from_name(name).unwrap();
+        /// let authorization = oauth::get("https://example.com/", &(), &token, signature_method);
+        /// assert!(authorization.contains(r#"oauth_signature="secret%26secret""#));
+        /// ```
+        #[derive(Clone, Debug)]
+        #[non_exhaustive]
+        pub enum AnyMethod {
+            $(
+                $(#[doc = $doc])*
+                #[cfg($cfg)]
+                #[cfg_attr(docsrs, doc(cfg($cfg)))]
+                $Variant($SM),
+            )*
+        }
+
+        /// A `Sign` produced by an [`AnyMethod`].
+        #[derive(Clone, Debug)]
+        #[non_exhaustive]
+        pub enum AnyMethodSign {
+            $(
+                #[doc = concat!("The `Sign` of [`AnyMethod::", stringify!($Variant), "`].")]
+                #[cfg($cfg)]
+                #[cfg_attr(docsrs, doc(cfg($cfg)))]
+                $Variant(<$SM as SignatureMethod>::Sign),
+            )*
+        }
+
+        /// A signature produced by an [`AnyMethodSign`].
+        #[non_exhaustive]
+        pub enum AnyMethodSignature {
+            $(
+                #[doc = concat!("The signature of [`AnyMethodSign::", stringify!($Variant), "`].")]
+                #[cfg($cfg)]
+                #[cfg_attr(docsrs, doc(cfg($cfg)))]
+                $Variant(<<$SM as SignatureMethod>::Sign as Sign>::Signature),
+            )*
+        }
+
+        $(
+            #[cfg($cfg)]
+            impl From<$SM> for AnyMethod {
+                fn from(signature_method: $SM) -> Self {
+                    AnyMethod::$Variant(signature_method)
+                }
+            }
+        )*
+
+        impl AnyMethod {
+            /// Returns the name of the signature method, i.e. the value of the
+            /// `oauth_signature_method` parameter.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethod::$Variant(_) => $name,
+                    )*
+                }
+            }
+        }
+
+        // The variables are unused if no variant is enabled.
+        #[allow(unused_variables)]
+        impl SignatureMethod for AnyMethod {
+            type Sign = AnyMethodSign;
+
+            fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> AnyMethodSign {
+                match self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethod::$Variant(sm) => {
+                            AnyMethodSign::$Variant(sm.sign_with(client_secret, token_secret))
+                        }
+                    )*
+                }
+            }
+
+            fn sign_with_raw_key(self, signing_key: &str) -> AnyMethodSign {
+                match self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethod::$Variant(sm) => {
+                            AnyMethodSign::$Variant(sm.sign_with_raw_key(signing_key))
+                        }
+                    )*
+                }
+            }
+
+            fn is_symmetric(&self) -> bool {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethod::$Variant(ref sm) => sm.is_symmetric(),
+                    )*
+                }
+            }
+
+            fn digest_strength(&self) -> DigestStrength {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethod::$Variant(ref sm) => sm.digest_strength(),
+                    )*
+                }
+            }
+        }
+
+        impl AnyMethodSign {
+            fn as_dyn(&mut self) -> &mut dyn DynSign {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethodSign::$Variant(ref mut sign) => sign,
+                    )*
+                }
+            }
+
+            fn as_dyn_ref(&self) -> &dyn DynSign {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethodSign::$Variant(ref sign) => sign,
+                    )*
+                }
+            }
+        }
+
+        impl Sign for AnyMethodSign {
+            type Signature = AnyMethodSignature;
+
+            fn get_signature_method_name(&self) -> &'static str {
+                self.as_dyn_ref().get_signature_method_name()
+            }
+
+            fn request_method(&mut self, method: &str) {
+                self.as_dyn().request_method(method);
+            }
+
+            fn uri<T: Display>(&mut self, uri: T) {
+                self.as_dyn().uri(&uri);
+            }
+
+            fn parameter<V: Display>(&mut self, key: &str, value: V) {
+                self.as_dyn().parameter(key, &value);
+            }
+
+            fn parameter_display_key<K: Display, V: Display>(
+                &mut self,
+                key: K,
+                value: V,
+            ) -> fmt::Result {
+                self.as_dyn().parameter_display_key(&key, &value)
+            }
+
+            fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+                self.as_dyn().parameter_bytes(key, value);
+            }
+
+            fn oauth_parameter<V: Display>(&mut self, kind: OAuthParamKind, value: V) {
+                self.as_dyn().oauth_parameter(kind, &value);
+            }
+
+            fn delimiter(&mut self) {
+                self.as_dyn().delimiter();
+            }
+
+            fn end(self) -> AnyMethodSignature {
+                match self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethodSign::$Variant(sign) => AnyMethodSignature::$Variant(sign.end()),
+                    )*
+                }
+            }
+
+            fn body_hash<V: Display>(&mut self, value: V) {
+                self.as_dyn().body_hash(&value);
+            }
+
+            fn callback<V: Display>(&mut self, value: V) {
+                self.as_dyn().callback(&value);
+            }
+
+            fn consumer_key<V: Display>(&mut self, value: V) {
+                self.as_dyn().consumer_key(&value);
+            }
+
+            fn nonce<V: Display>(&mut self, value: V) {
+                self.as_dyn().nonce(&value);
+            }
+
+            fn use_nonce(&self) -> bool {
+                self.as_dyn_ref().use_nonce()
+            }
+
+            fn signature_method(&mut self) {
+                self.as_dyn().signature_method();
+            }
+
+            fn timestamp(&mut self, value: u64) {
+                self.as_dyn().timestamp(value);
+            }
+
+            fn use_timestamp(&self) -> bool {
+                self.as_dyn_ref().use_timestamp()
+            }
+
+            fn token<V: Display>(&mut self, value: V) {
+                self.as_dyn().token(&value);
+            }
+
+            fn verifier<V: Display>(&mut self, value: V) {
+                self.as_dyn().verifier(&value);
+            }
+
+            fn version(&mut self) {
+                self.as_dyn().version();
+            }
+        }
+
+        #[allow(unused_variables)]
+        impl Display for AnyMethodSignature {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethodSignature::$Variant(ref signature) => signature.fmt(f),
+                    )*
+                }
+            }
+        }
+
+        #[allow(unused_variables)]
+        impl Signature for AnyMethodSignature {
+            fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+                match *self {
+                    $(
+                        #[cfg($cfg)]
+                        AnyMethodSignature::$Variant(ref signature) => signature.write_to(dst),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+any_method! {
+    /// The `ECDSA-SHA256` signature method.
+    #[cfg(feature = "ecdsa-p256-013")]
+    EcdsaP256Sha256(super::EcdsaP256Sha256) = "ECDSA-SHA256",
+    /// The `HMAC-SHA1` signature method.
+    #[cfg(any(feature = "hmac-sha1", feature = "hmac-sha1-ring"))]
+    HmacSha1(super::HmacSha1) = "HMAC-SHA1",
+    /// The `PLAINTEXT` signature method.
+    #[cfg(feature = "alloc")]
+    Plaintext(super::Plaintext) = "PLAINTEXT",
+    /// The `RSA-SHA1` signature method.
+    #[cfg(feature = "rsa-sha1-06")]
+    RsaSha1(super::RsaSha1) = "RSA-SHA1",
+    /// The `RSA-SHA256` signature method.
+    #[cfg(feature = "rsa-sha256-06")]
+    RsaSha256(super::RsaSha256) = "RSA-SHA256",
+}
+
+#[cfg(all(test, feature = "hmac-sha1", feature = "alloc"))]
+mod tests {
+    use crate::{HMAC_SHA1, PLAINTEXT};
+
+    use super::*;
+
+    #[test]
+    fn from_name() {
+        for &name in &["HMAC-SHA1", "PLAINTEXT"] {
+            let method = super::super::from_name(name).unwrap();
+            assert_eq!(method.name(), name);
+            let sign = method.sign_with("cs", None);
+            assert_eq!(Sign::get_signature_method_name(&sign), name);
+        }
+        assert!(super::super::from_name("plaintext").is_none());
+    }
+
+    #[test]
+    fn negotiate_with() {
+        let candidates = [AnyMethod::from(PLAINTEXT), AnyMethod::from(HMAC_SHA1)];
+        let advertised = "PLAINTEXT&HMAC-SHA1&RSA-SHA1".split('&');
+        let method = super::super::negotiate_with(advertised, &candidates).unwrap();
+        assert_eq!(method.name(), "HMAC-SHA1");
+        let method = super::super::negotiate_with(vec!["PLAINTEXT"], &candidates).unwrap();
+        assert_eq!(method.name(), "PLAINTEXT");
+        assert!(super::super::negotiate_with(vec!["RSA-SHA1"], &candidates).is_none());
+    }
+}
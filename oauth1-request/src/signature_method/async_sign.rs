@@ -0,0 +1,226 @@
+//! Signature methods whose signing operation is asynchronous.
+//!
+//! This module is only available when `async` feature is activated.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter, Write};
+use core::future::Future;
+
+use crate::request::Request;
+use crate::serializer::auth::{Authorizer, Encoding, IntoParts};
+
+use super::{BaseStringSign, Sign, SignBaseString, Signature, SignatureMethod};
+
+/// Types that represent a signature method whose signing operation is asynchronous.
+///
+/// This is the asynchronous counterpart of [`SignatureMethod`], which is useful when signing
+/// requires a network round trip, e.g. to a cloud key management service. Use it with the
+/// `*_async` functions like [`authorize_async`](crate::authorize_async).
+pub trait AsyncSignatureMethod {
+    /// The algorithm used by this signature method to sign a signature base string.
+    type Sign: AsyncSign;
+
+    /// Creates a `Self::Sign` that signs a signature base string with the given shared-secrets.
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign;
+
+    /// Creates a `Self::Sign` that signs a signature base string with `signing_key` as-is.
+    ///
+    /// See [`SignatureMethod::sign_with_raw_key`](super::SignatureMethod::sign_with_raw_key) for
+    /// details.
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign;
+}
+
+/// Algorithms to sign a signature base string asynchronously.
+///
+/// Unlike [`Sign`](super::Sign), the signature base string is assembled by this crate and then
+/// passed to [`finish`](Self::finish) at once.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "std", doc = " ```")]
+#[cfg_attr(not(feature = "std"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// use std::future::{ready, Ready};
+///
+/// use oauth::signature_method::{AsyncSign, AsyncSignatureMethod};
+///
+/// /// A signature method that "signs" a request with the signature base string itself.
+/// struct Echo;
+///
+/// impl AsyncSignatureMethod for Echo {
+///     type Sign = Echo;
+///
+///     fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Echo {
+///         Echo
+///     }
+///
+///     fn sign_with_raw_key(self, _signing_key: &str) -> Echo {
+///         Echo
+///     }
+/// }
+///
+/// impl AsyncSign for Echo {
+///     type Signature = String;
+///     type Error = std::convert::Infallible;
+///     // A real implementation would send the signature base string to a remote service here.
+///     type Future = Ready<Result<String, Self::Error>>;
+///
+///     fn get_signature_method_name(&self) -> &'static str {
+///         "ECHO"
+///     }
+///
+///     fn finish(self, base_string: String) -> Self::Future {
+///         ready(Ok(base_string))
+///     }
+/// }
+///
+/// # async fn f() {
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let authorization = oauth::authorize_async("GET", "https://example.com/", &(), &token, Echo)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+pub trait AsyncSign {
+    /// The URL-encoded representation of `oauth_signature` string the algorithm produces.
+    type Signature: Signature;
+
+    /// The error type returned when the signing operation fails.
+    type Error;
+
+    /// The future returned by [`finish`](Self::finish).
+    type Future: Future<Output = Result<Self::Signature, Self::Error>>;
+
+    /// Returns the `oauth_signature_method` string for the signature method associated with the
+    /// algorithm.
+    fn get_signature_method_name(&self) -> &'static str;
+
+    /// Signs the signature base string.
+    fn finish(self, base_string: String) -> Self::Future;
+
+    /// Whether the signature method uses the `oauth_nonce` parameter.
+    ///
+    /// The default implementation returns `true`.
+    fn use_nonce(&self) -> bool {
+        true
+    }
+
+    /// Whether the signature method uses the `oauth_timestamp` parameter.
+    ///
+    /// The default implementation returns `true`.
+    fn use_timestamp(&self) -> bool {
+        true
+    }
+}
+
+/// An error returned by the functions signing a request with an [`AsyncSignatureMethod`], like
+/// [`authorize_async`](crate::authorize_async).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AsyncError<E> {
+    /// A `Display` implementation of a value in the request returned an error.
+    Format(fmt::Error),
+    /// The [`AsyncSign`] failed to sign the request.
+    Sign(E),
+}
+
+impl<E: Display> Display for AsyncError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            AsyncError::Format(_) => {
+                f.write_str("a formatting trait implementation returned an error")
+            }
+            AsyncError::Sign(ref e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for AsyncError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            AsyncError::Format(ref e) => Some(e),
+            AsyncError::Sign(ref e) => Some(e),
+        }
+    }
+}
+
+/// Serializes `request` with an `Authorizer` built by `make_authorizer` and signs it with the
+/// `AsyncSign`.
+pub(crate) async fn serialize<'a, R, SM, E, F>(
+    request: &R,
+    signature_method: SM,
+    make_authorizer: F,
+) -> Result<E::Output, AsyncError<<SM::Sign as AsyncSign>::Error>>
+where
+    R: Request + ?Sized,
+    SM: AsyncSignatureMethod,
+    E: Encoding,
+    F: FnOnce(Adapter<SM>) -> Authorizer<'a, Adapter<SM>, E>,
+{
+    let (sign, encoding) = request
+        .serialize(IntoParts(make_authorizer(Adapter(signature_method))))
+        .map_err(AsyncError::Format)?;
+    let Pending { sign, base_string } = sign.end().into_inner().map_err(AsyncError::Format)?;
+    let signature = sign.finish(base_string).await.map_err(AsyncError::Sign)?;
+    encoding.end(signature).map_err(AsyncError::Format)
+}
+
+/// A `SignatureMethod` that assembles the signature base string for an `AsyncSignatureMethod`.
+pub(crate) struct Adapter<SM>(SM);
+
+pub(crate) struct PendingSign<S>(S);
+
+/// The "signature" of a `PendingSign`, which carries the signature base string to be signed
+/// asynchronously.
+pub(crate) struct Pending<S> {
+    sign: S,
+    base_string: String,
+}
+
+impl<SM: AsyncSignatureMethod> SignatureMethod for Adapter<SM> {
+    type Sign = BaseStringSign<PendingSign<SM::Sign>>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        BaseStringSign::new(PendingSign(self.0.sign_with(client_secret, token_secret)))
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+        BaseStringSign::new(PendingSign(self.0.sign_with_raw_key(signing_key)))
+    }
+}
+
+impl<S: AsyncSign> SignBaseString for PendingSign<S> {
+    type Signature = Pending<S>;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.0.get_signature_method_name()
+    }
+
+    fn sign_base_string(self, base_string: &str) -> Pending<S> {
+        Pending {
+            sign: self.0,
+            base_string: base_string.into(),
+        }
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.0.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.0.use_timestamp()
+    }
+}
+
+impl<S> Display for Pending<S> {
+    fn fmt(&self, _: &mut Formatter<'_>) -> fmt::Result {
+        // A `Pending` is never written to the output.
+        Err(fmt::Error)
+    }
+}
+
+impl<S> Signature for Pending<S> {
+    fn write_to<W: Write + ?Sized>(&self, _: &mut W) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
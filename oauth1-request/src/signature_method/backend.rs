@@ -0,0 +1,167 @@
+use core::fmt::Debug;
+
+#[cfg(feature = "alloc")]
+use super::BoxSignatureMethod;
+use super::SignatureMethod;
+
+/// A crypto backend providing signature method implementations, enabled by Cargo features.
+///
+/// This lets downstream code stay agnostic of the backend types, so that switching crypto
+/// providers is a matter of toggling features of this crate, or of configuration at runtime.
+///
+/// When more than one backend is enabled, they are preferred in the following order:
+///
+/// 1. RustCrypto (`hmac-sha1` feature)
+/// 2. `ring` (`ring` feature)
+/// 3. OpenSSL (`openssl` feature)
+///
+/// The set of variants depends on the enabled features, so the enum is marked as
+/// `#[non_exhaustive]`.
+///
+/// ## Example
+///
+/// Selecting a backend from configuration:
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # use oauth1_request::signature_method::SignatureBackend;
+/// // e.g. read from a configuration file.
+/// let name = "ring";
+///
+/// let backend = SignatureBackend::from_name(name).unwrap_or_else(SignatureBackend::preferred);
+/// let token = oauth1_request::Token::from_parts("consumer", "secret", "token", "secret");
+/// let authorization =
+///     oauth1_request::get("https://example.com/", &(), &token, backend.hmac_sha1_method());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SignatureBackend {
+    /// The RustCrypto crates.
+    #[cfg(feature = "hmac-sha1")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hmac-sha1")))]
+    RustCrypto,
+    /// The `ring` crate.
+    #[cfg(feature = "ring")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ring")))]
+    Ring,
+    /// The `openssl` crate.
+    #[cfg(feature = "openssl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "openssl")))]
+    OpenSsl,
+}
+
+const AVAILABLE: &[SignatureBackend] = &[
+    #[cfg(feature = "hmac-sha1")]
+    SignatureBackend::RustCrypto,
+    #[cfg(feature = "ring")]
+    SignatureBackend::Ring,
+    #[cfg(feature = "openssl")]
+    SignatureBackend::OpenSsl,
+];
+
+impl SignatureBackend {
+    /// Returns the `HMAC-SHA1` signature method of the preferred backend.
+    ///
+    #[cfg_attr(feature = "std", doc = " ```")]
+    #[cfg_attr(not(feature = "std"), doc = " ```ignore")]
+    /// # use oauth1_request::signature_method::SignatureBackend;
+    /// let token = oauth1_request::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let authorization = oauth1_request::get(
+    ///     "https://example.com/",
+    ///     &(),
+    ///     &token,
+    ///     SignatureBackend::hmac_sha1(),
+    /// );
+    /// ```
+    pub fn hmac_sha1() -> impl SignatureMethod + Copy + Debug {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "hmac-sha1")] {
+                super::hmac_sha1::HMAC_SHA1
+            } else if #[cfg(feature = "ring")] {
+                super::ring::HMAC_SHA1
+            } else {
+                super::openssl::HMAC_SHA1
+            }
+        }
+    }
+
+    /// Returns the preferred backend among the enabled ones.
+    pub fn preferred() -> Self {
+        AVAILABLE[0]
+    }
+
+    /// Returns the enabled backends, in the order of preference.
+    pub fn available() -> &'static [Self] {
+        AVAILABLE
+    }
+
+    /// Returns the backend named `name` (`"rustcrypto"`, `"ring"` or `"openssl"`), or `None` if
+    /// the name is unknown or the backend is not enabled.
+    pub fn from_name(name: &str) -> Option<Self> {
+        AVAILABLE
+            .iter()
+            .copied()
+            .find(|backend| backend.name() == name)
+    }
+
+    /// Returns the name of the backend, as accepted by [`from_name`](Self::from_name).
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "hmac-sha1")]
+            SignatureBackend::RustCrypto => "rustcrypto",
+            #[cfg(feature = "ring")]
+            SignatureBackend::Ring => "ring",
+            #[cfg(feature = "openssl")]
+            SignatureBackend::OpenSsl => "openssl",
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Returns the `HMAC-SHA1` signature method of the backend.
+        ///
+        /// Unlike [`hmac_sha1`](Self::hmac_sha1), this selects the backend at runtime, so the
+        /// signature method is type-erased.
+        #[cfg(feature = "alloc")]
+        pub fn hmac_sha1_method(self) -> BoxSignatureMethod<'static> {
+            match self {
+                #[cfg(feature = "hmac-sha1")]
+                SignatureBackend::RustCrypto => {
+                    BoxSignatureMethod::new(super::hmac_sha1::HMAC_SHA1)
+                }
+                #[cfg(feature = "ring")]
+                SignatureBackend::Ring => BoxSignatureMethod::new(super::ring::HMAC_SHA1),
+                #[cfg(feature = "openssl")]
+                SignatureBackend::OpenSsl => BoxSignatureMethod::new(super::openssl::HMAC_SHA1),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::Token;
+
+    use super::*;
+
+    #[test]
+    fn same_signature() {
+        let sign = |signature_method| {
+            let token = Token::from_parts("ck", "cs", "tk", "ts");
+            let mut builder = crate::Builder::with_token(token, signature_method);
+            builder
+                .nonce("nonce")
+                .timestamp(core::num::NonZeroU64::new(1000));
+            builder.get("https://example.com/", &())
+        };
+        let expected = sign(BoxSignatureMethod::new(SignatureBackend::hmac_sha1()));
+        for &backend in SignatureBackend::available() {
+            assert_eq!(SignatureBackend::from_name(backend.name()), Some(backend));
+            assert_eq!(sign(backend.hmac_sha1_method()), expected);
+        }
+        assert_eq!(
+            SignatureBackend::available()[0],
+            SignatureBackend::preferred()
+        );
+        assert_eq!(SignatureBackend::from_name("unknown"), None);
+    }
+}
@@ -0,0 +1,192 @@
+//! An adapter for signing algorithms that sign a complete signature base string at once.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter, Write};
+
+use super::{Sign, Signature};
+
+/// Algorithms that sign a complete signature base string at once.
+///
+/// Implementing [`Sign`] requires reconstituting the signature base string from a series of
+/// incremental method calls. Types implementing this trait instead receive the fully assembled
+/// signature base string, which is convenient for signature methods backed by an API that cannot
+/// sign incrementally (e.g. an external signing service) or for debugging purposes.
+///
+/// Use [`BaseStringSign`] to turn the type into a `Sign`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// use oauth::signature_method::{BaseStringSign, Sign, SignBaseString, SignatureMethod};
+///
+/// /// A signature method that "signs" a request with the signature base string itself.
+/// #[derive(Clone, Copy)]
+/// struct Echo;
+///
+/// impl SignatureMethod for Echo {
+///     type Sign = BaseStringSign<Echo>;
+///
+///     fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+///         BaseStringSign::new(self)
+///     }
+///
+///     fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+///         BaseStringSign::new(self)
+///     }
+/// }
+///
+/// impl SignBaseString for Echo {
+///     type Signature = String;
+///
+///     fn get_signature_method_name(&self) -> &'static str {
+///         "ECHO"
+///     }
+///
+///     fn sign_base_string(self, base_string: &str) -> String {
+///         base_string.to_owned()
+///     }
+/// }
+///
+/// let mut sign = Echo.sign_with("", None);
+/// sign.request_method("GET");
+/// sign.uri("http%3A%2F%2Fexample.com%2F");
+/// sign.parameter("foo", "bar");
+/// assert_eq!(
+///     sign.end().to_string(),
+///     "GET&http%3A%2F%2Fexample.com%2F&foo%3Dbar",
+/// );
+/// ```
+pub trait SignBaseString {
+    /// The URL-encoded representation of `oauth_signature` string the algorithm produces.
+    type Signature: Signature;
+
+    /// Returns the `oauth_signature_method` string for the signature method associated with the
+    /// algorithm.
+    fn get_signature_method_name(&self) -> &'static str;
+
+    /// Signs the signature base string.
+    fn sign_base_string(self, base_string: &str) -> Self::Signature;
+
+    /// Whether the signature method uses the `oauth_nonce` parameter.
+    ///
+    /// The default implementation returns `true`.
+    fn use_nonce(&self) -> bool {
+        true
+    }
+
+    /// Whether the signature method uses the `oauth_timestamp` parameter.
+    ///
+    /// The default implementation returns `true`.
+    fn use_timestamp(&self) -> bool {
+        true
+    }
+}
+
+/// A `Sign` implementation that assembles the signature base string and passes it to a
+/// [`SignBaseString`] on `end`.
+#[derive(Clone, Debug)]
+pub struct BaseStringSign<S> {
+    inner: S,
+    base_string: String,
+    result: fmt::Result,
+}
+
+/// A signature produced by a `BaseStringSign`.
+///
+/// Formatting the signature fails if a `Display` implementation has failed while assembling the
+/// signature base string.
+#[derive(Clone, Debug)]
+pub struct BaseStringSignature<T> {
+    inner: Result<T, fmt::Error>,
+}
+
+impl<S: SignBaseString> BaseStringSign<S> {
+    /// Creates a new `BaseStringSign` that signs the signature base string with `inner`.
+    pub fn new(inner: S) -> Self {
+        BaseStringSign {
+            inner,
+            base_string: String::new(),
+            result: Ok(()),
+        }
+    }
+
+    /// Returns the signature base string assembled so far.
+    pub fn base_string(&self) -> &str {
+        &self.base_string
+    }
+}
+
+impl<S: SignBaseString> Sign for BaseStringSign<S> {
+    type Signature = BaseStringSignature<S::Signature>;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.base_string.push_str(method);
+        self.base_string.push('&');
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        let result = write!(self.base_string, "{}&", uri);
+        self.result = self.result.and(result);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        let result = write!(self.base_string, "{}%3D{}", key, value);
+        self.result = self.result.and(result);
+    }
+
+    fn delimiter(&mut self) {
+        self.base_string.push_str("%26");
+    }
+
+    fn end(self) -> Self::Signature {
+        let BaseStringSign {
+            inner,
+            base_string,
+            result,
+        } = self;
+        BaseStringSignature {
+            inner: result.map(|()| inner.sign_base_string(&base_string)),
+        }
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+}
+
+impl<T> BaseStringSignature<T> {
+    /// Returns the signature produced by the underlying `SignBaseString`, or an error if a
+    /// `Display` implementation has failed while assembling the signature base string.
+    pub fn into_inner(self) -> Result<T, fmt::Error> {
+        self.inner
+    }
+}
+
+impl<T: Display> Display for BaseStringSignature<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.fmt(f),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T: Signature> Signature for BaseStringSignature<T> {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.write_to(dst),
+            Err(e) => Err(e),
+        }
+    }
+}
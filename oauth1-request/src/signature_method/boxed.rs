@@ -0,0 +1,256 @@
+//! Type-erased signature methods.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use crate::param::OAuthParamKind;
+
+use super::dyn_sign::DynSign;
+use super::{DigestStrength, Sign, Signature, SignatureMethod};
+
+/// A type-erased `SignatureMethod`, which lets you select a signature method at runtime, e.g.
+/// from configuration.
+///
+/// Unlike `Either` (with `either` feature), this works with any number of signature method types
+/// at the cost of a heap allocation and dynamic dispatch. The signature is formatted into
+/// a `String` when the signing process finishes.
+///
+/// The underlying signature method must be `Clone + Send + Sync`, so that a `BoxSignatureMethod`
+/// can be used with the `&self` methods of [`Builder`](crate::Builder) and shared across threads.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::BoxSignatureMethod;
+///
+/// fn signature_method(name: &str) -> Option<BoxSignatureMethod<'static>> {
+///     match name {
+///         "HMAC-SHA1" => Some(BoxSignatureMethod::new(oauth::HMAC_SHA1)),
+///         "PLAINTEXT" => Some(BoxSignatureMethod::new(oauth::PLAINTEXT)),
+///         _ => None,
+///     }
+/// }
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let signature_method = signature_method("PLAINTEXT").unwrap();
+/// let authorization = oauth::get("https://example.com/", &(), &token, signature_method);
+/// assert!(authorization.contains(r#"oauth_signature="secret%26secret""#));
+/// ```
+pub struct BoxSignatureMethod<'a> {
+    inner: Box<dyn DynSignatureMethod<'a> + Send + Sync + 'a>,
+}
+
+/// A type-erased `Sign` produced by a [`BoxSignatureMethod`].
+pub struct BoxSign<'a> {
+    inner: Box<dyn DynSign + 'a>,
+}
+
+/// A signature produced by a `BoxSign`.
+#[derive(Clone, Debug)]
+pub struct BoxSignature {
+    inner: Result<String, fmt::Error>,
+}
+
+/// An object-safe version of `SignatureMethod`.
+trait DynSignatureMethod<'a> {
+    fn sign_with(self: Box<Self>, client_secret: &str, token_secret: Option<&str>) -> BoxSign<'a>;
+    fn sign_with_raw_key(self: Box<Self>, signing_key: &str) -> BoxSign<'a>;
+    fn is_symmetric(&self) -> bool;
+    fn digest_strength(&self) -> DigestStrength;
+    fn box_clone(&self) -> Box<dyn DynSignatureMethod<'a> + Send + Sync + 'a>;
+}
+
+impl<'a> BoxSignatureMethod<'a> {
+    /// Creates a new `BoxSignatureMethod` that wraps `signature_method`.
+    pub fn new<SM>(signature_method: SM) -> Self
+    where
+        SM: SignatureMethod + Clone + Send + Sync + 'a,
+        SM::Sign: 'a,
+    {
+        BoxSignatureMethod {
+            inner: Box::new(signature_method),
+        }
+    }
+}
+
+impl<'a> Clone for BoxSignatureMethod<'a> {
+    fn clone(&self) -> Self {
+        BoxSignatureMethod {
+            inner: self.inner.box_clone(),
+        }
+    }
+}
+
+impl<'a> Debug for BoxSignatureMethod<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxSignatureMethod").finish()
+    }
+}
+
+impl<'a> Debug for BoxSign<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoxSign")
+            .field("signature_method", &self.inner.get_signature_method_name())
+            .finish()
+    }
+}
+
+impl<'a> SignatureMethod for BoxSignatureMethod<'a> {
+    type Sign = BoxSign<'a>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> BoxSign<'a> {
+        self.inner.sign_with(client_secret, token_secret)
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> BoxSign<'a> {
+        self.inner.sign_with_raw_key(signing_key)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.inner.is_symmetric()
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        self.inner.digest_strength()
+    }
+}
+
+impl<'a> Sign for BoxSign<'a> {
+    type Signature = BoxSignature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.inner.uri(&uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.inner.parameter(key, &value);
+    }
+
+    fn parameter_display_key<K: Display, V: Display>(&mut self, key: K, value: V) -> fmt::Result {
+        self.inner.parameter_display_key(&key, &value)
+    }
+
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.inner.parameter_bytes(key, value);
+    }
+
+    fn oauth_parameter<V: Display>(&mut self, kind: OAuthParamKind, value: V) {
+        self.inner.oauth_parameter(kind, &value);
+    }
+
+    fn delimiter(&mut self) {
+        self.inner.delimiter();
+    }
+
+    fn end(self) -> BoxSignature {
+        BoxSignature {
+            inner: self.inner.end(),
+        }
+    }
+
+    fn body_hash<V: Display>(&mut self, value: V) {
+        self.inner.body_hash(&value);
+    }
+
+    fn callback<V: Display>(&mut self, value: V) {
+        self.inner.callback(&value);
+    }
+
+    fn consumer_key<V: Display>(&mut self, value: V) {
+        self.inner.consumer_key(&value);
+    }
+
+    fn nonce<V: Display>(&mut self, value: V) {
+        self.inner.nonce(&value);
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn signature_method(&mut self) {
+        self.inner.signature_method();
+    }
+
+    fn timestamp(&mut self, value: u64) {
+        self.inner.timestamp(value);
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+
+    fn token<V: Display>(&mut self, value: V) {
+        self.inner.token(&value);
+    }
+
+    fn verifier<V: Display>(&mut self, value: V) {
+        self.inner.verifier(&value);
+    }
+
+    fn version(&mut self) {
+        self.inner.version();
+    }
+}
+
+impl Display for BoxSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref signature) => f.write_str(signature),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Signature for BoxSignature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref signature) => dst.write_str(signature),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<'a, SM> DynSignatureMethod<'a> for SM
+where
+    SM: SignatureMethod + Clone + Send + Sync + 'a,
+    SM::Sign: 'a,
+{
+    fn sign_with(self: Box<Self>, client_secret: &str, token_secret: Option<&str>) -> BoxSign<'a> {
+        BoxSign {
+            inner: Box::new((*self).sign_with(client_secret, token_secret)),
+        }
+    }
+
+    fn sign_with_raw_key(self: Box<Self>, signing_key: &str) -> BoxSign<'a> {
+        BoxSign {
+            inner: Box::new((*self).sign_with_raw_key(signing_key)),
+        }
+    }
+
+    fn is_symmetric(&self) -> bool {
+        SignatureMethod::is_symmetric(self)
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        SignatureMethod::digest_strength(self)
+    }
+
+    fn box_clone(&self) -> Box<dyn DynSignatureMethod<'a> + Send + Sync + 'a> {
+        Box::new(self.clone())
+    }
+}
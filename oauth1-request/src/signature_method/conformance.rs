@@ -0,0 +1,408 @@
+//! A conformance test suite for [`SignatureMethod`] implementations.
+//!
+//! This module is only available when `test-util` feature is activated.
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Write};
+use core::num::NonZeroU64;
+
+use crate::request::{ParameterList, Request};
+use crate::serializer::auth::{Authorizer, Encoding, Options};
+use crate::Credentials;
+
+use super::{Sign, Signature, SignatureMethod};
+
+const CONSUMER_KEY: &str = "9djdj82h48djs9d2";
+const NONCE: &str = "7d8f3e4a";
+const TIMESTAMP: u64 = 137131201;
+
+struct Case {
+    name: &'static str,
+    method: &'static str,
+    uri: &'static str,
+    // The base string URI the `uri` should be normalized to.
+    base_string_uri: &'static str,
+    parameters: &'static [(&'static str, &'static str)],
+    consumer_key: &'static str,
+    client_secret: &'static str,
+    token: Option<(&'static str, &'static str)>,
+    callback: Option<&'static str>,
+}
+
+const CASES: &[Case] = &[
+    Case {
+        name: "RFC 5849 section 3.4.1.1. example",
+        method: "POST",
+        uri: "http://example.com:80/request",
+        base_string_uri: "http://example.com/request",
+        parameters: &[
+            ("b5", "=%3D"),
+            ("a3", "a"),
+            ("c@", ""),
+            ("a2", "r b"),
+            ("c2", ""),
+            ("a3", "2 q"),
+        ],
+        consumer_key: CONSUMER_KEY,
+        client_secret: "j49sk3j29djd",
+        token: Some(("kkk9d7dh3k39sjv7", "dh893hdasih9")),
+        callback: None,
+    },
+    Case {
+        name: "percent-encoding",
+        method: "GET",
+        uri: "https://example.com:8443/a%20b/~c",
+        base_string_uri: "https://example.com:8443/a%20b/~c",
+        parameters: &[
+            ("unreserved", "AZaz09-._~"),
+            ("reserved", ":/?#[]@!$&'()*+,;="),
+            ("others", " \"%<>\\^`{|}\u{7f}"),
+            ("non-ascii", "\u{e9}\u{65e5}\u{672c}\u{1f980}"),
+            ("k\u{e9}y with space", "v"),
+            // A key of hundreds of bytes once percent-encoded.
+            (
+                concat!(
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                    "\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}\u{1f980}",
+                ),
+                "long key",
+            ),
+        ],
+        consumer_key: CONSUMER_KEY,
+        client_secret: "secret&with=reserved chars",
+        token: Some(("token", "\u{1f511}")),
+        callback: Some("https://client.example.net/cb?x=1&y=2"),
+    },
+    Case {
+        name: "sorting ties",
+        method: "GET",
+        uri: "https://example.com/search",
+        base_string_uri: "https://example.com/search",
+        parameters: &[
+            ("a", "b"),
+            ("a", "a"),
+            ("a", ""),
+            ("aa", "x"),
+            ("a1", "y"),
+            ("A", "z"),
+            ("a.b", "w"),
+            ("n", "2"),
+            ("n", "10"),
+            ("n", "1 0"),
+            ("v", "@"),
+            ("v", "1"),
+            ("oauth", "v"),
+            ("oauth_z", "v"),
+            ("z", "a%"),
+            ("z", "a "),
+        ],
+        consumer_key: CONSUMER_KEY,
+        client_secret: "secret",
+        token: Some(("token", "token secret")),
+        callback: None,
+    },
+    Case {
+        name: "empty values",
+        method: "POST",
+        uri: "http://example.com",
+        base_string_uri: "http://example.com",
+        parameters: &[("empty", ""), ("e", ""), ("f", "")],
+        consumer_key: CONSUMER_KEY,
+        client_secret: "",
+        token: None,
+        callback: None,
+    },
+    Case {
+        name: "custom request method",
+        method: "PROPFIND",
+        uri: "HTTPS://Example.com:443/",
+        base_string_uri: "https://example.com/",
+        parameters: &[],
+        consumer_key: CONSUMER_KEY,
+        client_secret: "secret",
+        token: Some(("token", "")),
+        callback: Some("oob"),
+    },
+    Case {
+        name: "anonymous consumer",
+        method: "GET",
+        uri: "https://example.com/public",
+        base_string_uri: "https://example.com/public",
+        parameters: &[("q", "x")],
+        consumer_key: "",
+        client_secret: "",
+        token: None,
+        callback: None,
+    },
+];
+
+/// Runs the conformance test suite against `signature_method`.
+///
+/// The suite signs a set of requests exercising the edge cases of the signature base string
+/// construction ([RFC 5849 section 3.4.1.][rfc]), including the example in the RFC,
+/// percent-encoding of reserved and non-ASCII characters, ties in the parameter sorting, empty
+/// values and an anonymous consumer with empty credentials. For each request, `verify` is called
+/// with the expected signature base string, the signing key (`client_secret&token_secret`, both
+/// percent-encoded) and the `oauth_signature` value the signature method produced
+/// (percent-decoded), and should return whether the signature is valid.
+///
+/// This is useful for certifying a custom `SignatureMethod` implementation against a reference
+/// implementation of the algorithm.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+///
+/// # Panics
+///
+/// Panics if `verify` returns `false` or the signature method produced a signature that is not
+/// properly percent-encoded.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::conformance::run_conformance;
+///
+/// // The `PLAINTEXT` signature is the signing key itself.
+/// run_conformance(oauth::PLAINTEXT, |_base_string, key, signature| signature == key);
+/// ```
+pub fn run_conformance<SM, F>(signature_method: SM, mut verify: F)
+where
+    SM: SignatureMethod + Clone,
+    F: FnMut(&str, &str, &str) -> bool,
+{
+    for case in CASES {
+        run_case(case, signature_method.clone(), &mut verify);
+    }
+}
+
+fn run_case<SM, F>(case: &Case, signature_method: SM, verify: &mut F)
+where
+    SM: SignatureMethod + Clone,
+    F: FnMut(&str, &str, &str) -> bool,
+{
+    let mut options = Options::new();
+    options
+        .nonce(NONCE)
+        .timestamp(NonZeroU64::new(TIMESTAMP))
+        .callback(case.callback)
+        .normalize_uri(true);
+    let client = Credentials::new(case.consumer_key, case.client_secret);
+    let token = case
+        .token
+        .map(|(identifier, secret)| Credentials::new(identifier, secret));
+
+    let base_string = {
+        let sign = signature_method.clone().sign_with(
+            client.secret.into_inner(),
+            token.map(|t| t.secret.into_inner()),
+        );
+        base_string(case, &sign)
+    };
+    let key = {
+        let mut key = String::new();
+        encode(&mut key, case.client_secret);
+        key.push('&');
+        if let Some((_, secret)) = case.token {
+            encode(&mut key, secret);
+        }
+        key
+    };
+
+    // `Serializer` takes the keys already percent-encoded.
+    let request: ParameterList<_, _, Vec<_>> = case
+        .parameters
+        .iter()
+        .map(|&(k, v)| (encoded(k), v))
+        .collect();
+    let authorizer = Authorizer::with_encoding(
+        SignatureCapture,
+        case.method,
+        case.uri,
+        client,
+        token,
+        &options,
+        signature_method,
+    );
+    let signature = request.serialize(authorizer);
+
+    let decoded = decode(&signature).unwrap_or_else(|| {
+        panic!(
+            "conformance case `{}`: the signature `{}` is not properly percent-encoded",
+            case.name, signature,
+        )
+    });
+    assert!(
+        verify(&base_string, &key, &decoded),
+        "conformance case `{}`: verification failed\
+         \n base string: `{}`\
+         \n signing key: `{}`\
+         \n   signature: `{}`",
+        case.name,
+        base_string,
+        key,
+        decoded,
+    );
+}
+
+/// Builds the signature base string of `case` from scratch, without relying on the serializers.
+fn base_string<S: Sign>(case: &Case, sign: &S) -> String {
+    let mut parameters: Vec<(String, String)> = case
+        .parameters
+        .iter()
+        .map(|&(k, v)| (encoded(k), encoded(v)))
+        .collect();
+    let mut oauth = |k: &str, v: &str| parameters.push((k.to_string(), encoded(v)));
+    if let Some(callback) = case.callback {
+        oauth("oauth_callback", callback);
+    }
+    oauth("oauth_consumer_key", case.consumer_key);
+    if sign.use_nonce() {
+        oauth("oauth_nonce", NONCE);
+    }
+    oauth("oauth_signature_method", sign.get_signature_method_name());
+    if sign.use_timestamp() {
+        oauth("oauth_timestamp", &TIMESTAMP.to_string());
+    }
+    if let Some((token, _)) = case.token {
+        oauth("oauth_token", token);
+    }
+    parameters.sort();
+
+    let mut normalized = String::new();
+    for (i, (k, v)) in parameters.iter().enumerate() {
+        if i > 0 {
+            normalized.push('&');
+        }
+        write!(normalized, "{}={}", k, v).unwrap();
+    }
+
+    let mut ret = String::new();
+    encode(&mut ret, case.method);
+    ret.push('&');
+    encode(&mut ret, case.base_string_uri);
+    ret.push('&');
+    encode(&mut ret, &normalized);
+    ret
+}
+
+/// Percent-encodes `s` as defined in RFC 5849 section 3.6.
+fn encode(buf: &mut String, s: &str) {
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                buf.push(b as char)
+            }
+            _ => write!(buf, "%{:02X}", b).unwrap(),
+        }
+    }
+}
+
+fn encoded(s: &str) -> String {
+    let mut ret = String::new();
+    encode(&mut ret, s);
+    ret
+}
+
+/// Decodes a string that consists only of unreserved characters and `%XX` sequences.
+fn decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => bytes.push(b),
+            b'%' => {
+                let hi = (iter.next()? as char).to_digit(16)?;
+                let lo = (iter.next()? as char).to_digit(16)?;
+                bytes.push((hi * 0x10 + lo) as u8);
+            }
+            _ => return None,
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// An `Encoding` that discards the `oauth_*` parameters and returns the signature.
+struct SignatureCapture;
+
+impl Encoding for SignatureCapture {
+    type Output = String;
+
+    fn oauth_parameter<V: Display>(&mut self, _key: &str, _value: V) -> fmt::Result {
+        Ok(())
+    }
+
+    fn default_parameter<V: Display>(&mut self, _key: &str, _value: V) -> fmt::Result {
+        Ok(())
+    }
+
+    fn end<V: Signature>(self, signature: V) -> Result<String, fmt::Error> {
+        let mut ret = String::new();
+        signature.write_to(&mut ret)?;
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc_example_base_string() {
+        struct Name;
+        impl Sign for Name {
+            type Signature = &'static str;
+            fn get_signature_method_name(&self) -> &'static str {
+                "HMAC-SHA1"
+            }
+            fn request_method(&mut self, _: &str) {}
+            fn uri<T: Display>(&mut self, _: T) {}
+            fn parameter<V: Display>(&mut self, _: &str, _: V) {}
+            fn delimiter(&mut self) {}
+            fn end(self) -> &'static str {
+                ""
+            }
+        }
+
+        // RFC 5849 section 3.4.1.1. (line breaks are for display purposes only).
+        let expected = "POST&http%3A%2F%2Fexample.com%2Frequest&a2%3Dr%2520b%26a3%3D2%2520q\
+            %26a3%3Da%26b5%3D%253D%25253D%26c%2540%3D%26c2%3D%26oauth_consumer_\
+            key%3D9djdj82h48djs9d2%26oauth_nonce%3D7d8f3e4a%26oauth_signature_m\
+            ethod%3DHMAC-SHA1%26oauth_timestamp%3D137131201%26oauth_token%3Dkkk\
+            9d7dh3k39sjv7";
+        assert_eq!(base_string(&CASES[0], &Name), expected);
+    }
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn hmac_sha1() {
+        use hmac012::{Hmac, Mac};
+
+        run_conformance(
+            crate::signature_method::HMAC_SHA1,
+            |base_string, key, signature| {
+                let mut mac = Hmac::<sha1::Sha1>::new_from_slice(key.as_bytes()).unwrap();
+                mac.update(base_string.as_bytes());
+                base64::encode(mac.finalize().into_bytes()) == signature
+            },
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn plaintext() {
+        run_conformance(crate::signature_method::PLAINTEXT, |_, key, signature| {
+            signature == key
+        });
+    }
+}
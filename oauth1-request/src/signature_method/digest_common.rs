@@ -1,51 +1,160 @@
-use core::fmt::{self, Display, Formatter, Write};
+use core::fmt::{self, Display, Write};
 
-use base64::display::Base64Display;
+#[cfg(feature = "hmac")]
+use digest::core_api::{Block, BlockSizeUser};
 use digest::Update;
+#[cfg(feature = "hmac")]
+use digest::{Digest, KeyInit};
 
-use crate::util::PercentEncode;
+use crate::util::{double_percent_encode_bytes, Chunked};
 
 #[derive(Clone, Debug)]
-pub struct UpdateSign<U>(pub U);
+pub struct UpdateSign<U> {
+    update: U,
+    // Errors from `Display` implementations are deferred until the signature is formatted so that
+    // they don't abort the process.
+    result: fmt::Result,
+}
 
-pub struct Base64PercentEncodeDisplay<A>(pub A);
+impl<U> UpdateSign<U> {
+    pub fn new(update: U) -> Self {
+        UpdateSign {
+            update,
+            result: Ok(()),
+        }
+    }
 
-struct UpdateWrite<'a, M>(&'a mut M);
+    /// Returns the underlying `Update`, or an error if a `Display` implementation has failed.
+    pub fn finish(self) -> Result<U, fmt::Error> {
+        self.result.map(|()| self.update)
+    }
+}
 
 impl<U: Update> UpdateSign<U> {
     pub fn request_method(&mut self, method: &str) {
-        self.0.update(method.as_bytes());
-        self.0.update(b"&");
+        self.update.update(method.as_bytes());
+        self.update.update(b"&");
     }
 
     pub fn uri<T: Display>(&mut self, uri: T) {
-        write!(UpdateWrite(&mut self.0), "{}&", uri).unwrap();
+        let update = &mut self.update;
+        let mut w = Chunked::new(|chunk| update.update(chunk));
+        let result = write!(w, "{}&", uri);
+        w.finish();
+        self.result = self.result.and(result);
     }
 
     pub fn parameter<V: Display>(&mut self, key: &str, value: V) {
-        self.0.update(key.as_bytes());
-        self.0.update(b"%3D"); // '='
-        write!(UpdateWrite(&mut self.0), "{}", value).unwrap();
+        self.update.update(key.as_bytes());
+        self.update.update(b"%3D"); // '='
+        let update = &mut self.update;
+        let mut w = Chunked::new(|chunk| update.update(chunk));
+        let result = write!(w, "{}", value);
+        w.finish();
+        self.result = self.result.and(result);
+    }
+
+    pub fn parameter_display_key<K: Display, V: Display>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> fmt::Result {
+        let update = &mut self.update;
+        let mut w = Chunked::new(|chunk| update.update(chunk));
+        let result = write!(w, "{}%3D{}", key, value);
+        w.finish();
+        self.result = self.result.and(result);
+        Ok(())
+    }
+
+    pub fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.update.update(key.as_bytes());
+        self.update.update(b"%3D"); // '='
+        let update = &mut self.update;
+        let _ = double_percent_encode_bytes(value, |chunk| {
+            update.update(chunk.as_bytes());
+            Ok(())
+        });
     }
 
     pub fn delimiter(&mut self) {
-        self.0.update(b"%26"); // '&'
+        self.update.update(b"%26"); // '&'
     }
 }
 
-impl<A: AsRef<[u8]>> Display for Base64PercentEncodeDisplay<A> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let d = PercentEncode(Base64Display::with_config(
-            self.0.as_ref(),
-            base64::STANDARD,
-        ));
-        Display::fmt(&d, f)
+/// A `Write` that builds an HMAC key without allocating, hashing the key if it turns out to be
+/// longer than the block size of `D` like HMAC does.
+#[cfg(feature = "hmac")]
+#[derive(Clone)]
+pub enum SigningKey<D: BlockSizeUser> {
+    Key { buf: Block<D>, pos: usize },
+    Digest(D),
+}
+
+#[cfg(feature = "hmac")]
+impl<D: Digest + BlockSizeUser> SigningKey<D> {
+    pub fn new() -> Self {
+        SigningKey::Key {
+            buf: Block::<D>::default(),
+            pos: 0,
+        }
+    }
+
+    pub fn write(&mut self, input: &[u8]) {
+        *self = match *self {
+            SigningKey::Key {
+                ref mut buf,
+                ref mut pos,
+            } => {
+                if input.len() > buf.len() - *pos {
+                    let mut digest = D::new();
+                    Digest::update(&mut digest, &buf[..*pos]);
+                    Digest::update(&mut digest, input);
+                    wipe(buf);
+                    SigningKey::Digest(digest)
+                } else {
+                    buf[*pos..(*pos + input.len())].copy_from_slice(input);
+                    *pos += input.len();
+                    return;
+                }
+            }
+            SigningKey::Digest(ref mut digest) => {
+                Digest::update(digest, input);
+                return;
+            }
+        };
     }
+
+    pub fn into_mac<M: KeyInit>(self) -> M {
+        match self {
+            SigningKey::Key { mut buf, pos } => {
+                let mac = M::new_from_slice(&buf[..pos]).unwrap();
+                wipe(&mut buf);
+                mac
+            }
+            SigningKey::Digest(digest) => {
+                let mut key = digest.finalize();
+                let mac = M::new_from_slice(&key).unwrap();
+                wipe(&mut key);
+                mac
+            }
+        }
+    }
+}
+
+/// Wipes the key material in `buf` with `zeroize` feature.
+#[cfg(feature = "hmac")]
+fn wipe(buf: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    zeroize1::Zeroize::zeroize(buf);
+    #[cfg(not(feature = "zeroize"))]
+    let _ = buf;
 }
 
-impl<'a, M: Update> Write for UpdateWrite<'a, M> {
+#[cfg(feature = "hmac")]
+impl<D: Digest + BlockSizeUser> Write for SigningKey<D> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.0.update(s.as_bytes());
+        self.write(s.as_bytes());
         Ok(())
     }
 }
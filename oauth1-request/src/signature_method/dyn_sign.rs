@@ -0,0 +1,124 @@
+//! An object-safe version of `Sign` shared by the signature methods that dispatch to a signature
+//! method chosen at runtime.
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+use core::fmt::{self, Display};
+
+use crate::param::OAuthParamKind;
+
+use super::Sign;
+
+/// An object-safe version of `Sign`.
+pub(super) trait DynSign {
+    fn get_signature_method_name(&self) -> &'static str;
+    fn request_method(&mut self, method: &str);
+    fn uri(&mut self, uri: &dyn Display);
+    fn parameter(&mut self, key: &str, value: &dyn Display);
+    fn parameter_display_key(&mut self, key: &dyn Display, value: &dyn Display) -> fmt::Result;
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]);
+    fn oauth_parameter(&mut self, kind: OAuthParamKind, value: &dyn Display);
+    fn delimiter(&mut self);
+    #[cfg(feature = "alloc")]
+    fn end(self: Box<Self>) -> Result<String, fmt::Error>;
+    fn body_hash(&mut self, value: &dyn Display);
+    fn callback(&mut self, value: &dyn Display);
+    fn consumer_key(&mut self, value: &dyn Display);
+    fn nonce(&mut self, value: &dyn Display);
+    fn use_nonce(&self) -> bool;
+    fn signature_method(&mut self);
+    fn timestamp(&mut self, value: u64);
+    fn use_timestamp(&self) -> bool;
+    fn token(&mut self, value: &dyn Display);
+    fn verifier(&mut self, value: &dyn Display);
+    fn version(&mut self);
+}
+
+impl<S: Sign> DynSign for S {
+    fn get_signature_method_name(&self) -> &'static str {
+        Sign::get_signature_method_name(self)
+    }
+
+    fn request_method(&mut self, method: &str) {
+        Sign::request_method(self, method);
+    }
+
+    fn uri(&mut self, uri: &dyn Display) {
+        Sign::uri(self, uri);
+    }
+
+    fn parameter(&mut self, key: &str, value: &dyn Display) {
+        Sign::parameter(self, key, value);
+    }
+
+    fn parameter_display_key(&mut self, key: &dyn Display, value: &dyn Display) -> fmt::Result {
+        Sign::parameter_display_key(self, key, value)
+    }
+
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        Sign::parameter_bytes(self, key, value);
+    }
+
+    fn oauth_parameter(&mut self, kind: OAuthParamKind, value: &dyn Display) {
+        Sign::oauth_parameter(self, kind, value);
+    }
+
+    fn delimiter(&mut self) {
+        Sign::delimiter(self);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn end(self: Box<Self>) -> Result<String, fmt::Error> {
+        // Not using `ToString` because it panics on errors.
+        let mut ret = String::new();
+        write!(ret, "{}", Sign::end(*self)).map(|()| ret)
+    }
+
+    fn body_hash(&mut self, value: &dyn Display) {
+        Sign::body_hash(self, value);
+    }
+
+    fn callback(&mut self, value: &dyn Display) {
+        Sign::callback(self, value);
+    }
+
+    fn consumer_key(&mut self, value: &dyn Display) {
+        Sign::consumer_key(self, value);
+    }
+
+    fn nonce(&mut self, value: &dyn Display) {
+        Sign::nonce(self, value);
+    }
+
+    fn use_nonce(&self) -> bool {
+        Sign::use_nonce(self)
+    }
+
+    fn signature_method(&mut self) {
+        Sign::signature_method(self);
+    }
+
+    fn timestamp(&mut self, value: u64) {
+        Sign::timestamp(self, value);
+    }
+
+    fn use_timestamp(&self) -> bool {
+        Sign::use_timestamp(self)
+    }
+
+    fn token(&mut self, value: &dyn Display) {
+        Sign::token(self, value);
+    }
+
+    fn verifier(&mut self, value: &dyn Display) {
+        Sign::verifier(self, value);
+    }
+
+    fn version(&mut self) {
+        Sign::version(self);
+    }
+}
@@ -0,0 +1,240 @@
+//! The `ECDSA-SHA256` signature method using the NIST P-256 curve.
+//!
+//! This is not defined by the OAuth standard, but is used by some deployments that have moved from
+//! RSA to elliptic curve keys. The signature is the fixed-size concatenation of the `r` and `s`
+//! values (64 bytes, as in the `ES256` algorithm of JWS), encoded with Base64.
+//!
+//! The signatures are deterministic ([RFC 6979][rfc]), so signing the same request twice produces
+//! the same signature.
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc6979
+//!
+//! This module is only available when `ecdsa-p256-013` feature is activated.
+
+pub use p256_013::ecdsa::SigningKey;
+pub use p256_013::pkcs8::DecodePrivateKey;
+
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use p256_013::ecdsa::signature::DigestSigner;
+use p256_013::ecdsa::Signature;
+use sha2::Sha256;
+
+use crate::Secret;
+
+use super::digest_common::UpdateSign;
+use super::{Base64PercentEncodeDisplay, DigestStrength, Sign, SignatureMethod};
+
+/// The `ECDSA-SHA256` signature method using the NIST P-256 curve.
+#[derive(Clone)]
+pub struct EcdsaP256Sha256 {
+    key: SigningKey,
+}
+
+/// A type that signs a signature base string with the ECDSA P-256 SHA-256 signature algorithm.
+#[derive(Clone)]
+pub struct EcdsaP256Sha256Sign<K = SigningKey> {
+    inner: UpdateSign<Sha256>,
+    key: K,
+}
+
+/// A signature produced by an `EcdsaP256Sha256Sign`.
+pub struct EcdsaP256Sha256Signature {
+    inner: Result<Base64PercentEncodeDisplay<[u8; 64]>, fmt::Error>,
+}
+
+impl EcdsaP256Sha256 {
+    /// Creates a new `EcdsaP256Sha256` that signs a signature base string with the given private
+    /// key.
+    pub fn new(key: SigningKey) -> Self {
+        EcdsaP256Sha256 { key }
+    }
+}
+
+impl From<SigningKey> for EcdsaP256Sha256 {
+    fn from(key: SigningKey) -> Self {
+        EcdsaP256Sha256::new(key)
+    }
+}
+
+impl Debug for EcdsaP256Sha256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcdsaP256Sha256")
+            .field("key", &Secret(()))
+            .finish()
+    }
+}
+
+impl<K> Debug for EcdsaP256Sha256Sign<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcdsaP256Sha256Sign")
+            .field("inner", &self.inner)
+            .field("key", &Secret(()))
+            .finish()
+    }
+}
+
+impl SignatureMethod for EcdsaP256Sha256 {
+    type Sign = EcdsaP256Sha256Sign;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        EcdsaP256Sha256Sign {
+            inner: UpdateSign::new(Sha256::default()),
+            key: self.key,
+        }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        self.sign_with("", None)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha256
+    }
+}
+
+impl<'a> SignatureMethod for &'a EcdsaP256Sha256 {
+    type Sign = EcdsaP256Sha256Sign<&'a SigningKey>;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        EcdsaP256Sha256Sign {
+            inner: UpdateSign::new(Sha256::default()),
+            key: &self.key,
+        }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        self.sign_with("", None)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha256
+    }
+}
+
+impl<K: Borrow<SigningKey>> Sign for EcdsaP256Sha256Sign<K> {
+    type Signature = EcdsaP256Sha256Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        "ECDSA-SHA256"
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.inner.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.inner.parameter(key, value);
+    }
+
+    fn parameter_display_key<T: Display, V: Display>(&mut self, key: T, value: V) -> fmt::Result {
+        self.inner.parameter_display_key(key, value)
+    }
+
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.inner.parameter_bytes(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.inner.delimiter();
+    }
+
+    fn end(self) -> EcdsaP256Sha256Signature {
+        let key = self.key;
+        let inner = self.inner.finish().map(|sha256| {
+            let signature: Signature = key.borrow().sign_digest(sha256);
+            let mut bytes = [0; 64];
+            bytes.copy_from_slice(&signature.to_bytes());
+            Base64PercentEncodeDisplay(bytes)
+        });
+        EcdsaP256Sha256Signature { inner }
+    }
+}
+
+impl Display for EcdsaP256Sha256Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.fmt(f),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl super::Signature for EcdsaP256Sha256Signature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.write_to(dst),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::ToString;
+
+    use p256_013::ecdsa::signature::Verifier;
+    use p256_013::ecdsa::VerifyingKey;
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        // A key from the test vectors of RFC 6979 section A.2.5.
+        let key = SigningKey::from_slice(
+            &base64::decode("ya+p2EW6dRZrXCFXZ7HWk05Qw9s26JsSe4piKxIPZyE=").unwrap(),
+        )
+        .unwrap();
+        let verifying_key = VerifyingKey::from(&key);
+        let signature_method = EcdsaP256Sha256::new(key);
+
+        let sign = |mut sign: EcdsaP256Sha256Sign<&SigningKey>| {
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+            sign.parameter("file", "vacaction.jpg");
+            sign.delimiter();
+            sign.consumer_key("dpf43f3p2l4k3l03");
+            sign.delimiter();
+            sign.nonce("13917289812797014437");
+            sign.delimiter();
+            sign.signature_method();
+            sign.delimiter();
+            sign.timestamp(1196666512);
+            sign.delimiter();
+            sign.version();
+            sign.delimiter();
+            sign.parameter("size", "original");
+            sign.end().to_string()
+        };
+
+        let signature = sign((&signature_method).sign_with("", None));
+        assert_eq!(signature, sign((&signature_method).sign_with("", None)));
+
+        let signature = percent_encoding::percent_decode_str(&signature)
+            .decode_utf8()
+            .unwrap();
+        let signature = Signature::from_slice(&base64::decode(&*signature).unwrap()).unwrap();
+        let base_string = "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacaction.jpg\
+            %26oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3D13917289812797014437\
+            %26oauth_signature_method%3DECDSA-SHA256%26oauth_timestamp%3D1196666512\
+            %26oauth_version%3D1.0%26size%3Doriginal";
+        verifying_key
+            .verify(base_string.as_bytes(), &signature)
+            .unwrap();
+    }
+}
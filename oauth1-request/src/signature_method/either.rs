@@ -5,6 +5,37 @@ use super::*;
 /// A `SignatureMethod` implementation that behaves like `L` or `R` depending on `self`'s variant.
 ///
 /// This may be useful when you want to decide on a method to use at runtime.
+///
+/// The signature type is `Either<L::Sign::Signature, R::Sign::Signature>`, which implements
+/// [`Signature`] whenever both of the inner signature types do, so the two signature methods
+/// don't need to produce the same signature type and the output doesn't need to be boxed. Use the
+/// methods of `Either` like `map_left` and `either` to construct or inspect the inner signature
+/// method.
+///
+/// Note that `From<L>` and `From<R>` cannot be implemented for `Either` outside of the `either`
+/// crate, so wrap the signature method with `Either::Left` or `Either::Right` instead.
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate either;
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use either::Either;
+///
+/// fn signature_method(name: &str) -> Either<oauth::HmacSha1, oauth::Plaintext<String>> {
+///     if name == "PLAINTEXT" {
+///         Either::Right(oauth::PLAINTEXT)
+///     } else {
+///         Either::Left(oauth::HMAC_SHA1)
+///     }
+/// }
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let authorization = oauth::get("https://example.com/", &(), &token, signature_method("PLAINTEXT"));
+/// assert!(authorization.contains(r#"oauth_signature="secret%26secret""#));
+/// ```
 impl<L: SignatureMethod, R: SignatureMethod> SignatureMethod for Either<L, R> {
     type Sign = Either<L::Sign, R::Sign>;
 
@@ -14,6 +45,36 @@ impl<L: SignatureMethod, R: SignatureMethod> SignatureMethod for Either<L, R> {
             Either::Right(r) => Either::Right(r.sign_with(client_secret, token_secret)),
         }
     }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+        match self {
+            Either::Left(l) => Either::Left(l.sign_with_raw_key(signing_key)),
+            Either::Right(r) => Either::Right(r.sign_with_raw_key(signing_key)),
+        }
+    }
+
+    fn is_symmetric(&self) -> bool {
+        match *self {
+            Either::Left(ref l) => l.is_symmetric(),
+            Either::Right(ref r) => r.is_symmetric(),
+        }
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        match *self {
+            Either::Left(ref l) => l.digest_strength(),
+            Either::Right(ref r) => r.digest_strength(),
+        }
+    }
+}
+
+impl<O, L: Configure<O>, R: Configure<O>> Configure<O> for Either<L, R> {
+    fn configure(&mut self, option: O) {
+        match *self {
+            Either::Left(ref mut l) => l.configure(option),
+            Either::Right(ref mut r) => r.configure(option),
+        }
+    }
 }
 
 macro_rules! delegate {
@@ -53,6 +114,9 @@ impl<L: Sign, R: Sign> Sign for Either<L, R> {
         fn request_method(&mut self, method: &str);
         fn uri[T: Display](&mut self, uri: T);
         fn parameter[V: Display](&mut self, key: &str, value: V);
+        fn parameter_display_key[K: Display, V: Display](&mut self, key: K, value: V) -> fmt::Result;
+        fn parameter_bytes(&mut self, key: &str, value: &[u8]);
+        fn oauth_parameter[V: Display](&mut self, kind: OAuthParamKind, value: V);
         fn delimiter(&mut self);
     }
 
@@ -61,6 +125,7 @@ impl<L: Sign, R: Sign> Sign for Either<L, R> {
     }
 
     delegate! {
+        fn body_hash[V: Display](&mut self, value: V);
         fn callback[V: Display](&mut self, value: V);
         fn nonce[V: Display](&mut self, value: V);
         fn use_nonce(&self) -> bool;
@@ -72,3 +137,12 @@ impl<L: Sign, R: Sign> Sign for Either<L, R> {
         fn version(&mut self);
     }
 }
+
+impl<L: Signature, R: Signature> Signature for Either<L, R> {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match *self {
+            Either::Left(ref l) => l.write_to(dst),
+            Either::Right(ref r) => r.write_to(dst),
+        }
+    }
+}
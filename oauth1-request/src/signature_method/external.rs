@@ -0,0 +1,166 @@
+//! Signature methods whose signing keys are held outside of the process.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+
+use super::{
+    Base64PercentEncodeDisplay, BaseStringSign, SignBaseString, Signature, SignatureMethod,
+};
+
+/// Signing operations delegated to a key store outside of the process, like a hardware security
+/// module (e.g. via PKCS#11) or a cloud key management service.
+///
+/// The signature base string is assembled locally and then passed to [`sign`](Self::sign), which
+/// returns the raw signature bytes. Base64-encoding the signature is done by this crate.
+///
+/// Use [`External`] to turn the type into a `SignatureMethod`.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "std", doc = " ```")]
+#[cfg_attr(not(feature = "std"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// use oauth::signature_method::external::{External, ExternalSign};
+///
+/// /// A client of a (hypothetical) key management service.
+/// struct Kms {
+///     key_id: &'static str,
+/// }
+///
+/// impl ExternalSign for &Kms {
+///     type Error = std::io::Error;
+///
+///     fn get_signature_method_name(&self) -> &'static str {
+///         "RSA-SHA256"
+///     }
+///
+///     fn sign(self, base_string: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+///         // Sends `base_string` to the service and has it signed with the key.
+///         request_signature(self.key_id, base_string)
+///     }
+/// }
+/// # fn request_signature(_: &str, _: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+/// #     Ok(vec![0xDE, 0xAD, 0xBE, 0xEF])
+/// # }
+///
+/// let kms = Kms { key_id: "my-key" };
+/// let token = oauth::Token::from_parts("consumer", "", "token", "");
+/// let authorization = oauth::get("https://example.com/", &(), &token, External::new(&kms));
+/// assert!(authorization.contains(r#"oauth_signature="3q2%2B7w%3D%3D""#));
+/// ```
+pub trait ExternalSign {
+    /// The error type returned when the signing operation fails.
+    type Error;
+
+    /// Returns the `oauth_signature_method` string for the signature method.
+    fn get_signature_method_name(&self) -> &'static str;
+
+    /// Signs the signature base string and returns the raw (not Base64-encoded) signature.
+    fn sign(self, base_string: &[u8]) -> Result<Vec<u8>, Self::Error>;
+
+    /// Whether the signature method uses the `oauth_nonce` parameter.
+    ///
+    /// The default implementation returns `true`.
+    fn use_nonce(&self) -> bool {
+        true
+    }
+
+    /// Whether the signature method uses the `oauth_timestamp` parameter.
+    ///
+    /// The default implementation returns `true`.
+    fn use_timestamp(&self) -> bool {
+        true
+    }
+}
+
+/// A `SignatureMethod` that delegates the signing operation to an [`ExternalSign`].
+///
+/// The client and token secrets are ignored since the signing key is held by the `ExternalSign`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct External<S> {
+    inner: S,
+}
+
+/// A signature produced by an [`External`] signature method.
+///
+/// Formatting the signature fails if the `ExternalSign` has returned an error. Note that the
+/// serializers of this crate (and the functions using them like [`oauth::get`](crate::get)) panic
+/// in that case, so use [`into_result`](Self::into_result) with a `Sign` you drive yourself if you
+/// need to handle the error.
+#[derive(Clone, Debug)]
+pub struct ExternalSignature<E> {
+    inner: Result<Vec<u8>, E>,
+}
+
+impl<S: ExternalSign> External<S> {
+    /// Creates a new `External` that signs requests with `inner`.
+    pub fn new(inner: S) -> Self {
+        External { inner }
+    }
+
+    /// Unwraps the underlying `ExternalSign`.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: ExternalSign> SignatureMethod for External<S> {
+    type Sign = BaseStringSign<Self>;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        BaseStringSign::new(self)
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        BaseStringSign::new(self)
+    }
+}
+
+impl<S: ExternalSign> SignBaseString for External<S> {
+    type Signature = ExternalSignature<S::Error>;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn sign_base_string(self, base_string: &str) -> Self::Signature {
+        ExternalSignature {
+            inner: self.inner.sign(base_string.as_bytes()),
+        }
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+}
+
+impl<E> ExternalSignature<E> {
+    /// Returns the raw signature returned by the `ExternalSign`, or the error it has returned.
+    pub fn into_result(self) -> Result<Vec<u8>, E> {
+        self.inner
+    }
+}
+
+impl<E> Display for ExternalSignature<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref signature) => Base64PercentEncodeDisplay(signature).fmt(f),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl<E> Signature for ExternalSignature<E> {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref signature) => Base64PercentEncodeDisplay(signature).write_to(dst),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! The `HMAC` signature method generic over the hash function.
+//!
+//! This module is only available when `hmac` feature is activated.
+
+use core::fmt::{self, Debug, Display, Formatter, Write};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use digest::core_api::BlockSizeUser;
+use digest::{Digest, Output};
+use hmac012::{Mac, SimpleHmac};
+
+use super::digest_common::{SigningKey, UpdateSign};
+use super::{
+    write_signing_key, Base64PercentEncodeDisplay, DigestStrength, Sign, Signature, SignatureMethod,
+};
+
+/// An HMAC signature method using the hash function `D`.
+///
+/// The OAuth standard only defines `HMAC-SHA1` ([`HmacSha1`](super::HmacSha1)), but some servers
+/// accept HMAC with other hash functions, like `HMAC-SHA256`. This signature method works with any
+/// [RustCrypto hash function][hashes] and the `oauth_signature_method` name you specify.
+///
+/// [hashes]: https://github.com/RustCrypto/hashes
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::Hmac;
+/// use sha2::Sha256;
+///
+/// const HMAC_SHA256: Hmac<Sha256> = Hmac::new("HMAC-SHA256");
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let authorization = oauth::get("https://example.com/", &(), &token, HMAC_SHA256);
+/// assert!(authorization.contains(r#"oauth_signature_method="HMAC-SHA256""#));
+/// ```
+pub struct Hmac<D> {
+    name: &'static str,
+    hex: bool,
+    marker: PhantomData<D>,
+}
+
+/// A type that signs a signature base string with the HMAC algorithm using the hash function `D`.
+pub struct HmacSign<D: Digest + BlockSizeUser> {
+    name: &'static str,
+    inner: UpdateSign<SimpleHmac<D>>,
+    hex: bool,
+}
+
+/// A signature produced by an `HmacSign`.
+pub struct HmacSignature<D: Digest + BlockSizeUser> {
+    inner: Result<Base64PercentEncodeDisplay<Output<D>>, fmt::Error>,
+    hex: bool,
+}
+
+impl<D> Hmac<D> {
+    /// Creates a new `Hmac` that identifies itself with the `oauth_signature_method` value `name`.
+    pub const fn new(name: &'static str) -> Self {
+        Hmac {
+            name,
+            hex: false,
+            marker: PhantomData,
+        }
+    }
+
+    /// Makes the signature method emit the `oauth_signature` as lowercase hexadecimal digits
+    /// instead of Base64.
+    ///
+    /// This violates the OAuth standard and is only meant for providers that expect hex-encoded
+    /// signatures.
+    pub fn with_hex_output(mut self) -> Self {
+        self.hex = true;
+        self
+    }
+
+    /// Returns the `oauth_signature_method` value of the signature method.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<D> Clone for Hmac<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for Hmac<D> {}
+
+impl<D> Debug for Hmac<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hmac")
+            .field("name", &self.name)
+            .field("hex_output", &self.hex)
+            .finish()
+    }
+}
+
+impl<D> PartialEq for Hmac<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.hex == other.hex
+    }
+}
+
+impl<D> Eq for Hmac<D> {}
+
+impl<D> Hash for Hmac<D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.hex.hash(state);
+    }
+}
+
+impl<D: Digest + BlockSizeUser> SignatureMethod for Hmac<D> {
+    type Sign = HmacSign<D>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSign<D> {
+        let mut key = SigningKey::<D>::new();
+        write_signing_key(&mut key, client_secret, token_secret).unwrap();
+        HmacSign {
+            name: self.name,
+            inner: UpdateSign::new(key.into_mac()),
+            hex: self.hex,
+        }
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> HmacSign<D> {
+        let mut key = SigningKey::<D>::new();
+        key.write(signing_key.as_bytes());
+        HmacSign {
+            name: self.name,
+            inner: UpdateSign::new(key.into_mac()),
+            hex: self.hex,
+        }
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::from_output_len(<D as Digest>::output_size())
+    }
+}
+
+impl<D: Digest + BlockSizeUser + Clone> Clone for HmacSign<D> {
+    fn clone(&self) -> Self {
+        HmacSign {
+            name: self.name,
+            inner: self.inner.clone(),
+            hex: self.hex,
+        }
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Debug for HmacSign<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSign")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Sign for HmacSign<D> {
+    type Signature = HmacSignature<D>;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.inner.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.inner.parameter(key, value);
+    }
+
+    fn parameter_display_key<K: Display, V: Display>(&mut self, key: K, value: V) -> fmt::Result {
+        self.inner.parameter_display_key(key, value)
+    }
+
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.inner.parameter_bytes(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.inner.delimiter();
+    }
+
+    fn end(self) -> HmacSignature<D> {
+        HmacSignature {
+            inner: self
+                .inner
+                .finish()
+                .map(|hmac| Base64PercentEncodeDisplay(hmac.finalize().into_bytes())),
+            hex: self.hex,
+        }
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Display for HmacSignature<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), f),
+            Ok(ref inner) => inner.fmt(f),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Signature for HmacSignature<D> {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), dst),
+            Ok(ref inner) => inner.write_to(dst),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::{String, ToString};
+    use core::fmt::Write;
+
+    use sha2::Sha256;
+
+    use super::*;
+
+    fn sign<S: Sign>(mut sign: S) -> String {
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fexample.com%2F");
+        sign.parameter("foo", "bar");
+        let mut buf = String::new();
+        write!(buf, "{}", sign.end()).unwrap();
+        buf
+    }
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn same_as_hmac_sha1() {
+        let hmac = Hmac::<sha1::Sha1>::new("HMAC-SHA1");
+        let long_secret = "a".repeat(100);
+        for &(client, token) in &[("", None), ("a&b", Some("c")), (&*long_secret, Some(""))] {
+            assert_eq!(
+                sign(hmac.sign_with(client, token)),
+                sign(crate::signature_method::HMAC_SHA1.sign_with(client, token)),
+            );
+        }
+    }
+
+    #[test]
+    fn sha256() {
+        let mut mac = SimpleHmac::<Sha256>::new_from_slice(b"a%26b&c").unwrap();
+        Mac::update(&mut mac, b"GET&http%3A%2F%2Fexample.com%2F&foo%3Dbar");
+        let digest = mac.finalize().into_bytes();
+        let expected = base64::encode(digest);
+        let expected = crate::util::percent_encode(&expected).to_string();
+
+        let hmac = Hmac::<Sha256>::new("HMAC-SHA256");
+        assert_eq!(sign(hmac.sign_with("a&b", Some("c"))), expected);
+        assert_eq!(sign(hmac.sign_with_raw_key("a%26b&c")), expected);
+        assert_eq!(
+            hmac.sign_with("", None).get_signature_method_name(),
+            "HMAC-SHA256"
+        );
+
+        let mut hex = String::new();
+        for b in digest {
+            write!(hex, "{:02x}", b).unwrap();
+        }
+        assert_eq!(
+            sign(hmac.with_hex_output().sign_with("a&b", Some("c"))),
+            hex
+        );
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn conformance() {
+        use crate::signature_method::conformance::run_conformance;
+
+        run_conformance(
+            Hmac::<Sha256>::new("HMAC-SHA256"),
+            |base_string, key, signature| {
+                let mut mac = SimpleHmac::<Sha256>::new_from_slice(key.as_bytes()).unwrap();
+                Mac::update(&mut mac, base_string.as_bytes());
+                base64::encode(mac.finalize().into_bytes()) == signature
+            },
+        );
+    }
+}
@@ -6,57 +6,105 @@
 
 use core::fmt::{self, Debug, Display, Formatter, Write};
 
-use digest::core_api::BlockSizeUser;
-use digest::generic_array::sequence::GenericSequence;
-use digest::generic_array::GenericArray;
-use digest::OutputSizeUser;
-use hmac::{Hmac, Mac};
-use sha1::{Digest, Sha1};
+use digest::Output;
+use hmac012::{Hmac, Mac};
+use sha1::Sha1;
 
-use super::digest_common::{Base64PercentEncodeDisplay, UpdateSign};
-use super::{write_signing_key, Sign, SignatureMethod};
+use crate::util::percent_encode;
+use crate::Secret;
+
+use super::digest_common::{SigningKey, UpdateSign};
+use super::{
+    write_signing_key, Base64Display, Base64PercentEncodeDisplay, DigestStrength, Sign, Signature,
+    SignatureMethod,
+};
 
 /// The `HMAC-SHA1` signature method.
-#[derive(Clone, Copy, Default)]
+///
+/// The signature is encoded in Base64 as the OAuth standard requires, unless
+/// [`with_hex_output`](HmacSha1::with_hex_output) is called. The hexadecimal output violates the
+/// standard and is only meant for providers that expect hex-encoded signatures. The same applies
+/// to the `HmacSha1` of the other backends.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct HmacSha1 {
-    _priv: (),
+    hex: bool,
 }
 
 /// A type that signs a signature base string with the HMAC-SHA1 signature algorithm.
 #[derive(Clone, Debug)]
 pub struct HmacSha1Sign {
     inner: UpdateSign<Hmac<Sha1>>,
+    hex: bool,
+}
+
+/// An `HMAC-SHA1` key precomputed from the client and token secrets.
+///
+/// Creating an `HmacSha1Sign` from the key only copies the keyed HMAC state, skipping the
+/// percent-encoding of the secrets and the HMAC key schedule. This makes it cheaper to sign many
+/// requests with the same credentials.
+///
+/// `&HmacSha1Key` implements `SignatureMethod` so that it can be used with a
+/// [`Builder`](crate::Builder). Note that it ignores the secrets passed to
+/// [`sign_with`](SignatureMethod::sign_with) and always signs with the precomputed key.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::hmac_sha1::HmacSha1Key;
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let key = HmacSha1Key::new(token.client.secret(), Some(token.token.secret()));
+///
+/// let builder = oauth::Builder::with_token(token, &key);
+/// for i in 0..3 {
+///     let uri = format!("https://example.com/items/{}", i);
+///     let authorization = builder.get(&uri, &());
+/// #   let _ = authorization;
+/// }
+/// ```
+///
+/// When the token credentials are refreshed, [`set_token_secret`](HmacSha1Key::set_token_secret)
+/// swaps the token secret in place, reusing the percent-encoded client secret.
+#[derive(Clone)]
+pub struct HmacSha1Key {
+    mac: Hmac<Sha1>,
+    // The part of the signing key derived from the client secret, i.e. `client_secret&`.
+    // `None` if the key was created with `from_raw_key`.
+    client_key: Option<SigningKey<Sha1>>,
+    hex: bool,
 }
 
 /// A signature produced by an `HmacSha1Sign`.
 pub struct HmacSha1Signature {
-    inner: Base64PercentEncodeDisplay<GenericArray<u8, <Sha1 as OutputSizeUser>::OutputSize>>,
+    inner: Result<Base64PercentEncodeDisplay<Output<Sha1>>, fmt::Error>,
+    hex: bool,
 }
 
 /// The `HMAC-SHA1` signature method with a default configuration.
 pub const HMAC_SHA1: HmacSha1 = HmacSha1::new();
 
-#[derive(Clone)]
-enum SigningKey {
-    Key {
-        buf: GenericArray<u8, <Sha1 as BlockSizeUser>::BlockSize>,
-        pos: usize,
-    },
-    Digest(Sha1),
-}
-
 impl HmacSha1 {
     /// Creates a new `HmacSha1`.
     pub const fn new() -> Self {
-        HmacSha1 { _priv: () }
+        HmacSha1 { hex: false }
+    }
+
+    /// Makes the signature method emit the `oauth_signature` as lowercase hexadecimal digits
+    /// instead of Base64.
+    pub fn with_hex_output(mut self) -> Self {
+        self.hex = true;
+        self
     }
 }
 
 impl Debug for HmacSha1 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        #[derive(Debug)]
-        struct HmacSha1;
-        HmacSha1.fmt(f)
+        f.debug_struct("HmacSha1")
+            .field("hex_output", &self.hex)
+            .finish()
     }
 }
 
@@ -64,14 +112,111 @@ impl SignatureMethod for HmacSha1 {
     type Sign = HmacSha1Sign;
 
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSha1Sign {
-        let mut key = SigningKey::new();
-        write_signing_key(&mut key, client_secret, token_secret).unwrap();
+        HmacSha1Key::new(client_secret, token_secret).into_sign(self.hex)
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> HmacSha1Sign {
+        HmacSha1Key::from_raw_key(signing_key).into_sign(self.hex)
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
+impl HmacSha1Key {
+    /// Creates a new `HmacSha1Key` from `client_secret` and `token_secret`, applying
+    /// percent-encoding to them.
+    pub fn new(client_secret: &str, token_secret: Option<&str>) -> Self {
+        let mut client_key = SigningKey::<Sha1>::new();
+        write_signing_key(&mut client_key, client_secret, None).unwrap();
+        let mut ret = HmacSha1Key {
+            mac: client_key.clone().into_mac(),
+            client_key: Some(client_key),
+            hex: false,
+        };
+        if token_secret.is_some() {
+            ret.set_token_secret(token_secret);
+        }
+        ret
+    }
+
+    /// Creates a new `HmacSha1Key` from `signing_key` as-is.
+    ///
+    /// See [`SignatureMethod::sign_with_raw_key`] for the use case.
+    pub fn from_raw_key(signing_key: &str) -> Self {
+        let mut key = SigningKey::<Sha1>::new();
+        key.write(signing_key.as_bytes());
+        HmacSha1Key {
+            mac: key.into_mac(),
+            client_key: None,
+            hex: false,
+        }
+    }
+
+    /// Replaces the token secret of the key, keeping the client secret.
+    ///
+    /// This is cheaper than creating a new key with [`new`](HmacSha1Key::new) since the
+    /// percent-encoded client secret is reused, and is meant for long-lived keys whose token
+    /// credentials are refreshed from time to time.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the key has been created with [`from_raw_key`](HmacSha1Key::from_raw_key),
+    /// which does not tell apart the client and token secrets.
+    pub fn set_token_secret(&mut self, token_secret: Option<&str>) {
+        let mut key = self
+            .client_key
+            .clone()
+            .expect("`set_token_secret` called on a key created with `from_raw_key`");
+        if let Some(ts) = token_secret {
+            write!(key, "{}", percent_encode(ts)).unwrap();
+        }
+        self.mac = key.into_mac();
+    }
+
+    /// Makes the signatures emit the `oauth_signature` as lowercase hexadecimal digits, like
+    /// [`HmacSha1::with_hex_output`].
+    pub fn with_hex_output(mut self) -> Self {
+        self.hex = true;
+        self
+    }
+
+    /// Creates an `HmacSha1Sign` that signs a signature base string with the key.
+    pub fn sign(&self) -> HmacSha1Sign {
+        self.clone().into_sign(self.hex)
+    }
+
+    fn into_sign(self, hex: bool) -> HmacSha1Sign {
         HmacSha1Sign {
-            inner: UpdateSign(key.into_hmac()),
+            inner: UpdateSign::new(self.mac),
+            hex,
         }
     }
 }
 
+impl Debug for HmacSha1Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HmacSha1Key").field(&Secret(())).finish()
+    }
+}
+
+impl SignatureMethod for &HmacSha1Key {
+    type Sign = HmacSha1Sign;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> HmacSha1Sign {
+        self.sign()
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> HmacSha1Sign {
+        self.sign()
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
 impl Sign for HmacSha1Sign {
     type Signature = HmacSha1Signature;
 
@@ -91,67 +236,84 @@ impl Sign for HmacSha1Sign {
         self.inner.parameter(key, value);
     }
 
+    fn parameter_display_key<K: Display, V: Display>(&mut self, key: K, value: V) -> fmt::Result {
+        self.inner.parameter_display_key(key, value)
+    }
+
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.inner.parameter_bytes(key, value);
+    }
+
     fn delimiter(&mut self) {
         self.inner.delimiter();
     }
 
     fn end(self) -> HmacSha1Signature {
         HmacSha1Signature {
-            inner: Base64PercentEncodeDisplay(self.inner.0.finalize().into_bytes()),
+            inner: self
+                .inner
+                .finish()
+                .map(|hmac| Base64PercentEncodeDisplay(hmac.finalize().into_bytes())),
+            hex: self.hex,
         }
     }
 }
 
-impl Display for HmacSha1Signature {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
+impl HmacSha1Signature {
+    /// Returns the raw HMAC-SHA1 digest.
+    ///
+    /// Returns an error if a `Display` implementation has failed while feeding the signature base
+    /// string, in which case formatting the signature fails as well.
+    pub fn as_bytes(&self) -> Result<&[u8], fmt::Error> {
+        match self.inner {
+            Ok(ref inner) => Ok(inner.0.as_ref()),
+            Err(e) => Err(e),
+        }
     }
-}
 
-impl SigningKey {
-    fn new() -> Self {
-        SigningKey::Key {
-            buf: GenericArray::generate(|_| 0),
-            pos: 0,
-        }
+    /// Converts the signature into the raw HMAC-SHA1 digest.
+    ///
+    /// Returns an error under the same condition as [`as_bytes`](Self::as_bytes).
+    pub fn into_bytes(self) -> Result<[u8; 20], fmt::Error> {
+        let mut ret = [0; 20];
+        ret.copy_from_slice(self.as_bytes()?);
+        Ok(ret)
     }
 
-    fn write(&mut self, input: &[u8]) {
-        *self = match *self {
-            SigningKey::Key {
-                ref mut buf,
-                ref mut pos,
-            } => {
-                if input.len() > buf.len() - *pos {
-                    let mut digest = Sha1::default();
-                    digest.update(&buf[..*pos]);
-                    digest.update(input);
-                    SigningKey::Digest(digest)
-                } else {
-                    buf[*pos..(*pos + input.len())].copy_from_slice(input);
-                    *pos += input.len();
-                    return;
-                }
-            }
-            SigningKey::Digest(ref mut digest) => {
-                digest.update(input);
-                return;
-            }
-        };
+    /// Returns a `Display` that writes the base64-encoded signature without percent-encoding,
+    /// i.e. the `oauth_signature` value as decoded from the request.
+    ///
+    /// Formatting it fails under the same condition as [`as_bytes`](Self::as_bytes).
+    pub fn to_base64(&self) -> impl Display + '_ {
+        Base64Display(self.as_bytes())
+    }
+
+    /// Checks whether the signature matches `encoded`, the percent-encoded base64 value of an
+    /// `oauth_signature` parameter, in constant time.
+    ///
+    /// See [`verify_signature`](super::verify_signature) for details.
+    pub fn verify(&self, encoded: &str) -> bool {
+        super::verify_signature(self, encoded)
     }
+}
 
-    fn into_hmac(self) -> Hmac<Sha1> {
-        match self {
-            SigningKey::Key { ref buf, pos } => Hmac::new_from_slice(&buf[..pos]).unwrap(),
-            SigningKey::Digest(digest) => Hmac::new_from_slice(&digest.finalize()).unwrap(),
+impl Display for HmacSha1Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), f),
+            Ok(ref inner) => inner.fmt(f),
+            Err(e) => Err(e),
         }
     }
 }
 
-impl Write for SigningKey {
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        self.write(s.as_bytes());
-        Ok(())
+impl Signature for HmacSha1Signature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), dst),
+            Ok(ref inner) => inner.write_to(dst),
+            Err(e) => Err(e),
+        }
     }
 }
 
@@ -159,22 +321,182 @@ impl Write for SigningKey {
 mod tests {
     extern crate alloc;
 
+    use alloc::string::String;
     use alloc::vec::Vec;
+    use core::fmt::Write;
 
+    use digest::core_api::BlockSizeUser;
     use digest::generic_array::typenum::Unsigned;
 
     use super::*;
 
+    #[test]
+    fn deferred_format_error() {
+        struct Failing;
+
+        impl Display for Failing {
+            fn fmt(&self, _: &mut Formatter<'_>) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        let mut sign = HmacSha1::new().sign_with("", None);
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fexample.com%2F");
+        sign.parameter("foo", Failing);
+
+        let mut buf = String::new();
+        assert!(write!(buf, "{}", sign.end()).is_err());
+    }
+
+    #[test]
+    fn hex_output() {
+        let mut sign = HmacSha1::new().with_hex_output().sign_with("a", Some("b"));
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fexample.com%2F");
+        sign.parameter("foo", "bar");
+        let signature = sign.end();
+
+        let mut buf = String::new();
+        write!(buf, "{}", signature).unwrap();
+        assert_eq!(buf, "3d7144338e968d80f989e445785234a87165cdad");
+        buf.clear();
+        signature.write_to(&mut buf).unwrap();
+        assert_eq!(buf, "3d7144338e968d80f989e445785234a87165cdad");
+        assert!(signature.verify("3d7144338e968d80f989e445785234a87165cdad"));
+
+        let mut sign = HmacSha1Key::new("a", Some("b")).with_hex_output().sign();
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fexample.com%2F");
+        sign.parameter("foo", "bar");
+        buf.clear();
+        write!(buf, "{}", sign.end()).unwrap();
+        assert_eq!(buf, "3d7144338e968d80f989e445785234a87165cdad");
+    }
+
+    #[test]
+    fn bytes() {
+        let mut sign = HmacSha1::new().sign_with("a", Some("b"));
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fexample.com%2F");
+        sign.parameter("foo", "bar");
+        let signature = sign.end();
+
+        let mut buf = String::new();
+        write!(buf, "{}", signature.to_base64()).unwrap();
+        assert_eq!(buf, "PXFEM46WjYD5ieRFeFI0qHFlza0=");
+        buf.clear();
+        write!(buf, "{}", signature).unwrap();
+        assert_eq!(buf, "PXFEM46WjYD5ieRFeFI0qHFlza0%3D");
+        assert!(signature.verify("PXFEM46WjYD5ieRFeFI0qHFlza0%3D"));
+        assert!(!signature.verify("PXFEM46WjYD5ieRFeFI0qHFlza0="));
+        assert!(!signature.verify("PXFEM46WjYD5ieRFeFI0qHFlza0%3D%3D"));
+        assert!(!signature.verify(""));
+        assert_eq!(
+            signature.into_bytes().unwrap(),
+            *b"\x3d\x71\x44\x33\x8e\x96\x8d\x80\xf9\x89\xe4\x45\x78\x52\x34\xa8\x71\x65\xcd\xad",
+        );
+    }
+
+    #[test]
+    fn parameter_bytes() {
+        let sign = |f: &dyn Fn(&mut HmacSha1Sign)| {
+            let mut sign = HmacSha1::new().sign_with("a", Some("b"));
+            sign.request_method("POST");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            f(&mut sign);
+            let mut buf = String::new();
+            write!(buf, "{}", sign.end()).unwrap();
+            buf
+        };
+
+        assert_eq!(
+            sign(&|sign| sign.parameter_bytes("data", b"\x00 a/\xFF")),
+            sign(&|sign| sign.parameter("data", "%2500%2520a%252F%25FF")),
+        );
+    }
+
+    #[test]
+    fn raw_signing_key() {
+        let sign = |sign: HmacSha1Sign| {
+            let mut sign = sign;
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.parameter("foo", "bar");
+            let mut buf = String::new();
+            write!(buf, "{}", sign.end()).unwrap();
+            buf
+        };
+
+        assert_eq!(
+            sign(HmacSha1::new().sign_with_raw_key("a%26b&c")),
+            sign(HmacSha1::new().sign_with("a&b", Some("c"))),
+        );
+        assert_ne!(
+            sign(HmacSha1::new().sign_with_raw_key("a&b&c")),
+            sign(HmacSha1::new().sign_with("a&b", Some("c"))),
+        );
+    }
+
+    #[test]
+    fn precomputed_key() {
+        let sign = |mut sign: HmacSha1Sign| {
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.parameter("foo", "bar");
+            let mut buf = String::new();
+            write!(buf, "{}", sign.end()).unwrap();
+            buf
+        };
+
+        let key = HmacSha1Key::new("a&b", Some("c"));
+        let expected = sign(HmacSha1::new().sign_with("a&b", Some("c")));
+        assert_eq!(sign(key.sign()), expected);
+        assert_eq!(sign(key.sign()), expected);
+        assert_eq!(sign((&key).sign_with("x", None)), expected);
+        assert_eq!(sign(HmacSha1Key::from_raw_key("a%26b&c").sign()), expected,);
+    }
+
+    #[test]
+    fn set_token_secret() {
+        let sign = |mut sign: HmacSha1Sign| {
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.parameter("foo", "bar");
+            let mut buf = String::new();
+            write!(buf, "{}", sign.end()).unwrap();
+            buf
+        };
+
+        let mut key = HmacSha1Key::new("a&b", Some("c"));
+        key.set_token_secret(Some("d/e"));
+        assert_eq!(
+            sign(key.sign()),
+            sign(HmacSha1Key::new("a&b", Some("d/e")).sign())
+        );
+        key.set_token_secret(None);
+        assert_eq!(sign(key.sign()), sign(HmacSha1Key::new("a&b", None).sign()));
+
+        // Long enough for the signing key to be hashed.
+        let long = "x".repeat(100);
+        let mut key = HmacSha1Key::new(&long, None);
+        key.set_token_secret(Some(&long));
+        assert_eq!(
+            sign(key.sign()),
+            sign(HmacSha1Key::new(&long, Some(&long)).sign())
+        );
+    }
+
     #[test]
     fn signing_key() {
-        let mut sk = SigningKey::new();
+        let mut sk = SigningKey::<Sha1>::new();
         let mut k = Vec::new();
 
         for _ in 0..=<Sha1 as BlockSizeUser>::BlockSize::to_usize() + 1 {
             sk.write(&[1]);
             k.extend(&[1]);
 
-            let mut skm = sk.clone().into_hmac();
+            let mut skm: Hmac<Sha1> = sk.clone().into_mac();
             let mut m = Hmac::<Sha1>::new_from_slice(&k).unwrap();
             skm.update(b"test");
             m.update(b"test");
@@ -0,0 +1,113 @@
+//! A signature method for debugging that outputs the signature base string as the signature.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::string::String;
+
+use super::{BaseStringSign, Configure, DigestStrength, Sign, SignBaseString, SignatureMethod};
+
+/// A `SignatureMethod` that "signs" a request with the signature base string itself instead of
+/// a MAC or digital signature.
+///
+/// This is meant for debugging signature mismatches: the `oauth_signature` value of the output is
+/// the complete signature base string ([RFC 5849 section 3.4.1.][rfc]), which you can compare
+/// against the one a server reports in its error response. The `oauth_signature_method` value
+/// and the use of `oauth_nonce` and `oauth_timestamp` are taken from the wrapped signature
+/// method, so the base string is the same as the one the wrapped method would sign.
+///
+/// Needless to say, requests signed with this method are rejected by servers.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::num::NonZeroU64;
+///
+/// use oauth::signature_method::Identity;
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let mut builder = oauth::Builder::with_token(token, Identity::new(oauth::HMAC_SHA1));
+/// builder.nonce("nonce").timestamp(NonZeroU64::new(9999999999));
+/// let request = oauth::ParameterList::new([("q", "a b")]);
+/// let authorization = builder.get("https://example.com/", &request);
+///
+/// assert!(authorization.ends_with(
+///     "oauth_signature=\"GET&https%3A%2F%2Fexample.com%2F&\
+///      oauth_consumer_key%3Dconsumer%26oauth_nonce%3Dnonce%26\
+///      oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D9999999999%26\
+///      oauth_token%3Dtoken%26q%3Da%2520b\"",
+/// ));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identity<SM> {
+    inner: SM,
+}
+
+/// A `SignBaseString` that returns the signature base string as is, with the
+/// `oauth_signature_method` name of the wrapped `Sign`.
+#[derive(Clone, Debug)]
+pub struct IdentitySign<S> {
+    inner: S,
+}
+
+impl<SM: SignatureMethod> Identity<SM> {
+    /// Creates a new `Identity` that mimics `inner`.
+    pub fn new(inner: SM) -> Self {
+        Identity { inner }
+    }
+
+    /// Unwraps the underlying signature method.
+    pub fn into_inner(self) -> SM {
+        self.inner
+    }
+}
+
+impl<SM: SignatureMethod> SignatureMethod for Identity<SM> {
+    type Sign = BaseStringSign<IdentitySign<SM::Sign>>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        BaseStringSign::new(IdentitySign {
+            inner: self.inner.sign_with(client_secret, token_secret),
+        })
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+        BaseStringSign::new(IdentitySign {
+            inner: self.inner.sign_with_raw_key(signing_key),
+        })
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::None
+    }
+}
+
+impl<SM: Configure<O>, O> Configure<O> for Identity<SM> {
+    fn configure(&mut self, option: O) {
+        self.inner.configure(option);
+    }
+}
+
+impl<S: Sign> SignBaseString for IdentitySign<S> {
+    type Signature = String;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn sign_base_string(self, base_string: &str) -> String {
+        base_string.into()
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+}
@@ -0,0 +1,204 @@
+//! A signature method adapter that post-processes the parameters before signing.
+//!
+//! This module is only available when `alloc` feature is activated.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+
+use percent_encoding::percent_decode_str;
+
+use crate::util::PercentEncode;
+
+use super::{Configure, DigestStrength, Sign, SignatureMethod};
+
+/// A `SignatureMethod` that passes the parameters of the signature base string to a hook before
+/// signing them with the underlying signature method.
+///
+/// The hook receives the parameters (including the `oauth_*` ones except for `oauth_signature`)
+/// as a list of key-value pairs in dictionary order. The keys and values are percent-encoded as
+/// in the request ([RFC 5849 section 3.6.][rfc]). The hook may modify, add or remove pairs, and
+/// the list is sorted again before signing, so added pairs need not be inserted at the right
+/// position.
+///
+/// This is meant for last-mile fixes to match a server that deviates from the standard in how it
+/// reconstructs the signature base string. The hook only affects the signature and not the
+/// parameters written to the output, so modifying the `oauth_*` parameters results in
+/// a signature that does not match the request.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.6
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::num::NonZeroU64;
+///
+/// let request = oauth::ParameterList::new([("status", "hello")]);
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+/// builder.nonce("nonce").timestamp(NonZeroU64::new(9999999999));
+///
+/// // The server signs the `status` parameter in upper case.
+/// let mut builder = builder.map_params(|params: &mut Vec<(String, String)>| {
+///     for (key, value) in params.iter_mut() {
+///         if key == "status" {
+///             value.make_ascii_uppercase();
+///         }
+///     }
+/// });
+/// let authorization = builder.post("https://example.com/", &request);
+///
+/// let mut expected = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+/// expected.nonce("nonce").timestamp(NonZeroU64::new(9999999999));
+/// let request = oauth::ParameterList::new([("status", "HELLO")]);
+/// assert_eq!(authorization, expected.post("https://example.com/", &request));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MapParams<SM, F> {
+    inner: SM,
+    f: F,
+}
+
+/// A `Sign` that passes the parameters to a hook before signing them with the underlying `Sign`.
+#[derive(Clone, Debug)]
+pub struct MapParamsSign<S, F> {
+    inner: S,
+    f: F,
+    params: Vec<(String, String)>,
+    error: bool,
+}
+
+struct Error;
+
+impl<SM, F> MapParams<SM, F>
+where
+    SM: SignatureMethod,
+    F: FnMut(&mut Vec<(String, String)>),
+{
+    /// Creates a new `MapParams` that passes the parameters to `f` before signing them with
+    /// `inner`.
+    pub fn new(inner: SM, f: F) -> Self {
+        MapParams { inner, f }
+    }
+
+    /// Unwraps the underlying signature method.
+    pub fn into_inner(self) -> SM {
+        self.inner
+    }
+}
+
+impl<SM, F> SignatureMethod for MapParams<SM, F>
+where
+    SM: SignatureMethod,
+    F: FnMut(&mut Vec<(String, String)>),
+{
+    type Sign = MapParamsSign<SM::Sign, F>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        MapParamsSign::new(self.inner.sign_with(client_secret, token_secret), self.f)
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+        MapParamsSign::new(self.inner.sign_with_raw_key(signing_key), self.f)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.inner.is_symmetric()
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        self.inner.digest_strength()
+    }
+}
+
+impl<SM: Configure<O>, F, O> Configure<O> for MapParams<SM, F> {
+    fn configure(&mut self, option: O) {
+        self.inner.configure(option);
+    }
+}
+
+impl<S: Sign, F> MapParamsSign<S, F> {
+    fn new(inner: S, f: F) -> Self {
+        MapParamsSign {
+            inner,
+            f,
+            params: Vec::new(),
+            error: false,
+        }
+    }
+}
+
+impl<S, F> Sign for MapParamsSign<S, F>
+where
+    S: Sign,
+    F: FnMut(&mut Vec<(String, String)>),
+{
+    type Signature = S::Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.inner.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        let mut buf = String::new();
+        if write!(buf, "{}", value).is_err() {
+            self.error = true;
+            return;
+        }
+        // The arguments are in the form of the signature base string, i.e. percent-encoded twice.
+        let key = percent_decode_str(key).decode_utf8_lossy().into_owned();
+        let value = percent_decode_str(&buf).decode_utf8_lossy().into_owned();
+        self.params.push((key, value));
+    }
+
+    fn delimiter(&mut self) {}
+
+    fn end(mut self) -> S::Signature {
+        if self.error {
+            // Let the underlying `Sign` report the error through its signature.
+            self.inner.parameter("", Error);
+            return self.inner.end();
+        }
+
+        (self.f)(&mut self.params);
+        self.params.sort();
+
+        let mut key_buf = String::new();
+        for (i, (key, value)) in self.params.iter().enumerate() {
+            if i > 0 {
+                self.inner.delimiter();
+            }
+            key_buf.clear();
+            write!(key_buf, "{}", PercentEncode(key)).unwrap();
+            self.inner.parameter(&key_buf, PercentEncode(value));
+        }
+
+        self.inner.end()
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, _: &mut Formatter<'_>) -> fmt::Result {
+        Err(fmt::Error)
+    }
+}
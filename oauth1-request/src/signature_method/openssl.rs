@@ -0,0 +1,702 @@
+//! The `HMAC-SHA1`, `RSA-SHA1` and `RSA-SHA256` signature methods backed by the [`openssl`] crate.
+//!
+//! [`openssl`]: https://docs.rs/openssl/0.10
+//!
+//! The signature methods in this module produce the same signatures as the RustCrypto-based ones
+//! provided by the `hmac-sha1`, `rsa-sha1-06` and `rsa-sha256-06` features, but delegate the
+//! cryptographic operations to OpenSSL. This is useful when a crypto policy mandates the use of
+//! OpenSSL, or for the performance of signing with large RSA keys.
+//!
+//! Unlike the other implementations, these buffer the whole signature base string in memory before
+//! signing it, so they are not well suited for requests with very large parameter values.
+//!
+//! If OpenSSL fails to sign the signature base string, formatting the signature fails and the
+//! `as_bytes` method of the signature returns the error as [`SignError::OpenSsl`].
+//!
+//! This module is only available when `openssl` feature is activated.
+
+extern crate alloc;
+
+pub use openssl::error::ErrorStack;
+pub use openssl::pkey::{PKey, PKeyRef, Private};
+pub use openssl::rsa::Rsa;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use openssl::hash::MessageDigest;
+use openssl::sha::Sha1;
+use openssl::sign::Signer;
+
+use super::sha1_key::Sha1Key;
+use super::Base64Display;
+use super::{Base64PercentEncodeDisplay, DigestStrength, Sign, Signature, SignatureMethod};
+
+doc_auto_cfg! {
+    /// The `HMAC-SHA1` signature method backed by OpenSSL.
+    #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub struct HmacSha1 {
+        hex: bool,
+    }
+
+    /// A type that signs a signature base string with the HMAC-SHA1 signature algorithm.
+    #[derive(Clone, Debug)]
+    pub struct HmacSha1Sign {
+        base_string: BaseString,
+        key: Result<PKey<Private>, ErrorStack>,
+        hex: bool,
+    }
+
+    /// A signature produced by an `HmacSha1Sign`.
+    pub struct HmacSha1Signature {
+        inner: Result<Base64PercentEncodeDisplay<Vec<u8>>, SignError>,
+        hex: bool,
+    }
+
+    /// The `RSA-SHA1` signature method backed by OpenSSL.
+    #[derive(Clone, Debug)]
+    pub struct RsaSha1 {
+        key: PKey<Private>,
+    }
+
+    /// A type that signs a signature base string with the RSA-SHA1 signature algorithm.
+    #[derive(Clone, Debug)]
+    pub struct RsaSha1Sign<K = PKey<Private>> {
+        base_string: BaseString,
+        key: K,
+    }
+
+    /// A signature produced by an `RsaSha1Sign`.
+    pub struct RsaSha1Signature {
+        inner: Result<Base64PercentEncodeDisplay<Vec<u8>>, SignError>,
+    }
+}
+
+/// The `RSA-SHA256` signature method backed by OpenSSL.
+#[derive(Clone, Debug)]
+pub struct RsaSha256 {
+    key: PKey<Private>,
+}
+
+/// A type that signs a signature base string with the RSA-SHA256 signature algorithm.
+#[derive(Clone, Debug)]
+pub struct RsaSha256Sign<K = PKey<Private>> {
+    base_string: BaseString,
+    key: K,
+}
+
+/// A signature produced by an `RsaSha256Sign`.
+pub struct RsaSha256Signature {
+    inner: Result<Base64PercentEncodeDisplay<Vec<u8>>, SignError>,
+}
+
+/// An error that occurred while producing a signature.
+#[derive(Clone, Debug)]
+pub enum SignError {
+    /// A `Display` implementation returned an error while feeding the signature base string.
+    Format(fmt::Error),
+    /// OpenSSL returned an error.
+    OpenSsl(ErrorStack),
+}
+
+/// The signature base string accumulated until the signing process finishes.
+///
+/// `openssl::sign::Signer` borrows the key it signs with, so the signature base string is
+/// buffered and fed to a `Signer` at once on `end`.
+#[derive(Clone, Debug)]
+struct BaseString {
+    buf: String,
+    // Errors from `Display` implementations are deferred until the signature is formatted.
+    result: fmt::Result,
+}
+
+doc_auto_cfg! {
+    /// The OpenSSL-backed `HMAC-SHA1` signature method with a default configuration.
+    pub const HMAC_SHA1: HmacSha1 = HmacSha1::new();
+}
+
+impl HmacSha1 {
+    /// Creates a new `HmacSha1`.
+    pub const fn new() -> Self {
+        HmacSha1 { hex: false }
+    }
+
+    /// Makes the signature method emit the `oauth_signature` as lowercase hexadecimal digits
+    /// instead of Base64.
+    ///
+    /// See [`hmac_sha1::HmacSha1`](super::hmac_sha1::HmacSha1) for the caveat.
+    pub fn with_hex_output(mut self) -> Self {
+        self.hex = true;
+        self
+    }
+}
+
+impl Debug for HmacSha1 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSha1")
+            .field("hex_output", &self.hex)
+            .finish()
+    }
+}
+
+impl SignatureMethod for HmacSha1 {
+    type Sign = HmacSha1Sign;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSha1Sign {
+        let key = Sha1Key::<Sha1>::with_signing_key(client_secret, token_secret, PKey::hmac);
+        HmacSha1Sign {
+            base_string: BaseString::new(),
+            key,
+            hex: self.hex,
+        }
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> HmacSha1Sign {
+        HmacSha1Sign {
+            base_string: BaseString::new(),
+            key: PKey::hmac(signing_key.as_bytes()),
+            hex: self.hex,
+        }
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
+impl Sign for HmacSha1Sign {
+    type Signature = HmacSha1Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        "HMAC-SHA1"
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.base_string.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.base_string.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.base_string.parameter(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.base_string.delimiter();
+    }
+
+    fn end(self) -> HmacSha1Signature {
+        let base_string = self.base_string;
+        let inner = self
+            .key
+            .map_err(SignError::OpenSsl)
+            .and_then(|key| base_string.sign(MessageDigest::sha1(), &key));
+        HmacSha1Signature {
+            inner,
+            hex: self.hex,
+        }
+    }
+}
+
+impl HmacSha1Signature {
+    /// Returns the raw HMAC-SHA1 digest.
+    ///
+    /// Returns an error if a `Display` implementation has failed while feeding the signature base
+    /// string or OpenSSL has failed to sign it, in which case formatting the signature fails as
+    /// well.
+    pub fn as_bytes(&self) -> Result<&[u8], SignError> {
+        as_bytes(&self.inner)
+    }
+
+    /// Converts the signature into the raw HMAC-SHA1 digest.
+    ///
+    /// Returns an error under the same condition as [`as_bytes`](Self::as_bytes).
+    pub fn into_bytes(self) -> Result<[u8; 20], SignError> {
+        let mut ret = [0; 20];
+        ret.copy_from_slice(self.as_bytes()?);
+        Ok(ret)
+    }
+
+    /// Returns a `Display` that writes the base64-encoded signature without percent-encoding,
+    /// i.e. the `oauth_signature` value as decoded from the request.
+    ///
+    /// Formatting it fails under the same condition as [`as_bytes`](Self::as_bytes).
+    pub fn to_base64(&self) -> impl Display + '_ {
+        Base64Display(self.as_bytes().map_err(|_| fmt::Error))
+    }
+
+    /// Checks whether the signature matches `encoded`, the percent-encoded base64 value of an
+    /// `oauth_signature` parameter, in constant time.
+    ///
+    /// See [`verify_signature`](super::verify_signature) for details.
+    pub fn verify(&self, encoded: &str) -> bool {
+        super::verify_signature(self, encoded)
+    }
+}
+
+impl Display for HmacSha1Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), f),
+            Ok(ref inner) => inner.fmt(f),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl Signature for HmacSha1Signature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), dst),
+            Ok(ref inner) => inner.write_to(dst),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl RsaSha1 {
+    /// Creates a new `RsaSha1` that signs a signature base string with the given RSA private key.
+    ///
+    /// Returns an error if OpenSSL fails to wrap the key in a `PKey`.
+    pub fn new(key: Rsa<Private>) -> Result<Self, ErrorStack> {
+        PKey::from_rsa(key).map(|key| RsaSha1 { key })
+    }
+}
+
+impl SignatureMethod for RsaSha1 {
+    type Sign = RsaSha1Sign;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        RsaSha1Sign {
+            base_string: BaseString::new(),
+            key: self.key,
+        }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        self.sign_with("", None)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
+impl<'a> SignatureMethod for &'a RsaSha1 {
+    type Sign = RsaSha1Sign<&'a PKeyRef<Private>>;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        RsaSha1Sign {
+            base_string: BaseString::new(),
+            key: &self.key,
+        }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        self.sign_with("", None)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
+impl<K: Borrow<PKeyRef<Private>>> Sign for RsaSha1Sign<K> {
+    type Signature = RsaSha1Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        "RSA-SHA1"
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.base_string.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.base_string.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.base_string.parameter(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.base_string.delimiter();
+    }
+
+    fn end(self) -> RsaSha1Signature {
+        RsaSha1Signature {
+            inner: self
+                .base_string
+                .sign(MessageDigest::sha1(), self.key.borrow()),
+        }
+    }
+}
+
+impl RsaSha1Signature {
+    /// Returns the raw RSA-SHA1 signature.
+    ///
+    /// Returns an error if a `Display` implementation has failed while feeding the signature base
+    /// string or OpenSSL has failed to sign it, in which case formatting the signature fails as
+    /// well.
+    pub fn as_bytes(&self) -> Result<&[u8], SignError> {
+        as_bytes(&self.inner)
+    }
+}
+
+impl Display for RsaSha1Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.fmt(f),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl Signature for RsaSha1Signature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.write_to(dst),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl RsaSha256 {
+    /// Creates a new `RsaSha256` that signs a signature base string with the given RSA private key.
+    ///
+    /// Returns an error if OpenSSL fails to wrap the key in a `PKey`.
+    pub fn new(key: Rsa<Private>) -> Result<Self, ErrorStack> {
+        PKey::from_rsa(key).map(|key| RsaSha256 { key })
+    }
+}
+
+impl SignatureMethod for RsaSha256 {
+    type Sign = RsaSha256Sign;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        RsaSha256Sign {
+            base_string: BaseString::new(),
+            key: self.key,
+        }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        self.sign_with("", None)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha256
+    }
+}
+
+impl<'a> SignatureMethod for &'a RsaSha256 {
+    type Sign = RsaSha256Sign<&'a PKeyRef<Private>>;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        RsaSha256Sign {
+            base_string: BaseString::new(),
+            key: &self.key,
+        }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+        self.sign_with("", None)
+    }
+
+    fn is_symmetric(&self) -> bool {
+        false
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha256
+    }
+}
+
+impl<K: Borrow<PKeyRef<Private>>> Sign for RsaSha256Sign<K> {
+    type Signature = RsaSha256Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        "RSA-SHA256"
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.base_string.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.base_string.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.base_string.parameter(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.base_string.delimiter();
+    }
+
+    fn end(self) -> RsaSha256Signature {
+        RsaSha256Signature {
+            inner: self
+                .base_string
+                .sign(MessageDigest::sha256(), self.key.borrow()),
+        }
+    }
+}
+
+impl RsaSha256Signature {
+    /// Returns the raw RSA-SHA256 signature.
+    ///
+    /// Returns an error if a `Display` implementation has failed while feeding the signature base
+    /// string or OpenSSL has failed to sign it, in which case formatting the signature fails as
+    /// well.
+    pub fn as_bytes(&self) -> Result<&[u8], SignError> {
+        as_bytes(&self.inner)
+    }
+}
+
+impl Display for RsaSha256Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.fmt(f),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl Signature for RsaSha256Signature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) => inner.write_to(dst),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl Display for SignError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            SignError::Format(ref e) => Display::fmt(e, f),
+            SignError::OpenSsl(ref e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            SignError::Format(ref e) => Some(e),
+            SignError::OpenSsl(ref e) => Some(e),
+        }
+    }
+}
+
+impl From<fmt::Error> for SignError {
+    fn from(e: fmt::Error) -> Self {
+        SignError::Format(e)
+    }
+}
+
+impl From<ErrorStack> for SignError {
+    fn from(e: ErrorStack) -> Self {
+        SignError::OpenSsl(e)
+    }
+}
+
+fn as_bytes(
+    inner: &Result<Base64PercentEncodeDisplay<Vec<u8>>, SignError>,
+) -> Result<&[u8], SignError> {
+    match *inner {
+        Ok(ref inner) => Ok(inner.0.as_ref()),
+        Err(ref e) => Err(e.clone()),
+    }
+}
+
+impl BaseString {
+    fn new() -> Self {
+        BaseString {
+            buf: String::new(),
+            result: Ok(()),
+        }
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.buf.push_str(method);
+        self.buf.push('&');
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        let result = write!(self.buf, "{}&", uri);
+        self.result = self.result.and(result);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        let result = write!(self.buf, "{}%3D{}", key, value);
+        self.result = self.result.and(result);
+    }
+
+    fn delimiter(&mut self) {
+        self.buf.push_str("%26");
+    }
+
+    fn sign(
+        self,
+        digest: MessageDigest,
+        key: &PKeyRef<Private>,
+    ) -> Result<Base64PercentEncodeDisplay<Vec<u8>>, SignError> {
+        self.result?;
+        let mut signer = Signer::new(digest, key)?;
+        signer.update(self.buf.as_bytes())?;
+        Ok(Base64PercentEncodeDisplay(signer.sign_to_vec()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use openssl::bn::BigNum;
+
+    use crate::util::percent_encode;
+
+    use super::*;
+
+    #[test]
+    fn hmac_sha1() {
+        // Test case from <https://oauth.net/core/1.0a/#sig_base_example>.
+        let mut sign = HMAC_SHA1.sign_with("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+        sign.parameter("file", "vacation.jpg");
+        sign.delimiter();
+        sign.consumer_key("dpf43f3p2l4k3l03");
+        sign.delimiter();
+        sign.nonce("kllo9940pd9333jh");
+        sign.delimiter();
+        sign.signature_method();
+        sign.delimiter();
+        sign.timestamp(1191242096);
+        sign.delimiter();
+        sign.token("nnch734d00sl2jdk");
+        sign.delimiter();
+        sign.version();
+        sign.delimiter();
+        sign.parameter("size", "original");
+
+        let expected = percent_encode("tR3+Ty81lMeYAr/Fid0kMTYa/WM=").to_string();
+        assert_eq!(sign.end().to_string(), expected);
+    }
+
+    #[test]
+    fn rsa_sha1() {
+        // Test case from <https://wiki.oauth.net/w/page/12238556/TestCases>.
+
+        let der =
+            "MIICdgIBADANBgkqhkiG9w0BAQEFAASCAmAwggJcAgEAAoGBALRiMLAh9iimur8VA7qVvdqxevEuUkW4K+2KdMXmnQbG9Aa7k7eBjK1S+0LYmVjPKlJGNXHDGuy5Fw/d7rjVJ0BLB+ubPK8iA/Tw3hLQgXMRRGRXXCn8ikfuQfjUS1uZSatdLB81mydBETlJhI6GH4twrbDJCR2Bwy/XWXgqgGRzAgMBAAECgYBYWVtleUzavkbrPjy0T5FMou8HX9u2AC2ry8vD/l7cqedtwMPp9k7TubgNFo+NGvKsl2ynyprOZR1xjQ7WgrgVB+mmuScOM/5HVceFuGRDhYTCObE+y1kxRloNYXnx3ei1zbeYLPCHdhxRYW7T0qcynNmwrn05/KO2RLjgQNalsQJBANeA3Q4Nugqy4QBUCEC09SqylT2K9FrrItqL2QKc9v0ZzO2uwllCbg0dwpVuYPYXYvikNHHg+aCWF+VXsb9rpPsCQQDWR9TT4ORdzoj+NccnqkMsDmzt0EfNaAOwHOmVJ2RVBspPcxt5iN4HI7HNeG6U5YsFBb+/GZbgfBT3kpNGWPTpAkBI+gFhjfJvRw38n3g/+UeAkwMI2TJQS4n8+hid0uus3/zOjDySH3XHCUnocn1xOJAyZODBo47E+67R4jV1/gzbAkEAklJaspRPXP877NssM5nAZMU0/O/NGCZ+3jPgDUno6WbJn5cqm8MqWhW1xGkImgRk+fkDBquiq4gPiT898jusgQJAd5Zrr6Q8AO/0isr/3aa6O6NLQxISLKcPDk2NOccAfS/xOtfOz4sJYM3+Bs4Io9+dZGSDCA54Lw03eHTNQghS0A==";
+        let der = base64::decode(der).unwrap();
+        let key = PKey::private_key_from_pkcs8(&der).unwrap().rsa().unwrap();
+        let signature_method = RsaSha1::new(key).unwrap();
+        let mut sign = (&signature_method).sign_with("", None);
+
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+        sign.parameter("file", "vacaction.jpg");
+        sign.delimiter();
+        sign.consumer_key("dpf43f3p2l4k3l03");
+        sign.delimiter();
+        sign.nonce("13917289812797014437");
+        sign.delimiter();
+        sign.signature_method();
+        sign.delimiter();
+        sign.timestamp(1196666512);
+        sign.delimiter();
+        sign.version();
+        sign.delimiter();
+        sign.parameter("size", "original");
+
+        let signature = sign.end();
+        let expected =
+            percent_encode("jvTp/wX1TYtByB1m+Pbyo0lnCOLIsyGCH7wke8AUs3BpnwZJtAuEJkvQL2/9n4s5wUmUl4aCI4BwpraNx4RtEXMe5qg5T1LVTGliMRpKasKsW//e+RinhejgCuzoH26dyF8iY2ZZ/5D1ilgeijhV/vBka5twt399mXwaYdCwFYE=")
+                .to_string();
+        assert_eq!(signature.to_string(), expected);
+    }
+
+    #[test]
+    fn sign_error() {
+        // A 255-bit RSA key, which is too small to hold a PKCS #1 v1.5 signature with a SHA-256
+        // digest. OpenSSL refuses to generate such a key, so it is assembled from its components.
+        let bn = |hex| BigNum::from_hex_str(hex).unwrap();
+        let key = Rsa::from_private_components(
+            bn("4000000000000000000000001d6f4ce2000000000000000000000b1cc9e15fa3"),
+            bn("10001"),
+            bn("2718d8e72718d8e72718d8e73914193f0aa4f55b0aa4f55b0aa4fc24c228cb81"),
+            bn("80000000000000000000000000003053"),
+            bn("8000000000000000000000003ade6971"),
+            bn("2d63529cad63529cad63529cad6363bf"),
+            bn("45a7ba5845a7ba5845a7ba5865b0c31"),
+            bn("73b34b41268e4cb62d4bc5fc2957932e"),
+        )
+        .unwrap();
+        let signature_method = RsaSha256::new(key).unwrap();
+        let mut sign = signature_method.sign_with("", None);
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fexample.com%2F");
+
+        let signature = sign.end();
+        assert!(matches!(signature.as_bytes(), Err(SignError::OpenSsl(_))));
+        assert!(signature.write_to(&mut String::new()).is_err());
+    }
+
+    #[test]
+    fn rsa_sha256() {
+        // Same as the test case of `rsa_sha256_06` module.
+
+        let der =
+            "MIICdgIBADANBgkqhkiG9w0BAQEFAASCAmAwggJcAgEAAoGBALRiMLAh9iimur8VA7qVvdqxevEuUkW4K+2KdMXmnQbG9Aa7k7eBjK1S+0LYmVjPKlJGNXHDGuy5Fw/d7rjVJ0BLB+ubPK8iA/Tw3hLQgXMRRGRXXCn8ikfuQfjUS1uZSatdLB81mydBETlJhI6GH4twrbDJCR2Bwy/XWXgqgGRzAgMBAAECgYBYWVtleUzavkbrPjy0T5FMou8HX9u2AC2ry8vD/l7cqedtwMPp9k7TubgNFo+NGvKsl2ynyprOZR1xjQ7WgrgVB+mmuScOM/5HVceFuGRDhYTCObE+y1kxRloNYXnx3ei1zbeYLPCHdhxRYW7T0qcynNmwrn05/KO2RLjgQNalsQJBANeA3Q4Nugqy4QBUCEC09SqylT2K9FrrItqL2QKc9v0ZzO2uwllCbg0dwpVuYPYXYvikNHHg+aCWF+VXsb9rpPsCQQDWR9TT4ORdzoj+NccnqkMsDmzt0EfNaAOwHOmVJ2RVBspPcxt5iN4HI7HNeG6U5YsFBb+/GZbgfBT3kpNGWPTpAkBI+gFhjfJvRw38n3g/+UeAkwMI2TJQS4n8+hid0uus3/zOjDySH3XHCUnocn1xOJAyZODBo47E+67R4jV1/gzbAkEAklJaspRPXP877NssM5nAZMU0/O/NGCZ+3jPgDUno6WbJn5cqm8MqWhW1xGkImgRk+fkDBquiq4gPiT898jusgQJAd5Zrr6Q8AO/0isr/3aa6O6NLQxISLKcPDk2NOccAfS/xOtfOz4sJYM3+Bs4Io9+dZGSDCA54Lw03eHTNQghS0A==";
+        let der = base64::decode(der).unwrap();
+        let key = PKey::private_key_from_pkcs8(&der).unwrap().rsa().unwrap();
+        let signature_method = RsaSha256::new(key).unwrap();
+        let mut sign = (&signature_method).sign_with("", None);
+
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+        sign.parameter("file", "vacaction.jpg");
+        sign.delimiter();
+        sign.consumer_key("dpf43f3p2l4k3l03");
+        sign.delimiter();
+        sign.nonce("13917289812797014437");
+        sign.delimiter();
+        sign.signature_method();
+        sign.delimiter();
+        sign.timestamp(1196666512);
+        sign.delimiter();
+        sign.version();
+        sign.delimiter();
+        sign.parameter("size", "original");
+
+        let signature = sign.end();
+        let expected =
+            percent_encode("kd2wjpiASoDx6BqAMHy/k8CqNdyZv4opl1U4bBN1EBi8vmSHRVfw4h72BDerCp7M8pNEZEAUtvTOhgGNPh04cMmTeB/89Ok8GxYqIqVeYfKK5buzrNoOOAhjRUFpTvNOChNbRnlE04/d3hAITJjjykQtIZ2FWxJ4JO0gOdfKY0c=")
+                .to_string();
+        assert_eq!(signature.to_string(), expected);
+    }
+}
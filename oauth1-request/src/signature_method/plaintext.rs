@@ -2,10 +2,12 @@
 //!
 //! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.4
 
-use core::fmt::{self, Debug, Display, Formatter, Write};
+use core::fmt::{self, Debug, Formatter, Write};
+use core::hash::{Hash, Hasher};
 use core::marker::PhantomData;
 
-use super::{write_signing_key, Sign, SignatureMethod};
+use super::{write_signing_key, DigestStrength, Sign, Signature, SignatureMethod};
+use crate::util::percent_encode;
 
 /// The `PLAINTEXT` signature method.
 pub struct Plaintext<
@@ -13,16 +15,19 @@ pub struct Plaintext<
     #[cfg(not(feature = "alloc"))] W,
 > {
     marker: PhantomData<fn() -> W>,
+    nonce_and_timestamp: bool,
 }
 
 cfg_type_param_hack! {
-    /// A `Sign` implementation that just returns the signing key used to construct it.
+    /// A `Sign` implementation that just returns the (percent-encoded) signing key used to
+    /// construct it.
     #[derive(Clone, Debug)]
     pub struct PlaintextSign<
         #[cfg(feature = "alloc")] W = alloc::string::String,
         #[cfg(not(feature = "alloc"))] W,
     > {
         signing_key: W,
+        nonce_and_timestamp: bool,
     }
 }
 
@@ -38,13 +43,16 @@ impl Plaintext {
         // cannot appear in constant functions directly as of Rust 1.57, but this somehow works.
         // cf. <https://github.com/rust-lang/rust/issues/67649>
         const MARKER: PhantomData<fn() -> alloc::string::String> = PhantomData;
-        Plaintext { marker: MARKER }
+        Plaintext {
+            marker: MARKER,
+            nonce_and_timestamp: true,
+        }
     }
 }
 
 impl<W> Plaintext<W>
 where
-    W: Default + Display + Write,
+    W: Default + Signature + Write,
 {
     // We separate constructors for the case of `W = String` and the generic case because
     // `Plaintext::new_with_buf` would result in a type inference error due to current limitation of
@@ -55,31 +63,81 @@ where
     pub fn with_buf() -> Self {
         Plaintext {
             marker: PhantomData,
+            nonce_and_timestamp: true,
         }
     }
 }
 
-impl<W> Clone for Plaintext<W> {
-    fn clone(&self) -> Self {
+impl<W> Plaintext<W> {
+    /// Returns a `Plaintext` that includes the `oauth_nonce` and `oauth_timestamp` parameters in
+    /// the requests if `enabled` is `true`, or omits them otherwise.
+    ///
+    /// The parameters are included by default. The OAuth standard ([RFC 5849 section 3.1.][rfc])
+    /// allows omitting them with `PLAINTEXT`, but OAuth Core 1.0 Revision A and some servers still
+    /// require them.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.1
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = " ```")]
+    #[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let plaintext = oauth::PLAINTEXT.with_nonce_and_timestamp(false);
+    ///
+    /// let authorization = oauth::get("https://example.com/", &(), &token, plaintext);
+    /// assert_eq!(
+    ///     authorization,
+    ///     "OAuth \
+    ///      oauth_consumer_key=\"consumer\",\
+    ///      oauth_signature_method=\"PLAINTEXT\",\
+    ///      oauth_token=\"token\",\
+    ///      oauth_signature=\"secret%26secret\"",
+    /// );
+    /// ```
+    pub fn with_nonce_and_timestamp(self, enabled: bool) -> Self {
         Plaintext {
             marker: PhantomData,
+            nonce_and_timestamp: enabled,
         }
     }
 }
 
+impl<W> Clone for Plaintext<W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
 impl<W> Copy for Plaintext<W> {}
 
 impl<W> Debug for Plaintext<W> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        #[derive(Debug)]
-        struct Plaintext;
-        Plaintext.fmt(f)
+        f.debug_struct("Plaintext")
+            .field("nonce_and_timestamp", &self.nonce_and_timestamp)
+            .finish()
+    }
+}
+
+impl<W> PartialEq for Plaintext<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nonce_and_timestamp == other.nonce_and_timestamp
+    }
+}
+
+impl<W> Eq for Plaintext<W> {}
+
+impl<W> Hash for Plaintext<W> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.nonce_and_timestamp.hash(state);
     }
 }
 
 impl<W> Default for Plaintext<W>
 where
-    W: Default + Display + Write,
+    W: Default + Signature + Write,
 {
     fn default() -> Self {
         Self::with_buf()
@@ -88,20 +146,43 @@ where
 
 impl<W> SignatureMethod for Plaintext<W>
 where
-    W: Default + Display + Write,
+    W: Default + Signature + Write,
 {
     type Sign = PlaintextSign<W>;
 
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
         let mut signing_key = W::default();
-        write_signing_key(&mut signing_key, client_secret, token_secret).unwrap();
-        PlaintextSign { signing_key }
+        write_signing_key(
+            &mut PercentEncodeWriter(&mut signing_key),
+            client_secret,
+            token_secret,
+        )
+        .unwrap();
+        PlaintextSign {
+            signing_key,
+            nonce_and_timestamp: self.nonce_and_timestamp,
+        }
+    }
+
+    fn sign_with_raw_key(self, key: &str) -> Self::Sign {
+        let mut signing_key = W::default();
+        PercentEncodeWriter(&mut signing_key)
+            .write_str(key)
+            .unwrap();
+        PlaintextSign {
+            signing_key,
+            nonce_and_timestamp: self.nonce_and_timestamp,
+        }
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::None
     }
 }
 
 impl<W> Sign for PlaintextSign<W>
 where
-    W: Display + Write,
+    W: Signature + Write,
 {
     type Signature = W;
 
@@ -115,6 +196,10 @@ where
 
     fn parameter<V>(&mut self, _key: &str, _value: V) {}
 
+    fn parameter_display_key<K, V>(&mut self, _key: K, _value: V) -> fmt::Result {
+        Ok(())
+    }
+
     fn delimiter(&mut self) {}
 
     fn end(self) -> W {
@@ -122,8 +207,120 @@ where
     }
 
     // The OAuth standard (section 3.1.) says that `oauth_timestamp` and `oauth_nonce` parameters
-    // MAY be omitted when using the `PLAINTEXT` signature method. So, technically, we could
-    // override `use_nonce` and `use_timestamp` so as not to use the parameters. However,
+    // MAY be omitted when using the `PLAINTEXT` signature method. However,
     // OAuth Core 1.0 Revision A (https://oauth.net/core/1.0a/) specification used to require these
-    // parameters. So, we don't override the methods here for compatibility's sake.
+    // parameters. So, we use them unless disabled with `Plaintext::with_nonce_and_timestamp`
+    // for compatibility's sake.
+
+    fn use_nonce(&self) -> bool {
+        self.nonce_and_timestamp
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.nonce_and_timestamp
+    }
+}
+
+doc_auto_cfg! {
+    /// A string buffer for [`Plaintext`] that wipes its contents from memory when dropped.
+    ///
+    /// Since a `PLAINTEXT` signature is the signing key itself, use
+    /// `Plaintext<ZeroizingString>` instead of [`PLAINTEXT`] to make sure the key doesn't linger
+    /// in memory. Note that the key is still written to the resulting `Authorization` header
+    /// value, which is up to you to wipe.
+    ///
+    /// The buffer is never reallocated in place. When it runs out of capacity, its contents are
+    /// moved to a larger buffer and the old one is wiped.
+    ///
+    /// This is only available when `zeroize` feature is activated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::signature_method::plaintext::{Plaintext, ZeroizingString};
+    /// use oauth::signature_method::{Sign, SignatureMethod};
+    ///
+    /// let signature = Plaintext::<ZeroizingString>::with_buf()
+    ///     .sign_with("secret", Some("token secret"))
+    ///     .end();
+    /// assert_eq!(signature.as_str(), "secret%26token%2520secret");
+    /// ```
+    #[cfg(feature = "zeroize")]
+    #[derive(Clone, Default, PartialEq, Eq)]
+    pub struct ZeroizingString {
+        buf: alloc::string::String,
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizingString {
+    /// Returns the contents of the buffer as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl AsRef<str> for ZeroizingString {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Debug for ZeroizingString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ZeroizingString")
+            .field(&crate::Secret(()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl fmt::Display for ZeroizingString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Signature for ZeroizingString {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        dst.write_str(&self.buf)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Write for ZeroizingString {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = self.buf.len() + s.len();
+        if len > self.buf.capacity() {
+            let capacity = core::cmp::max(len, 2 * self.buf.capacity());
+            let mut buf = alloc::string::String::with_capacity(capacity);
+            buf.push_str(&self.buf);
+            zeroize1::Zeroize::zeroize(&mut self.buf);
+            self.buf = buf;
+        }
+        self.buf.push_str(s);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ZeroizingString {
+    fn drop(&mut self) {
+        zeroize1::Zeroize::zeroize(&mut self.buf);
+    }
+}
+
+/// A `Write` adapter that percent-encodes the written string, since the signature needs to be
+/// percent-encoded like the other parameter values, while the signing key itself contains `&`.
+struct PercentEncodeWriter<'a, W>(&'a mut W);
+
+impl<'a, W: Write> Write for PercentEncodeWriter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        write!(self.0, "{}", percent_encode(s))
+    }
 }
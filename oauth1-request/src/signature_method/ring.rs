@@ -0,0 +1,483 @@
+//! The `HMAC-SHA1` signature method and `RSA-SHA1` signature verification backed by the [`ring`]
+//! crate.
+//!
+//! [`ring`]: https://docs.rs/ring/0.17
+//!
+//! This is useful if you are already depending on `ring` (e.g. through `rustls`) and want to avoid
+//! pulling in another crypto provider.
+//!
+//! Note that `ring` does not expose RSA signing with SHA-1, so this module only provides
+//! verification of `RSA-SHA1` signatures. Use the `rsa-sha1-06` or `openssl` feature to sign
+//! requests with `RSA-SHA1`.
+//!
+//! This module is only available when `ring` feature is activated.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use ring::digest;
+use ring::hmac::{self, Context, Tag, HMAC_SHA1_FOR_LEGACY_USE_ONLY};
+use ring::signature::{UnparsedPublicKey, RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY};
+
+use crate::util::{double_percent_encode_bytes, percent_encode, Chunked};
+use crate::Secret;
+
+use super::sha1_key::Sha1Key;
+use super::{
+    Base64Display, Base64PercentEncodeDisplay, DigestStrength, Sign, Signature, SignatureMethod,
+};
+
+/// The `HMAC-SHA1` signature method backed by `ring`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HmacSha1 {
+    hex: bool,
+}
+
+/// A type that signs a signature base string with the HMAC-SHA1 signature algorithm.
+#[derive(Clone, Debug)]
+pub struct HmacSha1Sign {
+    context: Context,
+    result: fmt::Result,
+    hex: bool,
+}
+
+/// An `HMAC-SHA1` key precomputed from the client and token secrets.
+///
+/// This is the `ring`-backed counterpart of
+/// [`hmac_sha1::HmacSha1Key`](super::hmac_sha1::HmacSha1Key). `&HmacSha1Key` implements
+/// `SignatureMethod`, ignoring the secrets passed to it.
+#[derive(Clone)]
+pub struct HmacSha1Key {
+    key: hmac::Key,
+    // `client_secret&`, or `None` if the key was created with `from_raw_key`.
+    client_key: Option<Sha1Key<digest::Context>>,
+    hex: bool,
+}
+
+/// A signature produced by an `HmacSha1Sign`.
+pub struct HmacSha1Signature {
+    inner: Result<Base64PercentEncodeDisplay<Tag>, fmt::Error>,
+    hex: bool,
+}
+
+/// An RSA public key to verify `RSA-SHA1` signatures with.
+#[derive(Clone, Debug)]
+pub struct RsaSha1PublicKey<B = Vec<u8>> {
+    key: B,
+}
+
+/// The `ring`-backed `HMAC-SHA1` signature method with a default configuration.
+pub const HMAC_SHA1: HmacSha1 = HmacSha1::new();
+
+impl HmacSha1 {
+    /// Creates a new `HmacSha1`.
+    pub const fn new() -> Self {
+        HmacSha1 { hex: false }
+    }
+
+    /// Makes the signature method emit the `oauth_signature` as lowercase hexadecimal digits
+    /// instead of Base64.
+    ///
+    /// See [`hmac_sha1::HmacSha1`](super::hmac_sha1::HmacSha1) for the caveat.
+    pub fn with_hex_output(mut self) -> Self {
+        self.hex = true;
+        self
+    }
+
+    /// Verifies an `oauth_signature` against a signature base string in constant time.
+    ///
+    /// `signature` is the raw signature, i.e. the Base64-decoded value of the `oauth_signature`
+    /// parameter.
+    pub fn verify(
+        self,
+        client_secret: &str,
+        token_secret: Option<&str>,
+        base_string: &[u8],
+        signature: &[u8],
+    ) -> bool {
+        let key = signing_key(client_secret, token_secret);
+        hmac::verify(&key, base_string, signature).is_ok()
+    }
+}
+
+impl Debug for HmacSha1 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSha1")
+            .field("hex_output", &self.hex)
+            .finish()
+    }
+}
+
+impl SignatureMethod for HmacSha1 {
+    type Sign = HmacSha1Sign;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSha1Sign {
+        HmacSha1Sign {
+            context: Context::with_key(&signing_key(client_secret, token_secret)),
+            result: Ok(()),
+            hex: self.hex,
+        }
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> HmacSha1Sign {
+        let key = hmac_key(signing_key.as_bytes());
+        HmacSha1Sign {
+            context: Context::with_key(&key),
+            result: Ok(()),
+            hex: self.hex,
+        }
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
+impl HmacSha1Key {
+    /// Creates a new `HmacSha1Key` from `client_secret` and `token_secret`, applying
+    /// percent-encoding to them.
+    pub fn new(client_secret: &str, token_secret: Option<&str>) -> Self {
+        let mut client_key = Sha1Key::new();
+        write!(client_key, "{}&", percent_encode(client_secret)).unwrap();
+        let mut ret = HmacSha1Key {
+            key: client_key.clone().finish(hmac_key),
+            client_key: Some(client_key),
+            hex: false,
+        };
+        if token_secret.is_some() {
+            ret.set_token_secret(token_secret);
+        }
+        ret
+    }
+
+    /// Creates a new `HmacSha1Key` from `signing_key` as-is.
+    pub fn from_raw_key(signing_key: &str) -> Self {
+        HmacSha1Key {
+            key: hmac_key(signing_key.as_bytes()),
+            client_key: None,
+            hex: false,
+        }
+    }
+
+    /// Replaces the token secret of the key, keeping the client secret.
+    ///
+    /// See [`hmac_sha1::HmacSha1Key::set_token_secret`][set] for the details.
+    ///
+    /// [set]: super::hmac_sha1::HmacSha1Key::set_token_secret
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the key has been created with [`from_raw_key`](HmacSha1Key::from_raw_key).
+    pub fn set_token_secret(&mut self, token_secret: Option<&str>) {
+        let mut key = self
+            .client_key
+            .clone()
+            .expect("`set_token_secret` called on a key created with `from_raw_key`");
+        if let Some(ts) = token_secret {
+            write!(key, "{}", percent_encode(ts)).unwrap();
+        }
+        self.key = key.finish(hmac_key);
+    }
+
+    /// Makes the signatures emit the `oauth_signature` as lowercase hexadecimal digits, like
+    /// [`HmacSha1::with_hex_output`].
+    pub fn with_hex_output(mut self) -> Self {
+        self.hex = true;
+        self
+    }
+
+    /// Creates an `HmacSha1Sign` that signs a signature base string with the key.
+    pub fn sign(&self) -> HmacSha1Sign {
+        HmacSha1Sign {
+            context: Context::with_key(&self.key),
+            result: Ok(()),
+            hex: self.hex,
+        }
+    }
+}
+
+impl Debug for HmacSha1Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HmacSha1Key").field(&Secret(())).finish()
+    }
+}
+
+impl SignatureMethod for &HmacSha1Key {
+    type Sign = HmacSha1Sign;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> HmacSha1Sign {
+        self.sign()
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> HmacSha1Sign {
+        self.sign()
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        DigestStrength::Sha1
+    }
+}
+
+impl Sign for HmacSha1Sign {
+    type Signature = HmacSha1Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        "HMAC-SHA1"
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.context.update(method.as_bytes());
+        self.context.update(b"&");
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        let context = &mut self.context;
+        let mut w = Chunked::new(|chunk| context.update(chunk));
+        let result = write!(w, "{}&", uri);
+        w.finish();
+        self.result = self.result.and(result);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.context.update(key.as_bytes());
+        self.context.update(b"%3D"); // '='
+        let context = &mut self.context;
+        let mut w = Chunked::new(|chunk| context.update(chunk));
+        let result = write!(w, "{}", value);
+        w.finish();
+        self.result = self.result.and(result);
+    }
+
+    fn parameter_display_key<K: Display, V: Display>(&mut self, key: K, value: V) -> fmt::Result {
+        let context = &mut self.context;
+        let mut w = Chunked::new(|chunk| context.update(chunk));
+        let result = write!(w, "{}%3D{}", key, value);
+        w.finish();
+        self.result = self.result.and(result);
+        Ok(())
+    }
+
+    fn parameter_bytes(&mut self, key: &str, value: &[u8]) {
+        self.context.update(key.as_bytes());
+        self.context.update(b"%3D"); // '='
+        let context = &mut self.context;
+        let _ = double_percent_encode_bytes(value, |chunk| {
+            context.update(chunk.as_bytes());
+            Ok(())
+        });
+    }
+
+    fn delimiter(&mut self) {
+        self.context.update(b"%26"); // '&'
+    }
+
+    fn end(self) -> HmacSha1Signature {
+        let context = self.context;
+        HmacSha1Signature {
+            inner: self
+                .result
+                .map(|()| Base64PercentEncodeDisplay(context.sign())),
+            hex: self.hex,
+        }
+    }
+}
+
+impl HmacSha1Signature {
+    /// Returns the raw HMAC-SHA1 digest.
+    ///
+    /// Returns an error if a `Display` implementation has failed while feeding the signature base
+    /// string, in which case formatting the signature fails as well.
+    pub fn as_bytes(&self) -> Result<&[u8], fmt::Error> {
+        match self.inner {
+            Ok(ref inner) => Ok(inner.0.as_ref()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Converts the signature into the raw HMAC-SHA1 digest.
+    ///
+    /// Returns an error under the same condition as [`as_bytes`](Self::as_bytes).
+    pub fn into_bytes(self) -> Result<[u8; 20], fmt::Error> {
+        let mut ret = [0; 20];
+        ret.copy_from_slice(self.as_bytes()?);
+        Ok(ret)
+    }
+
+    /// Returns a `Display` that writes the base64-encoded signature without percent-encoding,
+    /// i.e. the `oauth_signature` value as decoded from the request.
+    ///
+    /// Formatting it fails under the same condition as [`as_bytes`](Self::as_bytes).
+    pub fn to_base64(&self) -> impl Display + '_ {
+        Base64Display(self.as_bytes())
+    }
+
+    /// Checks whether the signature matches `encoded`, the percent-encoded base64 value of an
+    /// `oauth_signature` parameter, in constant time.
+    ///
+    /// See [`verify_signature`](super::verify_signature) for details.
+    pub fn verify(&self, encoded: &str) -> bool {
+        super::verify_signature(self, encoded)
+    }
+}
+
+impl Display for HmacSha1Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), f),
+            Ok(ref inner) => inner.fmt(f),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Signature for HmacSha1Signature {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        match self.inner {
+            Ok(ref inner) if self.hex => super::write_hex(inner.0.as_ref(), dst),
+            Ok(ref inner) => inner.write_to(dst),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<B: AsRef<[u8]>> RsaSha1PublicKey<B> {
+    /// Creates a new `RsaSha1PublicKey` from a DER-encoded `RSAPublicKey` structure
+    /// ([RFC 8017 appendix A.1.1.][rfc]).
+    ///
+    /// [rfc]: https://datatracker.ietf.org/doc/html/rfc8017#appendix-A.1.1
+    pub fn new(key: B) -> Self {
+        RsaSha1PublicKey { key }
+    }
+
+    /// Verifies an `oauth_signature` against a signature base string.
+    ///
+    /// `signature` is the raw signature, i.e. the Base64-decoded value of the `oauth_signature`
+    /// parameter.
+    pub fn verify(&self, base_string: &[u8], signature: &[u8]) -> bool {
+        UnparsedPublicKey::new(
+            &RSA_PKCS1_1024_8192_SHA1_FOR_LEGACY_USE_ONLY,
+            self.key.as_ref(),
+        )
+        .verify(base_string, signature)
+        .is_ok()
+    }
+}
+
+fn signing_key(client_secret: &str, token_secret: Option<&str>) -> hmac::Key {
+    Sha1Key::<digest::Context>::with_signing_key(client_secret, token_secret, hmac_key)
+}
+
+fn hmac_key(key: &[u8]) -> hmac::Key {
+    hmac::Key::new(HMAC_SHA1_FOR_LEGACY_USE_ONLY, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+    use alloc::string::ToString;
+
+    use crate::util::percent_encode;
+
+    use super::*;
+
+    const BASE_STRING: &str = "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacation.jpg%26oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3Dkllo9940pd9333jh%26oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D1191242096%26oauth_token%3Dnnch734d00sl2jdk%26oauth_version%3D1.0%26size%3Doriginal";
+    const SIGNATURE: &str = "tR3+Ty81lMeYAr/Fid0kMTYa/WM=";
+
+    #[test]
+    fn hmac_sha1() {
+        // Test case from <https://oauth.net/core/1.0a/#sig_base_example>.
+        let mut sign = HMAC_SHA1.sign_with("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+        sign.parameter("file", "vacation.jpg");
+        sign.delimiter();
+        sign.consumer_key("dpf43f3p2l4k3l03");
+        sign.delimiter();
+        sign.nonce("kllo9940pd9333jh");
+        sign.delimiter();
+        sign.signature_method();
+        sign.delimiter();
+        sign.timestamp(1191242096);
+        sign.delimiter();
+        sign.token("nnch734d00sl2jdk");
+        sign.delimiter();
+        sign.version();
+        sign.delimiter();
+        sign.parameter("size", "original");
+
+        let expected = percent_encode(SIGNATURE).to_string();
+        assert_eq!(sign.end().to_string(), expected);
+    }
+
+    #[test]
+    fn long_signing_key() {
+        // Keys longer than the block size are hashed while being written.
+        let secret = "&".repeat(40);
+        let sign = |mut sign: HmacSha1Sign| {
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.end().to_string()
+        };
+        assert_eq!(
+            sign(HMAC_SHA1.sign_with(&secret, Some("ts"))),
+            sign(HMAC_SHA1.sign_with_raw_key(&format!("{}&ts", "%26".repeat(40)))),
+        );
+    }
+
+    #[test]
+    fn set_token_secret() {
+        let sign = |mut sign: HmacSha1Sign| {
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.end().to_string()
+        };
+        let secret = "&".repeat(40);
+        let mut key = HmacSha1Key::new(&secret, None);
+        key.set_token_secret(Some("ts"));
+        assert_eq!(
+            sign(key.sign()),
+            sign(HMAC_SHA1.sign_with(&secret, Some("ts")))
+        );
+        key.set_token_secret(None);
+        assert_eq!(sign(key.sign()), sign(HMAC_SHA1.sign_with(&secret, None)));
+    }
+
+    #[test]
+    fn verify_hmac_sha1() {
+        let signature = base64::decode(SIGNATURE).unwrap();
+        let verify = |base_string: &str, token_secret| {
+            HMAC_SHA1.verify(
+                "kd94hf93k423kf44",
+                Some(token_secret),
+                base_string.as_bytes(),
+                &signature,
+            )
+        };
+        assert!(verify(BASE_STRING, "pfkkdhi9sl3r4s00"));
+        assert!(!verify(BASE_STRING, "pfkkdhi9sl3r4s01"));
+        assert!(!verify(
+            &BASE_STRING.replace("original", "large"),
+            "pfkkdhi9sl3r4s00"
+        ));
+    }
+
+    #[test]
+    fn verify_rsa_sha1() {
+        // Test case from <https://wiki.oauth.net/w/page/12238556/TestCases>.
+
+        let der = "MIGJAoGBALRiMLAh9iimur8VA7qVvdqxevEuUkW4K+2KdMXmnQbG9Aa7k7eBjK1S+0LYmVjPKlJGNXHDGuy5Fw/d7rjVJ0BLB+ubPK8iA/Tw3hLQgXMRRGRXXCn8ikfuQfjUS1uZSatdLB81mydBETlJhI6GH4twrbDJCR2Bwy/XWXgqgGRzAgMBAAE=";
+        let key = RsaSha1PublicKey::new(base64::decode(der).unwrap());
+
+        let base_string = "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacaction.jpg%26oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3D13917289812797014437%26oauth_signature_method%3DRSA-SHA1%26oauth_timestamp%3D1196666512%26oauth_version%3D1.0%26size%3Doriginal";
+        let signature = base64::decode("jvTp/wX1TYtByB1m+Pbyo0lnCOLIsyGCH7wke8AUs3BpnwZJtAuEJkvQL2/9n4s5wUmUl4aCI4BwpraNx4RtEXMe5qg5T1LVTGliMRpKasKsW//e+RinhejgCuzoH26dyF8iY2ZZ/5D1ilgeijhV/vBka5twt399mXwaYdCwFYE=").unwrap();
+
+        assert!(key.verify(base_string.as_bytes(), &signature));
+        assert!(!key.verify(
+            base_string.replace("original", "large").as_bytes(),
+            &signature
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+use core::fmt::{self, Write};
+
+/// The block size of SHA-1 in bytes.
+const BLOCK_SIZE: usize = 64;
+
+/// An incremental SHA-1 implementation of a crypto backend.
+pub trait Sha1Hasher {
+    type Output: AsRef<[u8]>;
+
+    fn new() -> Self;
+    fn update(&mut self, input: &[u8]);
+    fn finish(self) -> Self::Output;
+}
+
+/// A `Write` that builds an `HMAC-SHA1` key on the stack, hashing the key if it turns out to be
+/// longer than the block size of SHA-1 like HMAC does.
+///
+/// This is the counterpart of `digest_common::SigningKey` for the crypto backends that don't
+/// implement the RustCrypto traits.
+#[derive(Clone)]
+pub enum Sha1Key<H> {
+    Key { buf: [u8; BLOCK_SIZE], pos: usize },
+    Digest(H),
+}
+
+impl<H: Sha1Hasher> Sha1Key<H> {
+    pub fn new() -> Self {
+        Sha1Key::Key {
+            buf: [0; BLOCK_SIZE],
+            pos: 0,
+        }
+    }
+
+    /// Builds the key from `client_secret` and `token_secret`, and calls `f` with it.
+    pub fn with_signing_key<T, F>(client_secret: &str, token_secret: Option<&str>, f: F) -> T
+    where
+        F: FnOnce(&[u8]) -> T,
+    {
+        let mut key = Self::new();
+        super::write_signing_key(&mut key, client_secret, token_secret).unwrap();
+        key.finish(f)
+    }
+
+    pub fn write(&mut self, input: &[u8]) {
+        *self = match *self {
+            Sha1Key::Key {
+                ref mut buf,
+                ref mut pos,
+            } => {
+                if input.len() > buf.len() - *pos {
+                    let mut hasher = H::new();
+                    hasher.update(&buf[..*pos]);
+                    hasher.update(input);
+                    wipe(buf);
+                    Sha1Key::Digest(hasher)
+                } else {
+                    buf[*pos..(*pos + input.len())].copy_from_slice(input);
+                    *pos += input.len();
+                    return;
+                }
+            }
+            Sha1Key::Digest(ref mut hasher) => {
+                hasher.update(input);
+                return;
+            }
+        };
+    }
+
+    /// Calls `f` with the key and wipes it afterwards with `zeroize` feature.
+    pub fn finish<T, F>(self, f: F) -> T
+    where
+        F: FnOnce(&[u8]) -> T,
+    {
+        match self {
+            Sha1Key::Key { mut buf, pos } => {
+                let ret = f(&buf[..pos]);
+                wipe(&mut buf);
+                ret
+            }
+            Sha1Key::Digest(hasher) => {
+                let key = hasher.finish();
+                f(key.as_ref())
+            }
+        }
+    }
+}
+
+/// Wipes the key material in `buf` with `zeroize` feature.
+fn wipe(buf: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    zeroize1::Zeroize::zeroize(buf);
+    #[cfg(not(feature = "zeroize"))]
+    let _ = buf;
+}
+
+impl<H: Sha1Hasher> Write for Sha1Key<H> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl Sha1Hasher for openssl::sha::Sha1 {
+    type Output = [u8; 20];
+
+    fn new() -> Self {
+        openssl::sha::Sha1::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        openssl::sha::Sha1::update(self, input);
+    }
+
+    fn finish(self) -> [u8; 20] {
+        openssl::sha::Sha1::finish(self)
+    }
+}
+
+#[cfg(feature = "ring")]
+impl Sha1Hasher for ring::digest::Context {
+    type Output = ring::digest::Digest;
+
+    fn new() -> Self {
+        ring::digest::Context::new(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY)
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        ring::digest::Context::update(self, input);
+    }
+
+    fn finish(self) -> ring::digest::Digest {
+        ring::digest::Context::finish(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    struct Recorder(Vec<u8>);
+
+    impl Sha1Hasher for Recorder {
+        type Output = Vec<u8>;
+
+        fn new() -> Self {
+            Recorder(Vec::new())
+        }
+
+        fn update(&mut self, input: &[u8]) {
+            self.0.extend_from_slice(input);
+        }
+
+        fn finish(self) -> Vec<u8> {
+            let mut ret = b"hashed:".to_vec();
+            ret.extend(self.0);
+            ret
+        }
+    }
+
+    #[test]
+    fn hash_long_key() {
+        let mut k = Vec::new();
+        let mut key = Sha1Key::<Recorder>::new();
+        while k.len() < BLOCK_SIZE {
+            key.write(b"a");
+            k.extend_from_slice(b"a");
+        }
+        key.finish(|key| assert_eq!(key, &*k));
+
+        let mut key = Sha1Key::<Recorder>::new();
+        key.write(&k);
+        key.write(b"b");
+        k.extend_from_slice(b"b");
+        key.finish(|key| {
+            assert_eq!(&key[..7], b"hashed:");
+            assert_eq!(&key[7..], &*k);
+        });
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::string::String;
+use core::fmt::{self, Debug, Formatter};
+
+use crate::Secret;
+
+use super::signing_key_string;
+
+/// A signing key derived from the client and token secrets ([RFC 5849 section 3.4.2.][rfc]).
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.2
+///
+/// The key is created by [`signing_key_for`] and can be passed to
+/// [`SignatureMethod::sign_with_raw_key`](super::SignatureMethod::sign_with_raw_key). This lets
+/// you cache the key, e.g. per token in a connection pool, instead of percent-encoding the secrets
+/// on every request.
+///
+/// The `Debug` representation of the key doesn't reveal its value. With `zeroize` feature, the key
+/// is wiped from memory when the `SigningKey` is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SigningKey {
+    key: String,
+}
+
+/// Derives the signing key from `client_secret` and `token_secret`, applying percent-encoding to
+/// them.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{signing_key_for, Sign, SignatureMethod, HMAC_SHA1};
+///
+/// let key = signing_key_for("client secret", Some("token&secret"));
+/// assert_eq!(key.as_str(), "client%20secret&token%26secret");
+///
+/// let sign = |mut sign: <oauth::HmacSha1 as SignatureMethod>::Sign| {
+///     sign.request_method("GET");
+///     sign.uri("https%3A%2F%2Fexample.com%2F");
+///     sign.end().to_string()
+/// };
+/// assert_eq!(
+///     sign(HMAC_SHA1.sign_with_raw_key(key.as_str())),
+///     sign(HMAC_SHA1.sign_with("client secret", Some("token&secret"))),
+/// );
+/// ```
+pub fn signing_key_for(client_secret: &str, token_secret: Option<&str>) -> SigningKey {
+    SigningKey {
+        key: signing_key_string(client_secret, token_secret),
+    }
+}
+
+impl SigningKey {
+    /// Returns the signing key as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+}
+
+impl AsRef<str> for SigningKey {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        zeroize1::Zeroize::zeroize(&mut self.key);
+    }
+}
+
+impl Debug for SigningKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SigningKey").field(&Secret(())).finish()
+    }
+}
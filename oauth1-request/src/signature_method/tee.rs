@@ -0,0 +1,251 @@
+//! A signature method that signs a request with two signature methods at once.
+
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::param::OAuthParamKind;
+
+use super::{DigestStrength, Sign, Signature, SignatureMethod};
+
+/// A `SignatureMethod` that feeds the signature base string to two signature methods and
+/// produces both signatures.
+///
+/// This is useful for migrating to another signature method, signing requests with both the old
+/// and the new method during the cutover, or for capturing a debug representation (e.g. with
+/// `Identity`) alongside the real signature.
+///
+/// The `oauth_signature_method` name, `use_nonce` and `use_timestamp` are taken from `A`, and the
+/// `Display` implementation of the signature writes the signature of `A`, so a `Tee` used with a
+/// [`Builder`](crate::Builder) behaves like `A`. Each method still signs its own
+/// `oauth_signature_method` name, but `B` should use `oauth_nonce` and `oauth_timestamp` if and
+/// only if `A` does, since the parameters fed to both are the same otherwise.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{Sign, SignatureMethod, Tee};
+///
+/// let mut sign = Tee::new(oauth::HMAC_SHA1, oauth::HMAC_SHA1).sign_with("secret", None);
+/// sign.request_method("GET");
+/// sign.uri("https%3A%2F%2Fexample.com%2F");
+/// sign.consumer_key("consumer");
+/// let signature = sign.end();
+///
+/// assert_eq!(signature.first().to_string(), signature.second().to_string());
+/// assert_eq!(signature.to_string(), signature.first().to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+}
+
+/// A type that feeds the signature base string to two `Sign`s.
+#[derive(Clone, Debug)]
+pub struct TeeSign<A, B> {
+    first: A,
+    second: B,
+}
+
+/// A signature produced by a `TeeSign`, holding the signatures of both signature methods.
+#[derive(Clone, Debug)]
+pub struct TeeSignature<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: SignatureMethod, B: SignatureMethod> Tee<A, B> {
+    /// Creates a new `Tee` that signs requests with `first` and `second`.
+    pub fn new(first: A, second: B) -> Self {
+        Tee { first, second }
+    }
+
+    /// Unwraps the underlying signature methods.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: SignatureMethod, B: SignatureMethod> SignatureMethod for Tee<A, B> {
+    type Sign = TeeSign<A::Sign, B::Sign>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        TeeSign {
+            first: self.first.sign_with(client_secret, token_secret),
+            second: self.second.sign_with(client_secret, token_secret),
+        }
+    }
+
+    fn sign_with_raw_key(self, signing_key: &str) -> Self::Sign {
+        TeeSign {
+            first: self.first.sign_with_raw_key(signing_key),
+            second: self.second.sign_with_raw_key(signing_key),
+        }
+    }
+
+    fn is_symmetric(&self) -> bool {
+        self.first.is_symmetric()
+    }
+
+    fn digest_strength(&self) -> DigestStrength {
+        self.first.digest_strength()
+    }
+}
+
+macro_rules! forward {
+    ($(fn $method:ident(&mut self $(, $arg:ident: $typ:ty)*);)*) => {$(
+        fn $method(&mut self $(, $arg: $typ)*) {
+            self.first.$method($($arg),*);
+            self.second.$method($($arg),*);
+        }
+    )*};
+}
+
+impl<A: Sign, B: Sign> Sign for TeeSign<A, B> {
+    type Signature = TeeSignature<A::Signature, B::Signature>;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.first.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.first.request_method(method);
+        self.second.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.first.uri(&uri);
+        self.second.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.first.parameter(key, &value);
+        self.second.parameter(key, value);
+    }
+
+    fn parameter_display_key<K: Display, V: Display>(&mut self, key: K, value: V) -> fmt::Result {
+        let first = self.first.parameter_display_key(&key, &value);
+        let second = self.second.parameter_display_key(key, value);
+        first.and(second)
+    }
+
+    fn oauth_parameter<V: Display>(&mut self, kind: OAuthParamKind, value: V) {
+        self.first.oauth_parameter(kind, &value);
+        self.second.oauth_parameter(kind, value);
+    }
+
+    forward! {
+        fn parameter_bytes(&mut self, key: &str, value: &[u8]);
+        fn delimiter(&mut self);
+        fn signature_method(&mut self);
+        fn timestamp(&mut self, value: u64);
+        fn version(&mut self);
+    }
+
+    fn end(self) -> Self::Signature {
+        TeeSignature {
+            first: self.first.end(),
+            second: self.second.end(),
+        }
+    }
+
+    fn body_hash<V: Display>(&mut self, value: V) {
+        self.first.body_hash(&value);
+        self.second.body_hash(value);
+    }
+
+    fn callback<V: Display>(&mut self, value: V) {
+        self.first.callback(&value);
+        self.second.callback(value);
+    }
+
+    fn consumer_key<V: Display>(&mut self, value: V) {
+        self.first.consumer_key(&value);
+        self.second.consumer_key(value);
+    }
+
+    fn nonce<V: Display>(&mut self, value: V) {
+        self.first.nonce(&value);
+        self.second.nonce(value);
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.first.use_nonce()
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.first.use_timestamp()
+    }
+
+    fn token<V: Display>(&mut self, value: V) {
+        self.first.token(&value);
+        self.second.token(value);
+    }
+
+    fn verifier<V: Display>(&mut self, value: V) {
+        self.first.verifier(&value);
+        self.second.verifier(value);
+    }
+}
+
+impl<A, B> TeeSignature<A, B> {
+    /// Returns the signature of the first signature method.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the signature of the second signature method.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Returns the signatures of both signature methods.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: Display, B> Display for TeeSignature<A, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.first.fmt(f)
+    }
+}
+
+impl<A: Signature, B> Signature for TeeSignature<A, B> {
+    fn write_to<W: Write + ?Sized>(&self, dst: &mut W) -> fmt::Result {
+        self.first.write_to(dst)
+    }
+}
+
+#[cfg(all(test, feature = "alloc", feature = "hmac-sha1"))]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::signature_method::{Identity, HMAC_SHA1};
+
+    use super::*;
+
+    #[test]
+    fn both_signatures() {
+        fn sign<S: Sign>(mut sign: S) -> S::Signature {
+            sign.request_method("GET");
+            sign.uri("https%3A%2F%2Fexample.com%2F");
+            sign.parameter("a", "b%2520c");
+            sign.delimiter();
+            sign.signature_method();
+            sign.end()
+        }
+
+        let tee = Tee::new(HMAC_SHA1, Identity::new(HMAC_SHA1));
+        let signature = sign(tee.sign_with("cs", Some("ts")));
+        let expected = sign(HMAC_SHA1.sign_with("cs", Some("ts"))).to_string();
+        assert_eq!(signature.to_string(), expected);
+        assert_eq!(signature.first().to_string(), expected);
+        assert_eq!(
+            signature.second().to_string(),
+            "GET&https%3A%2F%2Fexample.com%2F&a%3Db%2520c%26oauth_signature_method%3DHMAC-SHA1",
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Signature methods for testing code that composes requests.
+//!
+//! This module is only available when `test` feature is activated.
+
+use core::fmt::{self, Display};
+
+use super::{Sign, SignatureMethod};
+
+/// A `SignatureMethod` that produces a fixed signature regardless of the request and the
+/// credentials.
+///
+/// This is meant for unit tests of code that builds requests, where the output needs to be
+/// predictable without pinning down the signature of a real signature method. The signature
+/// defaults to `"fake"` and the `oauth_signature_method` value to `"FAKE"`, and both can be
+/// replaced. The signature is written to the output verbatim, so it needs to be percent-encoded
+/// already.
+///
+/// Needless to say, requests signed with this method are rejected by servers.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::num::NonZeroU64;
+///
+/// use oauth::signature_method::test::Fake;
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let fake = Fake::with_signature("sig").name("HMAC-SHA1");
+/// let mut builder = oauth::Builder::with_token(token, fake);
+/// builder.nonce("nonce").timestamp(NonZeroU64::new(9999999999));
+///
+/// assert_eq!(
+///     builder.get("https://example.com/", &()),
+///     "OAuth \
+///      oauth_consumer_key=\"consumer\",\
+///      oauth_nonce=\"nonce\",\
+///      oauth_signature_method=\"HMAC-SHA1\",\
+///      oauth_timestamp=\"9999999999\",\
+///      oauth_token=\"token\",\
+///      oauth_signature=\"sig\"",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fake<'a> {
+    signature: &'a str,
+    name: &'static str,
+}
+
+/// A `Sign` that produces the signature of a [`Fake`].
+#[derive(Clone, Copy, Debug)]
+pub struct FakeSign<'a> {
+    fake: Fake<'a>,
+}
+
+/// The `Fake` signature method with the default signature.
+pub const FAKE: Fake<'static> = Fake::new();
+
+impl Fake<'static> {
+    /// Creates a `Fake` that produces `"fake"` as the signature.
+    pub const fn new() -> Self {
+        Fake::with_signature("fake")
+    }
+}
+
+impl<'a> Fake<'a> {
+    /// Creates a `Fake` that produces `signature` as the signature.
+    ///
+    /// `signature` must be percent-encoded.
+    pub const fn with_signature(signature: &'a str) -> Self {
+        Fake {
+            signature,
+            name: "FAKE",
+        }
+    }
+
+    /// Sets the `oauth_signature_method` value, e.g. to mimic a real signature method.
+    pub const fn name(self, name: &'static str) -> Self {
+        Fake {
+            signature: self.signature,
+            name,
+        }
+    }
+}
+
+impl Default for Fake<'static> {
+    fn default() -> Self {
+        Fake::new()
+    }
+}
+
+impl<'a> SignatureMethod for Fake<'a> {
+    type Sign = FakeSign<'a>;
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> FakeSign<'a> {
+        FakeSign { fake: self }
+    }
+
+    fn sign_with_raw_key(self, _signing_key: &str) -> FakeSign<'a> {
+        FakeSign { fake: self }
+    }
+}
+
+impl<'a> Sign for FakeSign<'a> {
+    type Signature = &'a str;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.fake.name
+    }
+
+    fn request_method(&mut self, _method: &str) {}
+
+    fn uri<T: Display>(&mut self, _uri: T) {}
+
+    fn parameter<V: Display>(&mut self, _key: &str, _value: V) {}
+
+    fn parameter_display_key<K: Display, V: Display>(&mut self, _key: K, _value: V) -> fmt::Result {
+        Ok(())
+    }
+
+    fn delimiter(&mut self) {}
+
+    fn end(self) -> &'a str {
+        self.fake.signature
+    }
+}
@@ -0,0 +1,130 @@
+//! Generation of strictly increasing `oauth_timestamp` values.
+//!
+//! This module is only available when `std` feature is activated.
+
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Formatter};
+use core::num::NonZeroU64;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::serializer::auth::get_current_timestamp;
+
+/// A generator of `oauth_timestamp` values that strictly increase across calls, even when the
+/// system clock steps backwards (e.g. adjusted by NTP).
+///
+/// Some providers reject a request whose timestamp is not greater than that of the previous
+/// request made with the same token. Keep a `TimestampSequencer` per token credentials and share
+/// it among the threads signing requests with the token.
+///
+/// The generated timestamp is the current time unless it is not greater than the previous one,
+/// in which case the previous timestamp plus one is used instead. So if you make more than one
+/// request per second on average, the timestamps drift ahead of the clock.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::timestamp::TimestampSequencer;
+///
+/// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+/// let sequencer = TimestampSequencer::new();
+/// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+///
+/// let first = sequencer.next();
+/// builder.timestamp(first);
+/// let authorization = builder.get("https://example.com/", &());
+///
+/// // The next request gets a greater timestamp even within the same second.
+/// let second = sequencer.next();
+/// assert!(second > first);
+/// builder.timestamp(second);
+/// let authorization = builder.get("https://example.com/", &());
+/// ```
+pub struct TimestampSequencer {
+    // Holds timestamps until 2106 even where `usize` is 32 bits wide.
+    last: AtomicUsize,
+}
+
+impl TimestampSequencer {
+    /// Creates a new `TimestampSequencer`.
+    pub const fn new() -> Self {
+        TimestampSequencer {
+            last: AtomicUsize::new(0),
+        }
+    }
+
+    /// Generates a timestamp greater than any generated before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timestamp would exceed `usize::MAX`.
+    pub fn next(&self) -> NonZeroU64 {
+        self.next_at(get_current_timestamp())
+    }
+
+    /// Same as [`next`](Self::next), but uses `now` (in seconds since the Unix epoch) as the
+    /// current time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timestamp would exceed `usize::MAX`.
+    pub fn next_at(&self, now: u64) -> NonZeroU64 {
+        // Saturate rather than truncate a `now` out of the range of `usize` so that it never
+        // makes the timestamp go backwards.
+        let now = usize::try_from(now).unwrap_or(usize::MAX);
+        let mut last = self.last.load(Ordering::Relaxed);
+        loop {
+            let next = if now > last {
+                now
+            } else {
+                last.checked_add(1).expect("timestamp overflowed `usize`")
+            };
+            match self
+                .last
+                .compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return NonZeroU64::new(next as u64).unwrap(),
+                Err(actual) => last = actual,
+            }
+        }
+    }
+}
+
+impl Debug for TimestampSequencer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimestampSequencer")
+            .field("last", &self.last.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl Default for TimestampSequencer {
+    fn default() -> Self {
+        TimestampSequencer::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monotonic() {
+        let sequencer = TimestampSequencer::new();
+        assert_eq!(sequencer.next_at(1000).get(), 1000);
+        assert_eq!(sequencer.next_at(1000).get(), 1001);
+        // The clock steps backwards.
+        assert_eq!(sequencer.next_at(900).get(), 1002);
+        assert_eq!(sequencer.next_at(2000).get(), 2000);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn overflow() {
+        let sequencer = TimestampSequencer::new();
+        sequencer.next_at(u64::MAX);
+        sequencer.next_at(u64::MAX);
+    }
+}
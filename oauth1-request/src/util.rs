@@ -1,8 +1,40 @@
+#[cfg(feature = "alloc")]
+mod auth_param;
+// Also used by the integration tests through `#[path]`.
+#[cfg(all(test, feature = "async"))]
+mod block_on;
+#[cfg(any(
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "rsa-sha256-06",
+    any(feature = "ring", feature = "rsa-sha1-06"),
+))]
+mod chunked;
 mod oauth_parameter;
 mod percent_encoding;
 
+#[cfg(feature = "alloc")]
+pub use self::auth_param::{split_auth_param, unescape, AuthParams};
+#[cfg(all(test, feature = "async"))]
+pub use self::block_on::block_on;
+#[cfg(any(
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "rsa-sha256-06",
+    any(feature = "ring", feature = "rsa-sha1-06"),
+))]
+pub use self::chunked::Chunked;
 pub use self::oauth_parameter::OAuthParameter;
-pub use self::percent_encoding::{percent_encode, DoublePercentEncode, PercentEncode};
+#[cfg(any(
+    feature = "ecdsa-p256-013",
+    feature = "hmac",
+    feature = "rsa-sha256-06",
+    any(feature = "ring", feature = "rsa-sha1-06"),
+))]
+pub use self::percent_encoding::double_percent_encode_bytes;
+pub use self::percent_encoding::{
+    percent_encode, DoublePercentEncode, DoublePercentEncodeBytes, PercentEncode,
+};
 
 /// Converts from `struct Foo<#[cfg(pred)] T>(T);` to
 /// `#[cfg(pred)] struct Foo<T>(T); #[cfg(not(pred))] struct Foo<>(T);` so that `#[derive]` work
@@ -140,7 +172,7 @@ macro_rules! impl_setters {
     };
     ($(#[$attr:meta])* $setter:ident: $t:ty, $($rest:tt)*) => {
         $(#[$attr])*
-        pub fn $setter(&mut self, $setter: impl Into<Option<$t>>) -> &mut Self {
+        pub fn $setter(&mut self, $setter: $t) -> &mut Self {
             self.$setter = $setter;
             self
         }
@@ -0,0 +1,114 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// An iterator over the `auth-param`s of an `Authorization` header, i.e. the comma-separated
+/// `key="value"` pairs following the authentication scheme.
+///
+/// Commas inside a quoted-string (like `realm="Example, Inc."`) don't separate the parameters.
+/// Empty elements are skipped.
+#[derive(Clone, Debug)]
+pub struct AuthParams<'a> {
+    rest: &'a str,
+}
+
+impl<'a> AuthParams<'a> {
+    pub fn new(params: &'a str) -> Self {
+        AuthParams { rest: params }
+    }
+}
+
+impl<'a> Iterator for AuthParams<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        while !self.rest.is_empty() {
+            let bytes = self.rest.as_bytes();
+            let mut quoted = false;
+            let mut i = 0;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'\\' if quoted => i += 1,
+                    b'"' => quoted = !quoted,
+                    b',' if !quoted => break,
+                    _ => {}
+                }
+                i += 1;
+            }
+            // `i` exceeds the length if the header ends with a backslash in a quoted-string.
+            let end = i.min(bytes.len());
+            let param = self.rest[..end].trim();
+            self.rest = self.rest.get(end + 1..).unwrap_or("");
+            if !param.is_empty() {
+                return Some(param);
+            }
+        }
+        None
+    }
+}
+
+/// Splits a `key="value"` pair of an `Authorization` header, returning the key and the contents
+/// of the quoted-string, which may still contain `\`-escapes (see [`unescape`]).
+///
+/// Returns `None` if the value is not a single well-formed quoted-string.
+pub fn split_auth_param(param: &str) -> Option<(&str, &str)> {
+    let i = param.find('=')?;
+    let value = param[i + 1..].trim_start().strip_prefix('"')?;
+    let bytes = value.as_bytes();
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => j += 1,
+            b'"' => break,
+            _ => {}
+        }
+        j += 1;
+    }
+    // The closing quote must end the parameter.
+    if j + 1 != bytes.len() {
+        return None;
+    }
+    Some((param[..i].trim_end(), &value[..j]))
+}
+
+/// Removes the `\`-escapes from the contents of a quoted-string.
+pub fn unescape(s: &str) -> Cow<'_, str> {
+    if !s.contains('\\') {
+        return Cow::Borrowed(s);
+    }
+    let mut ret = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            ret.extend(chars.next());
+        } else {
+            ret.push(c);
+        }
+    }
+    Cow::Owned(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn split() {
+        let params = AuthParams::new(r#" realm="a, \"b\", c\\",, k="v" ,x="#).collect::<Vec<_>>();
+        assert_eq!(params, [r#"realm="a, \"b\", c\\""#, r#"k="v""#, "x="]);
+
+        let (key, value) = split_auth_param(params[0]).unwrap();
+        assert_eq!(key, "realm");
+        assert_eq!(unescape(value), r#"a, "b", c\"#);
+        assert_eq!(split_auth_param(params[1]), Some(("k", "v")));
+        assert_eq!(split_auth_param("x="), None);
+        assert_eq!(split_auth_param(r#"x="a"b""#), None);
+        assert_eq!(split_auth_param(r#"x="a\""#), None);
+
+        assert_eq!(
+            AuthParams::new(r#"x="a\"#).collect::<Vec<_>>(),
+            [r#"x="a\"#]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use core::fmt::{self, Write};
+
+const CHUNK_SIZE: usize = 512;
+
+/// A `Write` that coalesces the writes into chunks of a bounded size before passing them to
+/// `sink`.
+///
+/// The percent-encoding adapters write their output in tiny pieces (down to a single `%2520`
+/// sequence per byte), which makes feeding a hash function with a multi-megabyte value
+/// unnecessarily slow. This buffers the pieces on the stack instead so that the memory usage stays
+/// bounded regardless of the length of the value.
+///
+/// Call `finish` to flush the remaining buffer.
+pub struct Chunked<F: FnMut(&[u8])> {
+    buf: [u8; CHUNK_SIZE],
+    len: usize,
+    sink: F,
+}
+
+impl<F: FnMut(&[u8])> Chunked<F> {
+    pub fn new(sink: F) -> Self {
+        Chunked {
+            buf: [0; CHUNK_SIZE],
+            len: 0,
+            sink,
+        }
+    }
+
+    pub fn finish(mut self) {
+        self.flush();
+    }
+
+    fn flush(&mut self) {
+        if self.len > 0 {
+            (self.sink)(&self.buf[..self.len]);
+            self.len = 0;
+        }
+    }
+}
+
+impl<F: FnMut(&[u8])> Write for Chunked<F> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let s = s.as_bytes();
+        if s.len() > CHUNK_SIZE - self.len {
+            self.flush();
+            if s.len() >= CHUNK_SIZE {
+                // The input is already large enough to be a chunk on its own.
+                (self.sink)(s);
+                return Ok(());
+            }
+        }
+        self.buf[self.len..(self.len + s.len())].copy_from_slice(s);
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn chunked() {
+        let mut chunks = Vec::new();
+        let mut expected = Vec::new();
+        let mut w = Chunked::new(|chunk: &[u8]| chunks.push(chunk.to_vec()));
+        for i in 0..1000 {
+            let s = if i % 100 == 0 {
+                "x".repeat(1000)
+            } else {
+                "%2520".into()
+            };
+            w.write_str(&s).unwrap();
+            expected.extend_from_slice(s.as_bytes());
+        }
+        w.finish();
+
+        assert!(chunks.iter().all(|c| !c.is_empty()));
+        assert!(chunks
+            .iter()
+            .all(|c| c.len() <= CHUNK_SIZE || c.iter().all(|&b| b == b'x')));
+        assert_eq!(chunks.concat(), expected);
+    }
+}
@@ -1,6 +1,6 @@
 use core::cmp::{Ordering, PartialEq, PartialOrd};
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OAuthParameter {
     Callback,
     ConsumerKey,
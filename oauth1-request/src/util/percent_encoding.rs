@@ -6,6 +6,8 @@ use percent_encoding::AsciiSet;
 
 pub struct DoublePercentEncode<D>(pub D);
 
+pub struct DoublePercentEncodeBytes<'a>(pub &'a [u8]);
+
 pub struct PercentEncode<D>(pub D);
 
 type BitBlock = u32;
@@ -67,6 +69,40 @@ impl<D: Display> Display for DoublePercentEncode<D> {
     }
 }
 
+impl<'a> Display for DoublePercentEncodeBytes<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        double_percent_encode_bytes(self.0, |s| f.write_str(s))
+    }
+}
+
+/// Calls `f` with the chunks of `input` percent-encoded twice, i.e. in the form of a parameter
+/// value in the signature base string.
+pub fn double_percent_encode_bytes<F>(input: &[u8], mut f: F) -> fmt::Result
+where
+    F: FnMut(&str) -> fmt::Result,
+{
+    let mut bytes = input;
+    while !bytes.is_empty() {
+        let i = bytes
+            .iter()
+            .position(|&b| should_percent_encode(b))
+            .unwrap_or(bytes.len());
+        if i > 0 {
+            let s = &bytes[..i];
+            debug_assert!(s.is_ascii());
+            f(unsafe { str::from_utf8_unchecked(s) })?;
+        }
+        match bytes.get(i) {
+            Some(&b) => {
+                f(double_encode_byte(b))?;
+                bytes = &bytes[i + 1..];
+            }
+            None => break,
+        }
+    }
+    Ok(())
+}
+
 impl<D: Display> Display for PercentEncode<D> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         struct Adapter<'a, 'b>(&'a mut Formatter<'b>);
@@ -136,6 +172,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn double_percent_encode_bytes() {
+        assert_eq!(
+            DoublePercentEncodeBytes(b"a b\xFF~/").to_string(),
+            "a%2520b%25FF~%252F",
+        );
+        assert_eq!(DoublePercentEncodeBytes(b"").to_string(), "");
+    }
+
     #[test]
     fn encode_map() {
         for b in 0..=0xFF {
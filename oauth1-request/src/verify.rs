@@ -0,0 +1,304 @@
+//! Helpers for consumers of signed requests.
+//!
+//! These complement the signature verification, which is specific to the signature method, with
+//! checks that OAuth 1.0 leaves up to the server.
+
+use core::fmt::{self, Display, Formatter};
+
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub mod gateway;
+}
+
+/// An error returned by [`check_timestamp`] and
+/// [`DeferredSignedRequest::check_expiry`](crate::deferred::DeferredSignedRequest::check_expiry).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TimestampError {
+    /// The URI has no `oauth_timestamp` parameter.
+    Missing,
+    /// The `oauth_timestamp` parameter is not a positive integer or appears more than once.
+    Malformed,
+    /// The timestamp is older than the max age.
+    Expired {
+        /// The `oauth_timestamp` value.
+        timestamp: u64,
+        /// The number of seconds the timestamp is older than the max age.
+        overdue: u64,
+    },
+    /// The timestamp is further in the future than the max age.
+    NotYetValid {
+        /// The `oauth_timestamp` value.
+        timestamp: u64,
+    },
+}
+
+/// A problem code of the OAuth Problem Reporting extension ([spec]), which tells the client why
+/// a request has been rejected.
+///
+/// Servers send the code in the `oauth_problem` parameter of the `WWW-Authenticate` header (see
+/// [`VerifyError::www_authenticate`](gateway::VerifyError::www_authenticate)).
+///
+/// [spec]: https://wiki.oauth.net/w/page/12238543/ProblemReporting
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Problem {
+    /// `version_rejected`: the `oauth_version` is not supported.
+    VersionRejected,
+    /// `parameter_absent`: a required parameter is missing.
+    ParameterAbsent,
+    /// `parameter_rejected`: a parameter is unexpected or malformed.
+    ParameterRejected,
+    /// `timestamp_refused`: the `oauth_timestamp` is out of the accepted range.
+    TimestampRefused,
+    /// `nonce_used`: the `oauth_nonce` has already been used.
+    NonceUsed,
+    /// `signature_method_rejected`: the `oauth_signature_method` is not supported.
+    SignatureMethodRejected,
+    /// `signature_invalid`: the `oauth_signature` is invalid.
+    SignatureInvalid,
+    /// `consumer_key_unknown`: the `oauth_consumer_key` is unknown to the server.
+    ConsumerKeyUnknown,
+    /// `consumer_key_rejected`: the client has been permanently banned.
+    ConsumerKeyRejected,
+    /// `consumer_key_refused`: the client is temporarily not allowed to make requests.
+    ConsumerKeyRefused,
+    /// `token_used`: the `oauth_token` has already been used.
+    TokenUsed,
+    /// `token_expired`: the `oauth_token` has expired.
+    TokenExpired,
+    /// `token_revoked`: the `oauth_token` has been revoked.
+    TokenRevoked,
+    /// `token_rejected`: the `oauth_token` is unknown or not acceptable.
+    TokenRejected,
+    /// `additional_authorization_required`: the token lacks the required permissions.
+    AdditionalAuthorizationRequired,
+    /// `permission_unknown`: the resource owner has not decided on the authorization yet.
+    PermissionUnknown,
+    /// `permission_denied`: the resource owner has refused the authorization.
+    PermissionDenied,
+    /// `user_refused`: the resource owner has refused the authorization.
+    UserRefused,
+}
+
+doc_auto_cfg! {
+    /// Checks the `oauth_timestamp` parameter embedded in the query of a signed URI (e.g. one
+    /// produced by [`Builder::to_query`](crate::Builder::to_query)) against the current time and
+    /// returns the timestamp if it is no older than `max_age` seconds.
+    ///
+    /// This makes pre-signed URLs expire, which plain OAuth 1.0 has no notion of. Timestamps up
+    /// to `max_age` seconds in the future are accepted as well to tolerate clock skew between the
+    /// signer and the consumer.
+    ///
+    /// Note that this does not verify the signature. A URL whose timestamp has been tampered with
+    /// has an invalid signature, so make sure to verify it as well.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(
+        all(feature = "hmac-sha1", feature = "std"),
+        doc = " ```"
+    )]
+    #[cfg_attr(
+        not(all(feature = "hmac-sha1", feature = "std")),
+        doc = " ```ignore"
+    )]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::verify::{check_timestamp, TimestampError};
+    ///
+    /// let token = oauth::Token::from_parts("consumer", "secret", "token", "secret");
+    /// let builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// let uri = builder.to_query("GET", "https://example.com/file".to_owned(), &());
+    ///
+    /// // The URL is valid for an hour.
+    /// assert!(check_timestamp(&uri, 60 * 60).is_ok());
+    ///
+    /// let expired = "https://example.com/file?oauth_timestamp=1234567890";
+    /// assert!(matches!(
+    ///     check_timestamp(expired, 60 * 60),
+    ///     Err(TimestampError::Expired { timestamp: 1234567890, .. }),
+    /// ));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn check_timestamp(uri: &str, max_age: u64) -> Result<u64, TimestampError> {
+        check_timestamp_at(
+            uri,
+            crate::serializer::auth::get_current_timestamp(),
+            max_age,
+        )
+    }
+}
+
+/// Same as [`check_timestamp`], but checks the timestamp against `now` (in seconds since the Unix
+/// epoch) instead of the current time.
+pub fn check_timestamp_at(uri: &str, now: u64, max_age: u64) -> Result<u64, TimestampError> {
+    check_age(find_timestamp(uri)?, now, max_age)
+}
+
+pub(crate) fn check_age(timestamp: u64, now: u64, max_age: u64) -> Result<u64, TimestampError> {
+    if timestamp < now {
+        let age = now - timestamp;
+        if age > max_age {
+            return Err(TimestampError::Expired {
+                timestamp,
+                overdue: age - max_age,
+            });
+        }
+    } else if timestamp - now > max_age {
+        return Err(TimestampError::NotYetValid { timestamp });
+    }
+    Ok(timestamp)
+}
+
+fn find_timestamp(uri: &str) -> Result<u64, TimestampError> {
+    let uri = uri.split('#').next().unwrap_or("");
+    let query = match uri.find('?') {
+        Some(i) => &uri[i + 1..],
+        None => return Err(TimestampError::Missing),
+    };
+
+    let mut values = query
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("oauth_timestamp="));
+    let value = values.next().ok_or(TimestampError::Missing)?;
+    if values.next().is_some() {
+        return Err(TimestampError::Malformed);
+    }
+    parse_timestamp(value)
+}
+
+pub(crate) fn parse_timestamp(value: &str) -> Result<u64, TimestampError> {
+    // `u64::from_str` accepts a leading `+`, which the OAuth standard does not allow.
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(TimestampError::Malformed);
+    }
+    match value.parse() {
+        Ok(0) | Err(_) => Err(TimestampError::Malformed),
+        Ok(timestamp) => Ok(timestamp),
+    }
+}
+
+impl Problem {
+    /// Returns the code of the problem, e.g. `"nonce_used"` for `Problem::NonceUsed`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Problem::VersionRejected => "version_rejected",
+            Problem::ParameterAbsent => "parameter_absent",
+            Problem::ParameterRejected => "parameter_rejected",
+            Problem::TimestampRefused => "timestamp_refused",
+            Problem::NonceUsed => "nonce_used",
+            Problem::SignatureMethodRejected => "signature_method_rejected",
+            Problem::SignatureInvalid => "signature_invalid",
+            Problem::ConsumerKeyUnknown => "consumer_key_unknown",
+            Problem::ConsumerKeyRejected => "consumer_key_rejected",
+            Problem::ConsumerKeyRefused => "consumer_key_refused",
+            Problem::TokenUsed => "token_used",
+            Problem::TokenExpired => "token_expired",
+            Problem::TokenRevoked => "token_revoked",
+            Problem::TokenRejected => "token_rejected",
+            Problem::AdditionalAuthorizationRequired => "additional_authorization_required",
+            Problem::PermissionUnknown => "permission_unknown",
+            Problem::PermissionDenied => "permission_denied",
+            Problem::UserRefused => "user_refused",
+        }
+    }
+
+    /// Returns the HTTP status code to respond with, as recommended by [RFC 5849 section 3.2][rfc].
+    ///
+    /// That is, `400 Bad Request` for malformed requests and unsupported parameters, and
+    /// `401 Unauthorized` for the other problems.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.2
+    pub fn status_code(self) -> u16 {
+        match self {
+            Problem::VersionRejected
+            | Problem::ParameterAbsent
+            | Problem::ParameterRejected
+            | Problem::SignatureMethodRejected => 400,
+            _ => 401,
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TimestampError {
+    /// Returns the Problem Reporting code corresponding to the error.
+    pub fn problem(&self) -> Problem {
+        match *self {
+            TimestampError::Missing => Problem::ParameterAbsent,
+            TimestampError::Malformed => Problem::ParameterRejected,
+            TimestampError::Expired { .. } | TimestampError::NotYetValid { .. } => {
+                Problem::TimestampRefused
+            }
+        }
+    }
+}
+
+impl Display for TimestampError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            TimestampError::Missing => f.write_str("missing `oauth_timestamp` parameter"),
+            TimestampError::Malformed => f.write_str("malformed `oauth_timestamp` parameter"),
+            TimestampError::Expired { overdue, .. } => {
+                write!(f, "the request has expired {} seconds ago", overdue)
+            }
+            TimestampError::NotYetValid { .. } => {
+                f.write_str("the request's timestamp is too far in the future")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimestampError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let uri =
+            "https://example.com/?a=b&oauth_timestamp=1000&oauth_timestamp_x=1#oauth_timestamp=1";
+        assert_eq!(check_timestamp_at(uri, 1000, 0), Ok(1000));
+        assert_eq!(check_timestamp_at(uri, 1060, 60), Ok(1000));
+        assert_eq!(check_timestamp_at(uri, 940, 60), Ok(1000));
+        assert_eq!(
+            check_timestamp_at(uri, 1061, 60),
+            Err(TimestampError::Expired {
+                timestamp: 1000,
+                overdue: 1,
+            }),
+        );
+        assert_eq!(
+            check_timestamp_at(uri, 939, 60),
+            Err(TimestampError::NotYetValid { timestamp: 1000 }),
+        );
+
+        for &uri in &[
+            "https://example.com/",
+            "https://example.com/?oauth_timestamp_x=1",
+            "https://example.com/#?oauth_timestamp=1",
+        ] {
+            assert_eq!(check_timestamp_at(uri, 0, 0), Err(TimestampError::Missing));
+        }
+        for &uri in &[
+            "https://example.com/?oauth_timestamp=",
+            "https://example.com/?oauth_timestamp=0",
+            "https://example.com/?oauth_timestamp=+1",
+            "https://example.com/?oauth_timestamp=1&oauth_timestamp=1",
+            "https://example.com/?oauth_timestamp=99999999999999999999",
+        ] {
+            assert_eq!(
+                check_timestamp_at(uri, 0, 0),
+                Err(TimestampError::Malformed)
+            );
+        }
+    }
+}
@@ -0,0 +1,1120 @@
+//! Verification of signed requests for API gateways that terminate OAuth 1.0.
+//!
+//! [`verify_request`] parses the `Authorization` header, looks up the credentials in a
+//! [`TokenStore`], verifies the signature and the timestamp, and returns the request's URI and
+//! body with the OAuth protocol parameters stripped, so that the request can be forwarded to an
+//! upstream service that knows nothing about OAuth.
+//!
+//! This module is only available when `alloc` feature is activated.
+//!
+//! ## Example
+//!
+#![cfg_attr(all(feature = "hmac-sha1", feature = "std"), doc = " ```")]
+#![cfg_attr(not(all(feature = "hmac-sha1", feature = "std")), doc = " ```ignore")]
+//! # extern crate oauth1_request as oauth;
+//! #
+//! use oauth::verify::gateway::{self, IncomingRequest, TokenStore};
+//!
+//! struct Store;
+//!
+//! impl TokenStore for Store {
+//!     type Secret = &'static str;
+//!
+//!     fn client_secret(&self, consumer_key: &str) -> Option<&'static str> {
+//!         if consumer_key == "consumer" {
+//!             Some("secret")
+//!         } else {
+//!             None
+//!         }
+//!     }
+//!
+//!     fn token_secret(&self, _consumer_key: &str, token: &str) -> Option<&'static str> {
+//!         if token == "token" {
+//!             Some("token_secret")
+//!         } else {
+//!             None
+//!         }
+//!     }
+//! }
+//!
+//! let request = oauth::ParameterList::new([("count", "10")]);
+//! let uri = "https://example.com/statuses";
+//! let token = oauth::Token::from_parts("consumer", "secret", "token", "token_secret");
+//! let authorization = oauth::get(uri, &request, &token, oauth::HMAC_SHA1);
+//! let uri = oauth::to_query(uri.to_owned(), &request);
+//!
+//! let request = IncomingRequest {
+//!     method: "GET",
+//!     uri: &uri,
+//!     authorization: Some(&authorization),
+//!     form_body: None,
+//! };
+//! let verified = gateway::verify_request(&request, &Store, oauth::HMAC_SHA1, 5 * 60).unwrap();
+//! assert_eq!(verified.consumer_key(), "consumer");
+//! assert_eq!(verified.token(), Some("token"));
+//! assert_eq!(verified.uri(), uri);
+//! ```
+
+use alloc::borrow::Cow;
+#[cfg(all(feature = "async", feature = "std"))]
+use alloc::boxed::Box;
+use alloc::string::String;
+#[cfg(all(feature = "async", feature = "std"))]
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+#[cfg(all(feature = "async", feature = "std"))]
+use core::future::Future;
+
+use percent_encoding::percent_decode_str;
+
+use crate::param;
+use crate::serializer::auth::{Audit, BaseStringUri, InsecureUriError, Options};
+use crate::signature_method::{self, Sign, SignatureMethod};
+use crate::util::{split_auth_param, unescape, AuthParams, PercentEncode};
+
+use super::{Problem, TimestampError};
+
+/// A source of the shared secrets of the clients and tokens known to the server.
+pub trait TokenStore {
+    /// The type of the secrets.
+    type Secret: AsRef<str>;
+
+    /// Returns the client secret of the client identified by `consumer_key`, or `None` if the
+    /// client is unknown.
+    fn client_secret(&self, consumer_key: &str) -> Option<Self::Secret>;
+
+    /// Returns the secret of `token`, or `None` if the token is unknown or has not been issued to
+    /// the client identified by `consumer_key`.
+    fn token_secret(&self, consumer_key: &str, token: &str) -> Option<Self::Secret>;
+
+    /// Records the `nonce` of a request with a valid signature and returns `false` if the nonce
+    /// has already been used with the same `consumer_key` and `timestamp`.
+    ///
+    /// The default implementation doesn't keep track of nonces and always returns `true`.
+    fn check_nonce(&self, consumer_key: &str, nonce: &str, timestamp: u64) -> bool {
+        let _ = (consumer_key, nonce, timestamp);
+        true
+    }
+
+    /// Returns `true` if a request to `uri` (without the query part) signed with `PLAINTEXT`
+    /// should be accepted.
+    ///
+    /// `PLAINTEXT` sends the secrets as they are, so the default implementation only accepts it
+    /// over `https`. Override this to accept it on some endpoints only, e.g. on the token
+    /// endpoints but not on the resource endpoints.
+    fn accept_plaintext(&self, method: &str, uri: &str) -> bool {
+        let _ = method;
+        matches!(uri.get(..8), Some(scheme) if scheme.eq_ignore_ascii_case("https://"))
+    }
+}
+
+/// The parts of an incoming request that are covered by the signature.
+#[derive(Clone, Copy, Debug)]
+pub struct IncomingRequest<'a> {
+    /// The HTTP request method.
+    pub method: &'a str,
+    /// The request URI, including the query part, if any.
+    pub uri: &'a str,
+    /// The `Authorization` header value, if any.
+    pub authorization: Option<&'a str>,
+    /// The request body if its content type is `application/x-www-form-urlencoded`.
+    pub form_body: Option<&'a str>,
+}
+
+/// A successfully verified request, returned by [`verify_request`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Verified {
+    consumer_key: String,
+    token: Option<String>,
+    timestamp: Option<u64>,
+    nonce: Option<String>,
+    signature_method: &'static str,
+    warnings: Vec<Warning>,
+    uri: String,
+    form_body: Option<String>,
+}
+
+/// A remark on a request that has been verified nonetheless, returned by
+/// [`Verified::warnings`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Warning {
+    /// The `oauth_timestamp` is within the last tenth of the `max_age` window, so the request
+    /// would have been rejected if it had arrived slightly later (or earlier, for a timestamp in
+    /// the future). This usually indicates clock skew between the client and the server.
+    TimestampNearWindowEdge,
+}
+
+/// An iterator over the parameters of an `Authorization` header, returned by
+/// [`parse_authorization`].
+#[derive(Clone, Debug)]
+pub struct AuthorizationParams<'a> {
+    pairs: AuthParams<'a>,
+}
+
+/// An error returned by [`verify_request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VerifyError {
+    /// The `Authorization` header is not a valid `OAuth` credentials.
+    MalformedHeader,
+    /// A required protocol parameter is missing.
+    MissingParameter(&'static str),
+    /// A protocol parameter appears more than once.
+    DuplicateParameter(&'static str),
+    /// The `oauth_signature_method` doesn't match the signature method of the server.
+    SignatureMethodMismatch,
+    /// The request is signed with `PLAINTEXT`, which
+    /// [`TokenStore::accept_plaintext`] rejected for the endpoint.
+    PlaintextRejected,
+    /// The `oauth_consumer_key` is unknown to the `TokenStore`.
+    UnknownConsumer,
+    /// The `oauth_token` is unknown to the `TokenStore`.
+    UnknownToken,
+    /// The `oauth_nonce` has already been used.
+    ReplayedNonce,
+    /// The `oauth_timestamp` is missing, malformed or out of the accepted range.
+    Timestamp(TimestampError),
+    /// The signature doesn't match the request.
+    InvalidSignature,
+    /// The request URI doesn't use `https` while
+    /// [`require_https`](Options::require_https) is set.
+    InsecureUri(InsecureUriError),
+}
+
+impl Verified {
+    /// Returns the `oauth_consumer_key` of the request.
+    pub fn consumer_key(&self) -> &str {
+        &self.consumer_key
+    }
+
+    /// Returns the `oauth_token` of the request, if any.
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns the `oauth_timestamp` of the request, if any.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    /// Returns the `oauth_nonce` of the request, if any.
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    /// Returns the name of the signature method the request has been signed with.
+    pub fn signature_method(&self) -> &'static str {
+        self.signature_method
+    }
+
+    /// Returns the remarks on the request, which didn't prevent it from being verified.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Returns the request URI with the OAuth protocol parameters removed from the query.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the request body with the OAuth protocol parameters removed, if the request had
+    /// a form body.
+    pub fn form_body(&self) -> Option<&str> {
+        self.form_body.as_deref()
+    }
+
+    /// Returns the consumer key, token, URI and form body of the request.
+    pub fn into_parts(self) -> (String, Option<String>, String, Option<String>) {
+        (self.consumer_key, self.token, self.uri, self.form_body)
+    }
+}
+
+doc_auto_cfg! {
+    /// Verifies a signed request against the credentials in `store` and returns the request with
+    /// the OAuth protocol parameters stripped.
+    ///
+    /// The protocol parameters are collected from the `Authorization` header, the query part of
+    /// the URI and the form body. `signature_method` is the signature method the server accepts,
+    /// and the timestamp must be within `max_age` seconds of the current time, like
+    /// [`check_timestamp`](super::check_timestamp).
+    #[cfg(feature = "std")]
+    pub fn verify_request<S, SM>(
+        request: &IncomingRequest<'_>,
+        store: &S,
+        signature_method: SM,
+        max_age: u64,
+    ) -> Result<Verified, VerifyError>
+    where
+        S: TokenStore + ?Sized,
+        SM: SignatureMethod,
+    {
+        verify_request_at(
+            request,
+            store,
+            signature_method,
+            crate::serializer::auth::get_current_timestamp(),
+            max_age,
+        )
+    }
+
+    /// A verification job passed to an [`Offload`] executor.
+    #[cfg(all(feature = "async", feature = "std"))]
+    pub type Job = Box<dyn FnOnce() -> Result<Verified, VerifyError> + Send + 'static>;
+
+    /// Executors that run verification jobs off the asynchronous event loop, e.g. on a thread
+    /// pool, used by [`verify_request_offloaded`].
+    ///
+    /// This is implemented for closures taking a [`Job`] and returning a `Future` of its result.
+    #[cfg(all(feature = "async", feature = "std"))]
+    pub trait Offload {
+        /// The future that resolves to the result of the job.
+        type Future: Future<Output = Result<Verified, VerifyError>>;
+
+        /// Runs `job`, returning a future of its result.
+        fn offload(&self, job: Job) -> Self::Future;
+    }
+
+    /// Same as [`verify_request`], but runs the verification with `executor`, so that
+    /// an asynchronous server isn't blocked by CPU-heavy signature methods like `RSA-SHA1`.
+    ///
+    /// The request is copied so that the job can be sent to another thread.
+    ///
+    /// ## Example
+    ///
+    /// Running the verification on Tokio's blocking thread pool:
+    ///
+    /// ```ignore
+    /// let offload = |job| async {
+    ///     tokio::task::spawn_blocking(job).await.expect("the verification panicked")
+    /// };
+    /// let verified =
+    ///     gateway::verify_request_offloaded(&request, store, signature_method, 300, &offload)
+    ///         .await?;
+    /// ```
+    #[cfg(all(feature = "async", feature = "std"))]
+    pub fn verify_request_offloaded<S, SM, E>(
+        request: &IncomingRequest<'_>,
+        store: Arc<S>,
+        signature_method: SM,
+        max_age: u64,
+        executor: &E,
+    ) -> E::Future
+    where
+        S: TokenStore + Send + Sync + ?Sized + 'static,
+        SM: SignatureMethod + Send + 'static,
+        E: Offload + ?Sized,
+    {
+        let method = String::from(request.method);
+        let uri = String::from(request.uri);
+        let authorization = request.authorization.map(String::from);
+        let form_body = request.form_body.map(String::from);
+        executor.offload(Box::new(move || {
+            let request = IncomingRequest {
+                method: &method,
+                uri: &uri,
+                authorization: authorization.as_deref(),
+                form_body: form_body.as_deref(),
+            };
+            verify_request(&request, &*store, signature_method, max_age)
+        }))
+    }
+}
+
+#[cfg(all(feature = "async", feature = "std"))]
+impl<F, Fut> Offload for F
+where
+    F: Fn(Job) -> Fut + ?Sized,
+    Fut: Future<Output = Result<Verified, VerifyError>>,
+{
+    type Future = Fut;
+
+    fn offload(&self, job: Job) -> Fut {
+        self(job)
+    }
+}
+
+/// Same as [`verify_request`], but checks the timestamp against `now` (in seconds since the Unix
+/// epoch) instead of the current time.
+pub fn verify_request_at<S, SM>(
+    request: &IncomingRequest<'_>,
+    store: &S,
+    signature_method: SM,
+    now: u64,
+    max_age: u64,
+) -> Result<Verified, VerifyError>
+where
+    S: TokenStore + ?Sized,
+    SM: SignatureMethod,
+{
+    verify_request_with_options(
+        request,
+        store,
+        signature_method,
+        &Options::new(),
+        now,
+        max_age,
+    )
+}
+
+/// Same as [`verify_request_at`], but verifies the request with `options`.
+///
+/// Requests to URIs that are rejected by [`Options::check_https`] fail with
+/// [`VerifyError::InsecureUri`], and the options affecting the signature base string URI (e.g.
+/// [`lowercase_path`](Options::lowercase_path)) are applied as when signing the request.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::auth::Options;
+/// use oauth::verify::gateway::{self, IncomingRequest, TokenStore, VerifyError};
+///
+/// struct Store;
+///
+/// impl TokenStore for Store {
+///     type Secret = &'static str;
+///
+///     fn client_secret(&self, _: &str) -> Option<&'static str> {
+///         Some("secret")
+///     }
+///
+///     fn token_secret(&self, _: &str, _: &str) -> Option<&'static str> {
+///         None
+///     }
+/// }
+///
+/// let mut options = Options::new();
+/// options.require_https(true);
+///
+/// let request = IncomingRequest {
+///     method: "GET",
+///     uri: "http://example.com/",
+///     authorization: Some(r#"OAuth oauth_consumer_key="consumer""#),
+///     form_body: None,
+/// };
+/// let result =
+///     gateway::verify_request_with_options(&request, &Store, oauth::HMAC_SHA1, &options, 0, 0);
+/// assert!(matches!(result, Err(VerifyError::InsecureUri(_))));
+/// ```
+pub fn verify_request_with_options<S, SM>(
+    request: &IncomingRequest<'_>,
+    store: &S,
+    signature_method: SM,
+    options: &Options<'_>,
+    now: u64,
+    max_age: u64,
+) -> Result<Verified, VerifyError>
+where
+    S: TokenStore + ?Sized,
+    SM: SignatureMethod,
+{
+    let uri = request.uri.split('#').next().unwrap_or("");
+    let path = uri.split('?').next().unwrap_or("");
+    let mut audit = Audit::verification(options, request.method, path);
+    let result = verify(
+        request,
+        store,
+        signature_method,
+        options,
+        now,
+        max_age,
+        &mut audit,
+    );
+    audit.emit_verification(result.as_ref().err().map(VerifyError::problem));
+    result
+}
+
+fn verify<S, SM>(
+    request: &IncomingRequest<'_>,
+    store: &S,
+    signature_method: SM,
+    options: &Options<'_>,
+    now: u64,
+    max_age: u64,
+    audit: &mut Audit,
+) -> Result<Verified, VerifyError>
+where
+    S: TokenStore + ?Sized,
+    SM: SignatureMethod,
+{
+    let uri = request.uri.split('#').next().unwrap_or("");
+    let (path, query) = match uri.find('?') {
+        Some(i) => (&uri[..i], Some(&uri[i + 1..])),
+        None => (uri, None),
+    };
+
+    options
+        .check_https(path)
+        .map_err(VerifyError::InsecureUri)?;
+
+    let mut params: Vec<(Cow<'_, str>, Cow<'_, str>)> = Vec::new();
+    if let Some(authorization) = request.authorization {
+        for param in parse_authorization(authorization)? {
+            params.push(param?);
+        }
+    }
+    for pairs in query.into_iter().chain(request.form_body) {
+        params.extend(form_pairs(pairs).map(|(_, k, v)| (k, v)));
+    }
+    for (key, _) in &params {
+        if key != param::SIGNATURE {
+            audit.parameter(key);
+        }
+    }
+
+    if let Some(key) = param::detect_duplicate(params.iter().map(|(k, _)| k)) {
+        return Err(VerifyError::DuplicateParameter(key));
+    }
+
+    let consumer_key = required(&params, param::CONSUMER_KEY)?;
+    audit.consumer_key(consumer_key);
+    let signature_method_name = required(&params, param::SIGNATURE_METHOD)?;
+    let signature = required(&params, param::SIGNATURE)?;
+    let token = single(&params, param::TOKEN);
+    let nonce = single(&params, param::NONCE);
+    let timestamp = single(&params, param::TIMESTAMP);
+
+    let client_secret = store
+        .client_secret(consumer_key)
+        .ok_or(VerifyError::UnknownConsumer)?;
+    let token_secret = match token {
+        Some(token) => Some(
+            store
+                .token_secret(consumer_key, token)
+                .ok_or(VerifyError::UnknownToken)?,
+        ),
+        None => None,
+    };
+    let mut sign = signature_method.sign_with(
+        client_secret.as_ref(),
+        token_secret.as_ref().map(AsRef::as_ref),
+    );
+
+    audit.signature_method(signature_method_name);
+    let sign_name = sign.get_signature_method_name();
+    if signature_method_name != sign_name {
+        return Err(VerifyError::SignatureMethodMismatch);
+    }
+    if signature_method_name == "PLAINTEXT" && !store.accept_plaintext(request.method, path) {
+        return Err(VerifyError::PlaintextRejected);
+    }
+    let timestamp = match timestamp {
+        Some(timestamp) => Some(
+            super::parse_timestamp(timestamp)
+                .and_then(|timestamp| super::check_age(timestamp, now, max_age))
+                .map_err(VerifyError::Timestamp)?,
+        ),
+        None if sign.use_timestamp() => {
+            return Err(VerifyError::Timestamp(TimestampError::Missing));
+        }
+        None => None,
+    };
+    if nonce.is_none() && sign.use_nonce() {
+        return Err(VerifyError::MissingParameter(param::NONCE));
+    }
+
+    // Construct the signature base string as in `PreparedRequest::finalize`.
+    let mut encoded: Vec<(String, String)> = params
+        .iter()
+        .filter(|&(k, _)| k != param::SIGNATURE)
+        .map(|(k, v)| (encode(k), encode(v)))
+        .collect();
+    encoded.sort();
+
+    sign.request_method(request.method);
+    sign.uri(PercentEncode(BaseStringUri::new(path, options)));
+    let mut key_buf = String::new();
+    for (i, (key, value)) in encoded.iter().enumerate() {
+        if i > 0 {
+            sign.delimiter();
+        }
+        key_buf.clear();
+        write!(key_buf, "{}", PercentEncode(key)).unwrap();
+        sign.parameter(&key_buf, PercentEncode(value));
+    }
+
+    if !signature_method::verify_signature(sign.end(), &encode(signature)) {
+        return Err(VerifyError::InvalidSignature);
+    }
+
+    if let (Some(nonce), Some(timestamp)) = (nonce, timestamp) {
+        if !store.check_nonce(consumer_key, nonce, timestamp) {
+            return Err(VerifyError::ReplayedNonce);
+        }
+    }
+
+    let mut forward_uri = String::with_capacity(uri.len());
+    forward_uri.push_str(path);
+    if let Some(query) = query {
+        let query = strip_protocol_parameters(query);
+        if !query.is_empty() {
+            forward_uri.push('?');
+            forward_uri.push_str(&query);
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(timestamp) = timestamp {
+        let skew = timestamp.max(now) - timestamp.min(now);
+        if skew > max_age - max_age / 10 {
+            warnings.push(Warning::TimestampNearWindowEdge);
+        }
+    }
+
+    Ok(Verified {
+        consumer_key: consumer_key.into(),
+        token: token.map(Into::into),
+        timestamp,
+        nonce: nonce.map(Into::into),
+        signature_method: sign_name,
+        warnings,
+        uri: forward_uri,
+        form_body: request.form_body.map(strip_protocol_parameters),
+    })
+}
+
+/// Parses an `OAuth` `Authorization` header value, returning an iterator over its
+/// percent-decoded parameters except `realm`.
+///
+/// The keys and values borrow from `header` unless they contain percent-encoded octets, so
+/// parsing a typical header doesn't allocate. Returns an error if the authentication scheme is not
+/// `OAuth`, and the iterator yields an error for a malformed parameter.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::borrow::Cow;
+///
+/// use oauth::verify::gateway;
+///
+/// let header = r#"OAuth realm="Example", oauth_consumer_key="key", oauth_signature="a%2Bb%3D""#;
+/// let params = gateway::parse_authorization(header)
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(params[0], ("oauth_consumer_key".into(), "key".into()));
+/// assert!(matches!(params[0].1, Cow::Borrowed(_)));
+/// assert_eq!(params[1], ("oauth_signature".into(), "a+b=".into()));
+/// ```
+pub fn parse_authorization(header: &str) -> Result<AuthorizationParams<'_>, VerifyError> {
+    let header = header.trim_start();
+    let scheme_len = header
+        .find(|c: char| c.is_ascii_whitespace())
+        .unwrap_or(header.len());
+    let (scheme, rest) = header.split_at(scheme_len);
+    if !scheme.eq_ignore_ascii_case("OAuth") {
+        return Err(VerifyError::MalformedHeader);
+    }
+    Ok(AuthorizationParams {
+        pairs: AuthParams::new(rest),
+    })
+}
+
+impl<'a> Iterator for AuthorizationParams<'a> {
+    type Item = Result<(Cow<'a, str>, Cow<'a, str>), VerifyError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let pair = self.pairs.next()?;
+            let (key, value) = match split_auth_param(pair) {
+                Some(kv) => kv,
+                None => return Some(Err(VerifyError::MalformedHeader)),
+            };
+            if key.eq_ignore_ascii_case("realm") {
+                continue;
+            }
+            let value = match unescape(value) {
+                Cow::Borrowed(value) => decode(value),
+                Cow::Owned(value) => Cow::Owned(decode(&value).into_owned()),
+            };
+            return Some(Ok((decode(key), value)));
+        }
+    }
+}
+
+/// Iterates over the pairs of an `application/x-www-form-urlencoded` string, yielding the raw
+/// pair and its decoded key and value.
+fn form_pairs(pairs: &str) -> impl Iterator<Item = (&str, Cow<'_, str>, Cow<'_, str>)> {
+    pairs
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = match pair.find('=') {
+                Some(i) => (&pair[..i], &pair[i + 1..]),
+                None => (pair, ""),
+            };
+            (pair, decode_form(k), decode_form(v))
+        })
+}
+
+fn strip_protocol_parameters(pairs: &str) -> String {
+    let mut ret = String::with_capacity(pairs.len());
+    for (pair, key, _) in form_pairs(pairs) {
+        if key.starts_with(param::PREFIX) {
+            continue;
+        }
+        if !ret.is_empty() {
+            ret.push('&');
+        }
+        ret.push_str(pair);
+    }
+    ret
+}
+
+fn decode(s: &str) -> Cow<'_, str> {
+    percent_decode_str(s).decode_utf8_lossy()
+}
+
+fn decode_form(s: &str) -> Cow<'_, str> {
+    if s.contains('+') {
+        decode(&s.replace('+', " ")).into_owned().into()
+    } else {
+        decode(s)
+    }
+}
+
+fn encode(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    write!(ret, "{}", PercentEncode(s)).unwrap();
+    ret
+}
+
+fn required<'a>(
+    params: &'a [(Cow<'_, str>, Cow<'_, str>)],
+    key: &'static str,
+) -> Result<&'a str, VerifyError> {
+    single(params, key).ok_or(VerifyError::MissingParameter(key))
+}
+
+// Duplicates have been rejected by `param::detect_duplicate` beforehand.
+fn single<'a>(params: &'a [(Cow<'_, str>, Cow<'_, str>)], key: &str) -> Option<&'a str> {
+    params.iter().find(|&(k, _)| k == key).map(|(_, v)| &**v)
+}
+
+impl VerifyError {
+    /// Returns the Problem Reporting code corresponding to the error.
+    pub fn problem(&self) -> Problem {
+        match *self {
+            VerifyError::MalformedHeader
+            | VerifyError::DuplicateParameter(_)
+            | VerifyError::InsecureUri(_) => Problem::ParameterRejected,
+            VerifyError::MissingParameter(_) => Problem::ParameterAbsent,
+            VerifyError::SignatureMethodMismatch | VerifyError::PlaintextRejected => {
+                Problem::SignatureMethodRejected
+            }
+            VerifyError::UnknownConsumer => Problem::ConsumerKeyUnknown,
+            VerifyError::UnknownToken => Problem::TokenRejected,
+            VerifyError::ReplayedNonce => Problem::NonceUsed,
+            VerifyError::Timestamp(ref e) => e.problem(),
+            VerifyError::InvalidSignature => Problem::SignatureInvalid,
+        }
+    }
+
+    /// Returns the protocol parameter the error is about, if any.
+    fn parameter(&self) -> Option<&'static str> {
+        match *self {
+            VerifyError::MissingParameter(key) | VerifyError::DuplicateParameter(key) => Some(key),
+            VerifyError::Timestamp(TimestampError::Missing)
+            | VerifyError::Timestamp(TimestampError::Malformed) => Some(param::TIMESTAMP),
+            _ => None,
+        }
+    }
+
+    /// Renders a `WWW-Authenticate` header value reporting the error to the client, with the
+    /// `oauth_problem` parameter and the `oauth_parameters_absent` or `oauth_parameters_rejected`
+    /// parameter where applicable.
+    ///
+    /// Respond with the status code of [`Problem::status_code`] of [`problem`](Self::problem).
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = " ```")]
+    #[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::verify::gateway::VerifyError;
+    ///
+    /// let e = VerifyError::MissingParameter(oauth::param::NONCE);
+    /// assert_eq!(e.problem().status_code(), 400);
+    /// assert_eq!(
+    ///     e.www_authenticate(Some("Example")),
+    ///     "OAuth realm=\"Example\",\
+    ///      oauth_problem=\"parameter_absent\",\
+    ///      oauth_parameters_absent=\"oauth_nonce\"",
+    /// );
+    /// ```
+    pub fn www_authenticate(&self, realm: Option<&str>) -> String {
+        let mut ret = String::from("OAuth ");
+        if let Some(realm) = realm {
+            ret.push_str("realm=\"");
+            for c in realm.chars() {
+                if c == '"' || c == '\\' {
+                    ret.push('\\');
+                }
+                ret.push(c);
+            }
+            ret.push_str("\",");
+        }
+        let problem = self.problem();
+        write!(ret, "oauth_problem=\"{}\"", problem).unwrap();
+        if let Some(key) = self.parameter() {
+            let name = match problem {
+                Problem::ParameterAbsent => "oauth_parameters_absent",
+                _ => "oauth_parameters_rejected",
+            };
+            write!(ret, ",{}=\"{}\"", name, PercentEncode(key)).unwrap();
+        }
+        ret
+    }
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            VerifyError::MalformedHeader => f.write_str("malformed `Authorization` header"),
+            VerifyError::MissingParameter(key) => write!(f, "missing `{}` parameter", key),
+            VerifyError::DuplicateParameter(key) => write!(f, "duplicate `{}` parameter", key),
+            VerifyError::SignatureMethodMismatch => f.write_str("unsupported signature method"),
+            VerifyError::PlaintextRejected => {
+                f.write_str("`PLAINTEXT` is not accepted for the endpoint")
+            }
+            VerifyError::UnknownConsumer => f.write_str("unknown consumer key"),
+            VerifyError::UnknownToken => f.write_str("unknown token"),
+            VerifyError::ReplayedNonce => f.write_str("the nonce has already been used"),
+            VerifyError::Timestamp(ref e) => e.fmt(f),
+            VerifyError::InvalidSignature => f.write_str("invalid signature"),
+            VerifyError::InsecureUri(_) => f.write_str("the request was not sent over `https`"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            VerifyError::Timestamp(ref e) => Some(e),
+            VerifyError::InsecureUri(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "hmac-sha1"))]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use crate::{Builder, ParameterList, Request, Token, HMAC_SHA1};
+
+    use super::*;
+
+    struct Store;
+
+    impl TokenStore for Store {
+        type Secret = &'static str;
+
+        fn client_secret(&self, consumer_key: &str) -> Option<&'static str> {
+            if consumer_key == "ck" {
+                Some("cs")
+            } else {
+                None
+            }
+        }
+
+        fn token_secret(&self, _: &str, token: &str) -> Option<&'static str> {
+            if token == "tk" {
+                Some("ts")
+            } else {
+                None
+            }
+        }
+
+        fn check_nonce(&self, _: &str, nonce: &str, _: u64) -> bool {
+            nonce != "used"
+        }
+    }
+
+    fn sign<R: Request>(nonce: &str, request: &R) -> String {
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let mut builder = Builder::with_token(token, HMAC_SHA1);
+        builder.nonce(nonce).timestamp(NonZeroU64::new(1000));
+        builder.post("https://Example.com/a", request)
+    }
+
+    #[test]
+    fn verify() {
+        // The query parameters are covered by the signature as well as the form body.
+        let request = ParameterList::new([("b", "c d"), ("e", ""), ("f", "g h"), ("i", "+")]);
+        let authorization = sign("nonce", &request);
+        let form = "f=g+h&i=%2B";
+        let mut incoming = IncomingRequest {
+            method: "POST",
+            uri: "https://Example.com/a?b=c%20d&e&oauth_x=y#fragment",
+            authorization: Some(&authorization),
+            form_body: Some(form),
+        };
+
+        // `oauth_x=y` is not covered by the signature, so it is rejected.
+        assert_eq!(
+            verify_request_at(&incoming, &Store, HMAC_SHA1, 1000, 0),
+            Err(VerifyError::InvalidSignature),
+        );
+
+        incoming.uri = "https://Example.com/a?b=c%20d&e#fragment";
+        let verified = verify_request_at(&incoming, &Store, HMAC_SHA1, 1000, 0).unwrap();
+        assert_eq!(verified.consumer_key(), "ck");
+        assert_eq!(verified.token(), Some("tk"));
+        assert_eq!(verified.timestamp(), Some(1000));
+        assert_eq!(verified.nonce(), Some("nonce"));
+        assert_eq!(verified.signature_method(), "HMAC-SHA1");
+        assert_eq!(verified.warnings(), []);
+        assert_eq!(verified.uri(), "https://Example.com/a?b=c%20d&e");
+        assert_eq!(verified.form_body(), Some("f=g+h&i=%2B"));
+
+        let verified = verify_request_at(&incoming, &Store, HMAC_SHA1, 1091, 100).unwrap();
+        assert_eq!(verified.warnings(), [Warning::TimestampNearWindowEdge]);
+        let verified = verify_request_at(&incoming, &Store, HMAC_SHA1, 1090, 100).unwrap();
+        assert_eq!(verified.warnings(), []);
+
+        assert_eq!(
+            verify_request_at(&incoming, &Store, HMAC_SHA1, 1001, 0),
+            Err(VerifyError::Timestamp(TimestampError::Expired {
+                timestamp: 1000,
+                overdue: 1,
+            })),
+        );
+        incoming.method = "PUT";
+        assert_eq!(
+            verify_request_at(&incoming, &Store, HMAC_SHA1, 1000, 0),
+            Err(VerifyError::InvalidSignature),
+        );
+
+        let authorization = sign("used", &request);
+        incoming.method = "POST";
+        incoming.authorization = Some(&authorization);
+        assert_eq!(
+            verify_request_at(&incoming, &Store, HMAC_SHA1, 1000, 0),
+            Err(VerifyError::ReplayedNonce),
+        );
+    }
+
+    #[test]
+    fn require_https() {
+        let request = ParameterList::new([("a", "b")]);
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let mut builder = Builder::with_token(token, HMAC_SHA1);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1000));
+        let mut options = Options::new();
+        options
+            .require_https(true)
+            .http_allowed_hosts(&["localhost"]);
+        let check = |uri: &str, options: &Options<'_>| {
+            let authorization = builder.post(uri, &request);
+            let incoming = IncomingRequest {
+                method: "POST",
+                uri,
+                authorization: Some(&authorization),
+                form_body: Some("a=b"),
+            };
+            verify_request_with_options(&incoming, &Store, HMAC_SHA1, options, 1000, 0)
+        };
+
+        assert!(check("https://example.com/", &options).is_ok());
+        assert!(check("http://localhost/", &options).is_ok());
+        assert!(check("http://example.com/", &Options::new()).is_ok());
+        assert!(matches!(
+            check("http://example.com/", &options),
+            Err(VerifyError::InsecureUri(_)),
+        ));
+    }
+
+    #[test]
+    fn plaintext_policy() {
+        struct TokenEndpointOnly;
+
+        impl TokenStore for TokenEndpointOnly {
+            type Secret = &'static str;
+
+            fn client_secret(&self, consumer_key: &str) -> Option<&'static str> {
+                Store.client_secret(consumer_key)
+            }
+
+            fn token_secret(&self, consumer_key: &str, token: &str) -> Option<&'static str> {
+                Store.token_secret(consumer_key, token)
+            }
+
+            fn accept_plaintext(&self, method: &str, uri: &str) -> bool {
+                method == "POST" && uri == "https://example.com/token"
+            }
+        }
+
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let check = |store: &dyn TokenStore<Secret = &'static str>, uri: &str| {
+            let authorization = crate::post(uri, &(), &token, crate::PLAINTEXT);
+            let incoming = IncomingRequest {
+                method: "POST",
+                uri,
+                authorization: Some(&authorization),
+                form_body: None,
+            };
+            verify_request_at(&incoming, store, crate::PLAINTEXT, 1000, u64::MAX)
+        };
+
+        assert!(check(&Store, "https://example.com/resource").is_ok());
+        assert_eq!(
+            check(&Store, "http://example.com/resource"),
+            Err(VerifyError::PlaintextRejected),
+        );
+        assert!(check(&TokenEndpointOnly, "https://example.com/token").is_ok());
+        assert_eq!(
+            check(&TokenEndpointOnly, "https://example.com/resource"),
+            Err(VerifyError::PlaintextRejected),
+        );
+    }
+
+    #[test]
+    fn query() {
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let builder = Builder::with_token(token, HMAC_SHA1);
+        let request = ParameterList::new([("a", "b")]);
+        let mut uri = builder.to_query("GET", String::from("https://example.com/"), &request);
+        uri.push_str("&a=b");
+        let incoming = IncomingRequest {
+            method: "GET",
+            uri: &uri,
+            authorization: None,
+            form_body: None,
+        };
+        let verified = verify_request_at(&incoming, &Store, HMAC_SHA1, 1000, u64::MAX).unwrap();
+        assert_eq!(verified.uri(), "https://example.com/?a=b");
+    }
+
+    #[cfg(all(feature = "async", feature = "std"))]
+    #[test]
+    fn offloaded() {
+        use alloc::sync::Arc;
+        use core::future::ready;
+        use std::thread;
+
+        let request = ParameterList::new([("a", "b")]);
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let authorization = crate::get("https://example.com/", &request, &token, HMAC_SHA1);
+        let incoming = IncomingRequest {
+            method: "GET",
+            uri: "https://example.com/?a=b",
+            authorization: Some(&authorization),
+            form_body: None,
+        };
+        let main = thread::current().id();
+        let offload = |job: Job| {
+            ready(
+                thread::spawn(move || {
+                    assert_ne!(thread::current().id(), main);
+                    job()
+                })
+                .join()
+                .unwrap(),
+            )
+        };
+        let verified = crate::util::block_on(verify_request_offloaded(
+            &incoming,
+            Arc::new(Store),
+            HMAC_SHA1,
+            60,
+            &offload,
+        ))
+        .unwrap();
+        assert_eq!(verified.uri(), "https://example.com/?a=b");
+    }
+
+    #[test]
+    fn parse_realm() {
+        let token = Token::from_parts("ck", "cs", "tk", "ts");
+        let mut builder = Builder::with_token(token, HMAC_SHA1);
+        builder.realm("Example, Inc. \"\\o/\"");
+        let header = builder.get("https://example.com/", &());
+        let params = parse_authorization(&header)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(params[0], ("oauth_consumer_key".into(), "ck".into()));
+        assert_eq!(params.len(), 6);
+
+        let header = r#"OAuth oauth_consumer_key="a\,b""#;
+        let params = parse_authorization(header)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(params, [("oauth_consumer_key".into(), "a,b".into())]);
+    }
+
+    #[test]
+    fn malformed() {
+        let check = |authorization: &str| {
+            let incoming = IncomingRequest {
+                method: "GET",
+                uri: "https://example.com/",
+                authorization: Some(authorization),
+                form_body: None,
+            };
+            verify_request_at(&incoming, &Store, HMAC_SHA1, 0, 0)
+        };
+        assert_eq!(check("Basic abc"), Err(VerifyError::MalformedHeader));
+        assert_eq!(
+            check(r#"OAuth oauth_consumer_key=ck"#),
+            Err(VerifyError::MalformedHeader),
+        );
+        assert_eq!(
+            check(r#"OAuth realm="x""#),
+            Err(VerifyError::MissingParameter(param::CONSUMER_KEY)),
+        );
+        assert_eq!(
+            check(r#"OAuth realm="x,oauth_consumer_key="ck""#),
+            Err(VerifyError::MalformedHeader),
+        );
+        assert_eq!(
+            check(r#"OAuth oauth_consumer_key="ck",oauth_consumer_key="ck""#),
+            Err(VerifyError::DuplicateParameter(param::CONSUMER_KEY)),
+        );
+        assert_eq!(
+            check(
+                r#"oauth oauth_consumer_key="x",oauth_signature_method="HMAC-SHA1",oauth_signature="""#
+            ),
+            Err(VerifyError::UnknownConsumer),
+        );
+        assert_eq!(
+            check(
+                r#"OAuth oauth_consumer_key="ck",oauth_signature_method="PLAINTEXT",oauth_signature="""#
+            ),
+            Err(VerifyError::SignatureMethodMismatch),
+        );
+    }
+
+    #[test]
+    fn problem() {
+        let e = VerifyError::Timestamp(TimestampError::NotYetValid { timestamp: 1 });
+        assert_eq!(e.problem(), Problem::TimestampRefused);
+        assert_eq!(e.problem().status_code(), 401);
+        assert_eq!(
+            e.www_authenticate(Some(r#"a "quoted" realm"#)),
+            r#"OAuth realm="a \"quoted\" realm",oauth_problem="timestamp_refused""#,
+        );
+        assert_eq!(
+            VerifyError::Timestamp(TimestampError::Missing).www_authenticate(None),
+            r#"OAuth oauth_problem="parameter_absent",oauth_parameters_absent="oauth_timestamp""#,
+        );
+        assert_eq!(
+            VerifyError::DuplicateParameter(param::TOKEN).www_authenticate(None),
+            r#"OAuth oauth_problem="parameter_rejected",oauth_parameters_rejected="oauth_token""#,
+        );
+        assert_eq!(
+            VerifyError::InvalidSignature.www_authenticate(None),
+            r#"OAuth oauth_problem="signature_invalid""#,
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers shared by the integration tests.
+
+// Each test crate uses a different subset of the helpers.
+#![allow(dead_code)]
+
+use std::num::NonZeroU64;
+
+use oauth1_request::{Builder, Credentials};
+
+#[cfg(feature = "async")]
+#[path = "../../src/util/block_on.rs"]
+pub mod block_on;
+
+/// Creates a `Builder` with the client credentials and a fixed nonce and timestamp.
+pub fn pinned_builder<SM>(signature_method: SM) -> Builder<'static, SM, &'static str> {
+    let mut builder = Builder::new(Credentials::new("ck", "cs"), signature_method);
+    builder
+        .nonce("nonce")
+        .timestamp(NonZeroU64::new(1500000000));
+    builder
+}
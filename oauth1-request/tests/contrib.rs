@@ -0,0 +1,156 @@
+//! Tests for the typed requests in `contrib` module against recorded `Authorization` headers.
+
+#![cfg(all(feature = "contrib", feature = "hmac-sha1"))]
+
+mod common;
+
+use oauth1_request::contrib::{discogs, tumblr};
+use oauth1_request::{Credentials, HMAC_SHA1, PLAINTEXT};
+
+use self::common::pinned_builder;
+
+const CALLBACK: &str = "https://client.example.net/cb";
+
+fn token() -> Credentials<&'static str> {
+    Credentials::new("tk", "ts")
+}
+
+#[test]
+fn tumblr() {
+    // Temporary credentials.
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder.callback(CALLBACK);
+    assert_eq!(
+        builder.post(tumblr::REQUEST_TOKEN_URI, &()),
+        "OAuth \
+         oauth_callback=\"https%3A%2F%2Fclient.example.net%2Fcb\",\
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"HMAC-SHA1\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_signature=\"c%2BBRP203S%2FBNPCVJACnIBvJhupI%3D\"",
+    );
+
+    // Token credentials.
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder
+        .token(Credentials::new("rtk", "rts"))
+        .verifier("verifier");
+    assert_eq!(
+        builder.post(tumblr::ACCESS_TOKEN_URI, &()),
+        "OAuth \
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"HMAC-SHA1\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_token=\"rtk\",\
+         oauth_verifier=\"verifier\",\
+         oauth_signature=\"%2BNE5eEt4F%2BNmSGCydSWwl9oE0CU%3D\"",
+    );
+
+    // Protected resources.
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder.token(token());
+
+    let dashboard = tumblr::Dashboard {
+        limit: Some(5),
+        kind: Some("text"),
+        reblog_info: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        builder.get(tumblr::Dashboard::URI, &dashboard),
+        "OAuth \
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"HMAC-SHA1\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_token=\"tk\",\
+         oauth_signature=\"FUgXlnTx9nd83VXetVPlJDixbK4%3D\"",
+    );
+    assert_eq!(
+        oauth1_request::to_query(tumblr::Dashboard::URI.to_owned(), &dashboard),
+        "https://api.tumblr.com/v2/user/dashboard?limit=5&reblog_info=true&type=text",
+    );
+
+    let post = tumblr::CreatePost {
+        blog_identifier: "example.tumblr.com",
+        kind: "text",
+        title: Some("Hello"),
+        body: Some("<p>Hello, world!</p>"),
+        tags: Some("rust,oauth"),
+        ..Default::default()
+    };
+    assert_eq!(
+        post.uri(),
+        "https://api.tumblr.com/v2/blog/example.tumblr.com/post",
+    );
+    assert_eq!(
+        builder.post(post.uri(), &post),
+        "OAuth \
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"HMAC-SHA1\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_token=\"tk\",\
+         oauth_signature=\"%2BjSZ5BbJrQD%2FFZHxtCKuEVfooCs%3D\"",
+    );
+    assert_eq!(
+        oauth1_request::to_form(&post),
+        "body=%3Cp%3EHello%2C%20world%21%3C%2Fp%3E&tags=rust%2Coauth&title=Hello&type=text",
+    );
+}
+
+#[test]
+fn discogs() {
+    let mut builder = pinned_builder(PLAINTEXT);
+    builder.callback(CALLBACK);
+    assert_eq!(
+        builder.get(discogs::REQUEST_TOKEN_URI, &()),
+        "OAuth \
+         oauth_callback=\"https%3A%2F%2Fclient.example.net%2Fcb\",\
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"PLAINTEXT\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_signature=\"cs%26\"",
+    );
+
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder.token(token());
+
+    let search = discogs::Search {
+        q: Some("Nirvana"),
+        kind: Some("release"),
+        per_page: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(
+        builder.get(discogs::Search::URI, &search),
+        "OAuth \
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"HMAC-SHA1\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_token=\"tk\",\
+         oauth_signature=\"p3dJ8ipxfxZzqa9yk9jcx1oIafw%3D\"",
+    );
+
+    let folders = discogs::CollectionFolders {
+        username: "some user",
+    };
+    assert_eq!(
+        folders.uri(),
+        "https://api.discogs.com/users/some%20user/collection/folders",
+    );
+    assert_eq!(
+        builder.get(folders.uri(), &folders),
+        "OAuth \
+         oauth_consumer_key=\"ck\",\
+         oauth_nonce=\"nonce\",\
+         oauth_signature_method=\"HMAC-SHA1\",\
+         oauth_timestamp=\"1500000000\",\
+         oauth_token=\"tk\",\
+         oauth_signature=\"dkbLaFhkAvEK2rA7NUMwEoc%2B5NE%3D\"",
+    );
+}
@@ -0,0 +1,231 @@
+//! Tests checking that the alternative ways of signing a request produce the same output as
+//! signing it with `Builder` directly.
+
+#![cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+
+mod common;
+
+use oauth1_request::serializer::auth::SignaturePosition;
+use oauth1_request::signature_method::{AnyMethod, BoxSignatureMethod, Identity, SignatureMethod};
+use oauth1_request::{Builder, Credentials, ParameterList, HMAC_SHA1, PLAINTEXT};
+
+const CALLBACK: &str = "https://client.example.net/cb";
+const URI: &str = "https://example.com/path";
+
+/// Same as `common::pinned_builder`, but with the token credentials set as well.
+fn pinned_builder<SM>(signature_method: SM) -> Builder<'static, SM, &'static str> {
+    let mut builder = common::pinned_builder(signature_method);
+    builder.token(Credentials::new("tk", "ts"));
+    builder
+}
+
+#[test]
+fn boxed() {
+    let mut builder = pinned_builder(BoxSignatureMethod::new(HMAC_SHA1));
+    builder.callback(CALLBACK);
+    let mut expected = pinned_builder(HMAC_SHA1);
+    expected.callback(CALLBACK);
+
+    let request = ParameterList::new([("a", "b c")]);
+    assert_eq!(builder.post(URI, &request), expected.post(URI, &request));
+}
+
+#[test]
+fn any_method() {
+    fn post<SM: SignatureMethod + Clone>(signature_method: SM) -> String {
+        pinned_builder(signature_method).post(URI, &ParameterList::new([("a", "b c")]))
+    }
+
+    assert_eq!(post(AnyMethod::from(HMAC_SHA1)), post(HMAC_SHA1));
+    assert_eq!(post(AnyMethod::from(PLAINTEXT)), post(PLAINTEXT));
+}
+
+#[test]
+fn identity() {
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder.callback(CALLBACK);
+    let request = ParameterList::new([("a%20b", "c d"), ("z", "1")]);
+    let expected = builder.prepare("POST", URI, &request).base_string();
+
+    let mut builder = pinned_builder(Identity::new(HMAC_SHA1));
+    builder.callback(CALLBACK);
+    let authorization = builder.post(URI, &request);
+    let signature = authorization
+        .split("oauth_signature=\"")
+        .nth(1)
+        .unwrap()
+        .trim_end_matches('"');
+    assert_eq!(signature, expected);
+}
+
+#[test]
+fn map_params() {
+    let builder = pinned_builder(HMAC_SHA1);
+    let request = ParameterList::new([("a%20b", "c"), ("z", "1")]);
+    let expected = builder.get(URI, &request);
+
+    let builder = builder.map_params(|params| params.push(("a%20b".into(), "c".into())));
+    let request = ParameterList::new([("z", "1")]);
+    assert_eq!(builder.get(URI, &request), expected);
+}
+
+#[test]
+fn authorize_each() {
+    let builder = pinned_builder(HMAC_SHA1);
+    let requests = [
+        ParameterList::new([("part", "0")]),
+        ParameterList::new([("part", "1")]),
+    ];
+    let authorizations = builder
+        .authorize_each("PUT", requests.iter().map(|r| (URI, r)))
+        .collect::<Vec<_>>();
+    let expected = requests
+        .iter()
+        .map(|r| builder.authorize("PUT", URI, r))
+        .collect::<Vec<_>>();
+    assert_eq!(authorizations, expected);
+}
+
+#[test]
+fn prepare() {
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder.callback(CALLBACK);
+    let request = ParameterList::new([("a%20b", "c d"), ("z", "1")]);
+
+    let prepared = builder.prepare("POST", URI, &request);
+    assert_eq!(prepared.nonce(), Some("nonce"));
+    assert_eq!(prepared.timestamp(), Some(1500000000));
+    assert_eq!(prepared.finalize().unwrap(), builder.post(URI, &request));
+
+    builder
+        .realm("Example")
+        .signature_position(SignaturePosition::First);
+    let authorization = builder.prepare("POST", URI, &request).finalize().unwrap();
+    assert!(authorization.starts_with(r#"OAuth realm="Example",oauth_signature=""#));
+    assert_eq!(authorization, builder.post(URI, &request));
+}
+
+#[test]
+fn prepare_with_curl() {
+    let builder = pinned_builder(HMAC_SHA1);
+    let request = ParameterList::new([("a", "b c")]);
+
+    let (authorization, curl) = builder
+        .prepare("POST", URI, &request)
+        .finalize_with_curl()
+        .unwrap();
+    assert_eq!(authorization, builder.post(URI, &request));
+    assert_eq!(
+        curl,
+        format!(
+            "curl -X 'POST' -H 'Authorization: {}' --data-raw 'a=b%20c' '{}'",
+            authorization.replace(r#"oauth_token="tk""#, r#"oauth_token="<redacted>""#),
+            URI,
+        ),
+    );
+}
+
+#[test]
+fn to_pairs() {
+    let mut builder = pinned_builder(HMAC_SHA1);
+    builder.version(true);
+    let request = ParameterList::new([("a", "x y"), ("z", "1")]);
+
+    let pairs = builder.to_pairs("POST", URI, &request);
+    let authorization = builder.post(URI, &request);
+    for (k, v) in &pairs {
+        if k.starts_with("oauth_") {
+            assert!(authorization.contains(&*format!(r#"{}="{}""#, k, v)));
+        }
+    }
+    assert_eq!(pairs.len(), 9);
+    assert_eq!((&*pairs[0].0, &*pairs[0].1), ("a", "x%20y"));
+    assert_eq!((&*pairs[8].0, &*pairs[8].1), ("z", "1"));
+}
+
+#[cfg(feature = "async")]
+mod async_sign {
+    use std::convert::Infallible;
+    use std::future::{ready, Ready};
+
+    use oauth1_request::signature_method::{
+        AsyncSign, AsyncSignatureMethod, BaseStringSign, SignBaseString, SignatureMethod,
+    };
+    use oauth1_request::ParameterList;
+
+    use super::common::block_on::block_on;
+    use super::{pinned_builder, URI};
+
+    /// A signature method that uses the signature base string as the signature.
+    #[derive(Clone, Copy)]
+    struct Echo;
+
+    impl SignatureMethod for Echo {
+        type Sign = BaseStringSign<Echo>;
+
+        fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+            BaseStringSign::new(self)
+        }
+
+        fn sign_with_raw_key(self, _signing_key: &str) -> Self::Sign {
+            BaseStringSign::new(self)
+        }
+    }
+
+    impl SignBaseString for Echo {
+        type Signature = String;
+
+        fn get_signature_method_name(&self) -> &'static str {
+            "ECHO"
+        }
+
+        fn sign_base_string(self, base_string: &str) -> String {
+            base_string.into()
+        }
+    }
+
+    impl AsyncSignatureMethod for Echo {
+        type Sign = Echo;
+
+        fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Echo {
+            self
+        }
+
+        fn sign_with_raw_key(self, _signing_key: &str) -> Echo {
+            self
+        }
+    }
+
+    impl AsyncSign for Echo {
+        type Signature = String;
+        type Error = Infallible;
+        type Future = Ready<Result<String, Infallible>>;
+
+        fn get_signature_method_name(&self) -> &'static str {
+            "ECHO"
+        }
+
+        fn finish(self, base_string: String) -> Self::Future {
+            ready(Ok(base_string))
+        }
+    }
+
+    #[test]
+    fn same_as_sync() {
+        let builder = pinned_builder(Echo);
+        let request = ParameterList::new([("foo", "bar"), ("q", "a b")]);
+
+        assert_eq!(
+            block_on(builder.authorize_async("GET", URI, &request)).unwrap(),
+            builder.authorize("GET", URI, &request),
+        );
+        assert_eq!(
+            block_on(builder.to_form_async("POST", URI, &request)).unwrap(),
+            builder.to_form("POST", URI, &request),
+        );
+        assert_eq!(
+            block_on(builder.to_query_async("GET", String::from(URI), &request)).unwrap(),
+            builder.to_query("GET", String::from(URI), &request),
+        );
+    }
+}